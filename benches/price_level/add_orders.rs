@@ -1,5 +1,7 @@
 use criterion::{BenchmarkId, Criterion};
-use pricelevel::{Order, OrderCommon, OrderId, PegReferenceType, PriceLevel, Side, TimeInForce};
+use pricelevel::{
+    IcebergRefresh, Order, OrderCommon, OrderId, PegReferenceType, PriceLevel, Side, TimeInForce,
+};
 use std::hint::black_box;
 
 /// Register all benchmarks for adding orders to a price level
@@ -12,7 +14,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
             let mut price_level = PriceLevel::new(10000);
             for i in 0..100 {
                 let order = create_standard_order(i, 10000, 100);
-                black_box(price_level.add_order(order));
+                black_box(price_level.add_order(order).unwrap());
             }
         })
     });
@@ -23,7 +25,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
             let mut price_level = PriceLevel::new(10000);
             for i in 0..100 {
                 let order = create_iceberg_order(i, 10000, 50, 150);
-                black_box(price_level.add_order(order));
+                black_box(price_level.add_order(order).unwrap());
             }
         })
     });
@@ -34,7 +36,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
             let mut price_level = PriceLevel::new(10000);
             for i in 0..100 {
                 let order = create_reserve_order(i, 10000, 50, 150, 10, true, None);
-                black_box(price_level.add_order(order));
+                black_box(price_level.add_order(order).unwrap());
             }
         })
     });
@@ -51,7 +53,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
                     3 => create_reserve_order(i, 10000, 50, 150, 10, true, None),
                     _ => create_pegged_order(i, 10000, 100),
                 };
-                black_box(price_level.add_order(order));
+                black_box(price_level.add_order(order).unwrap());
             }
         })
     });
@@ -66,7 +68,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
                     let mut price_level = PriceLevel::new(10000);
                     for i in 0..order_count {
                         let order = create_standard_order(i, 10000, 100);
-                        black_box(price_level.add_order(order));
+                        black_box(price_level.add_order(order).unwrap());
                     }
                 })
             },
@@ -89,6 +91,9 @@ fn create_standard_order(id: u64, price: u64, quantity: u64) -> Order<()> {
             timestamp: 1616823000000,
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
     }
 }
@@ -104,8 +109,14 @@ fn create_iceberg_order(id: u64, price: u64, visible: u64, hidden: u64) -> Order
             timestamp: 1616823000000,
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
         reserve_quantity: hidden,
+        original_display: visible,
+        refresh_strategy: IcebergRefresh::default(),
+        last_reveal_ms: None,
     }
 }
 
@@ -120,6 +131,9 @@ fn create_post_only_order(id: u64, price: u64, quantity: u64) -> Order<()> {
             timestamp: 1616823000000,
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
     }
 }
@@ -143,11 +157,18 @@ fn create_reserve_order(
             timestamp: 1616823000000,
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
         reserve_quantity: hidden,
         replenish_threshold: threshold,
         replenish_amount,
         auto_replenish,
+        auto_replenish_interval_ms: None,
+        last_reveal_ms: None,
+        max_total_display: None,
+        revealed_quantity: 0,
     }
 }
 
@@ -162,6 +183,9 @@ fn create_pegged_order(id: u64, price: u64, quantity: u64) -> Order<()> {
             timestamp: 1616823000000,
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
         // Reference price offset (can be positive or negative)
         reference_price_offset: -10,
@@ -0,0 +1,80 @@
+use criterion::{BatchSize, BenchmarkId, Criterion};
+use pricelevel::{CancelReason, Order, OrderCommon, OrderId, PriceLevel, Side, TimeInForce};
+use std::hint::black_box;
+
+/// Register benchmarks for canceling orders as the queue grows
+pub fn register_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PriceLevel - Cancel Orders");
+
+    // Only the cancellations are timed; building the level happens in the batch setup, so
+    // the curve shows how cancel latency itself scales with queue depth.
+    for depth in [10u64, 100, 1_000, 10_000] {
+        group.bench_with_input(
+            BenchmarkId::new("cancel_middle_order", depth),
+            &depth,
+            |b, &depth| {
+                b.iter_batched(
+                    || setup_standard_orders(depth),
+                    |mut price_level| {
+                        black_box(price_level.cancel_order(
+                            OrderId::from_u64(depth / 2),
+                            CancelReason::UserRequested,
+                        ));
+                        price_level
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("cancel_all_one_by_one", depth),
+            &depth,
+            |b, &depth| {
+                b.iter_batched(
+                    || setup_standard_orders(depth),
+                    |mut price_level| {
+                        // Back to front, the worst case for a queue scanned from the head
+                        for i in (0..depth).rev() {
+                            black_box(
+                                price_level.cancel_order(
+                                    OrderId::from_u64(i),
+                                    CancelReason::UserRequested,
+                                ),
+                            );
+                        }
+                        price_level
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Set up a price level with standard orders
+fn setup_standard_orders(order_count: u64) -> PriceLevel {
+    let mut price_level = PriceLevel::new(10000);
+
+    for i in 0..order_count {
+        let order = Order::Standard {
+            common: OrderCommon {
+                id: OrderId::from_u64(i),
+                price: 10000,
+                display_quantity: 10,
+                side: Side::Buy,
+                timestamp: 1616823000000 + i,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+        };
+        price_level.add_order(order).unwrap();
+    }
+
+    price_level
+}
@@ -1,5 +1,7 @@
 use criterion::{BenchmarkId, Criterion};
-use pricelevel::{Order, OrderCommon, OrderId, PriceLevel, Side, TimeInForce, UuidGenerator};
+use pricelevel::{
+    IcebergRefresh, Order, OrderCommon, OrderId, PriceLevel, Side, TimeInForce, UuidGenerator,
+};
 use std::hint::black_box;
 use uuid::Uuid;
 
@@ -125,9 +127,12 @@ fn setup_standard_orders(order_count: u64) -> PriceLevel {
                 timestamp: 1616823000000 + i,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 
     price_level
@@ -147,10 +152,16 @@ fn setup_iceberg_orders(order_count: u64) -> PriceLevel {
                 timestamp: 1616823000000 + i,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 15,
+            original_display: 5,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 
     price_level
@@ -170,13 +181,20 @@ fn setup_reserve_orders(order_count: u64) -> PriceLevel {
                 timestamp: 1616823000000 + i,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 15,
             replenish_threshold: 2,
             replenish_amount: Some(5),
             auto_replenish: true,
+            auto_replenish_interval_ms: None,
+            last_reveal_ms: None,
+            max_total_display: None,
+            revealed_quantity: 0,
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 
     price_level
@@ -197,6 +215,9 @@ fn setup_mixed_orders(order_count: u64) -> PriceLevel {
                     timestamp: 1616823000000 + i,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
             },
             1 => Order::IcebergOrder {
@@ -208,8 +229,14 @@ fn setup_mixed_orders(order_count: u64) -> PriceLevel {
                     timestamp: 1616823000000 + i,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
                 reserve_quantity: 15,
+                original_display: 5,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
             },
             _ => Order::PostOnly {
                 common: OrderCommon {
@@ -220,10 +247,13 @@ fn setup_mixed_orders(order_count: u64) -> PriceLevel {
                     timestamp: 1616823000000 + i,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
             },
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 
     price_level
@@ -1,6 +1,7 @@
 use criterion::Criterion;
 use pricelevel::{
-    Order, OrderCommon, OrderId, OrderUpdate, PriceLevel, Side, TimeInForce, UuidGenerator,
+    IcebergRefresh, Order, OrderCommon, OrderId, OrderUpdate, PriceLevel, Side, TimeInForce,
+    UuidGenerator,
 };
 use std::hint::black_box;
 use uuid::Uuid;
@@ -23,7 +24,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
                     7..=8 => create_iceberg_order(i, 10000, 5, 15),
                     _ => create_reserve_order(i, 10000, 5, 15, 2, true, None),
                 };
-                price_level.add_order(order);
+                price_level.add_order(order).unwrap();
             }
 
             // Phase 2: Execute some matches
@@ -56,7 +57,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
                     7..=8 => create_iceberg_order(i, 10000, 5, 15),
                     _ => create_reserve_order(i, 10000, 5, 15, 2, true, None),
                 };
-                price_level.add_order(order);
+                price_level.add_order(order).unwrap();
             }
 
             // Phase 5: Execute final matches
@@ -80,7 +81,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
             // Add initial orders
             for i in 0..200 {
                 let order = create_standard_order(i, 10000, 5);
-                price_level.add_order(order);
+                price_level.add_order(order).unwrap();
             }
 
             // Execute many small matches interspersed with new orders and cancellations
@@ -94,7 +95,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
 
                 // Add a new order
                 let order = create_standard_order(200 + i, 10000, 5);
-                price_level.add_order(order);
+                price_level.add_order(order).unwrap();
 
                 // Cancel an order
                 if i % 10 == 0 {
@@ -116,7 +117,7 @@ pub fn register_benchmarks(c: &mut Criterion) {
             // Add a large number of small orders
             for i in 0..500 {
                 let order = create_standard_order(i, 10000, 2);
-                price_level.add_order(order);
+                price_level.add_order(order).unwrap();
             }
 
             // Execute a few large matches
@@ -166,6 +167,9 @@ fn create_standard_order(id: u64, price: u64, quantity: u64) -> Order<()> {
             timestamp: 1616823000000 + id,
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
     }
 }
@@ -181,8 +185,14 @@ fn create_iceberg_order(id: u64, price: u64, visible: u64, hidden: u64) -> Order
             timestamp: 1616823000000 + id,
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
         reserve_quantity: hidden,
+        original_display: visible,
+        refresh_strategy: IcebergRefresh::default(),
+        last_reveal_ms: None,
     }
 }
 
@@ -205,11 +215,18 @@ fn create_reserve_order(
             timestamp: 1616823000000 + id,
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
         reserve_quantity: hidden,
         replenish_threshold: threshold,
         replenish_amount,
         auto_replenish,
+        auto_replenish_interval_ms: None,
+        last_reveal_ms: None,
+        max_total_display: None,
+        revealed_quantity: 0,
     }
 }
 
@@ -223,7 +240,7 @@ fn setup_mixed_orders(order_count: u64) -> PriceLevel {
             1 => create_iceberg_order(i, 10000, 5, 15),
             _ => create_reserve_order(i, 10000, 5, 15, 2, true, None),
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 
     price_level
@@ -1,7 +1,10 @@
 // benches/price_level/mod.rs
 pub mod add_orders;
+pub mod cancel_orders;
 pub mod match_orders;
 pub mod mixed_operations;
+pub mod parse_orders;
+pub mod snapshot;
 pub mod update_orders;
 
 // Import common benchmarks into the main bench group
@@ -9,5 +12,8 @@ pub fn register_benchmarks(c: &mut criterion::Criterion) {
     add_orders::register_benchmarks(c);
     match_orders::register_benchmarks(c);
     update_orders::register_benchmarks(c);
+    cancel_orders::register_benchmarks(c);
     mixed_operations::register_benchmarks(c);
+    parse_orders::register_benchmarks(c);
+    snapshot::register_benchmarks(c);
 }
@@ -0,0 +1,80 @@
+use criterion::Criterion;
+use pricelevel::{IcebergRefresh, Order, OrderCommon, OrderId, Side, TimeInForce};
+use std::hint::black_box;
+use std::str::FromStr;
+
+/// Register benchmarks comparing the tolerant and positional order parsers
+pub fn register_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Order - Parse");
+
+    let inputs: Vec<String> = (0..100)
+        .map(|i| match i % 3 {
+            0 => create_standard_order(i).to_string(),
+            1 => create_iceberg_order(i).to_string(),
+            _ => create_reserve_order(i).to_string(),
+        })
+        .collect();
+
+    group.bench_function("from_str", |b| {
+        b.iter(|| {
+            for input in &inputs {
+                black_box(Order::<()>::from_str(black_box(input)).unwrap());
+            }
+        })
+    });
+
+    group.bench_function("from_str_fast", |b| {
+        b.iter(|| {
+            for input in &inputs {
+                black_box(Order::<()>::from_str_fast(black_box(input)).unwrap());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn create_common(id: u64) -> OrderCommon<()> {
+    OrderCommon {
+        id: OrderId::from_u64(id),
+        price: 10000,
+        display_quantity: 100,
+        side: Side::Buy,
+        timestamp: 1616823000000,
+        time_in_force: TimeInForce::Gtc,
+        extra_fields: (),
+        parent_id: None,
+        oco_group: None,
+        suppress_in_feed: false,
+    }
+}
+
+fn create_standard_order(id: u64) -> Order<()> {
+    Order::Standard {
+        common: create_common(id),
+    }
+}
+
+fn create_iceberg_order(id: u64) -> Order<()> {
+    Order::IcebergOrder {
+        common: create_common(id),
+        reserve_quantity: 400,
+        original_display: 100,
+        refresh_strategy: IcebergRefresh::default(),
+        last_reveal_ms: None,
+    }
+}
+
+fn create_reserve_order(id: u64) -> Order<()> {
+    Order::ReserveOrder {
+        common: create_common(id),
+        reserve_quantity: 400,
+        replenish_threshold: 10,
+        replenish_amount: Some(50),
+        auto_replenish: true,
+        auto_replenish_interval_ms: None,
+        last_reveal_ms: None,
+        max_total_display: None,
+        revealed_quantity: 0,
+    }
+}
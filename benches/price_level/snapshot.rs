@@ -0,0 +1,47 @@
+use criterion::Criterion;
+use pricelevel::{Order, OrderCommon, OrderId, PriceLevel, PriceLevelSnapshot, Side, TimeInForce};
+use std::hint::black_box;
+
+/// Register benchmarks comparing allocating snapshots with refilling a reused one.
+///
+/// Their allocation counts are reported by the `snapshot_allocations` bench target, which
+/// installs a counting allocator.
+pub fn register_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("PriceLevel - Snapshot");
+
+    let price_level = setup_level(100);
+
+    group.bench_function("snapshot", |b| b.iter(|| black_box(price_level.snapshot())));
+
+    group.bench_function("snapshot_into", |b| {
+        let mut snapshot = PriceLevelSnapshot::new(0);
+        b.iter(|| {
+            price_level.snapshot_into(black_box(&mut snapshot));
+        })
+    });
+
+    group.finish();
+}
+
+fn setup_level(order_count: u64) -> PriceLevel {
+    let mut price_level = PriceLevel::new(10000);
+    for id in 0..order_count {
+        price_level
+            .add_order(Order::Standard {
+                common: OrderCommon {
+                    id: OrderId::from_u64(id),
+                    price: 10000,
+                    display_quantity: 10,
+                    side: Side::Buy,
+                    timestamp: 1616823000000 + id,
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
+                },
+            })
+            .unwrap();
+    }
+    price_level
+}
@@ -1,5 +1,7 @@
 use criterion::{BenchmarkId, Criterion};
-use pricelevel::{Order, OrderCommon, OrderId, OrderUpdate, PriceLevel, Side, TimeInForce};
+use pricelevel::{
+    IcebergRefresh, Order, OrderCommon, OrderId, OrderUpdate, PriceLevel, Side, TimeInForce,
+};
 use std::hint::black_box;
 
 /// Register all benchmarks for updating orders at a price level
@@ -116,9 +118,12 @@ fn setup_standard_orders(order_count: u64) -> PriceLevel {
                 timestamp: 1616823000000 + i,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 
     price_level
@@ -138,10 +143,16 @@ fn setup_iceberg_orders(order_count: u64) -> PriceLevel {
                 timestamp: 1616823000000 + i,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 15,
+            original_display: 5,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 
     price_level
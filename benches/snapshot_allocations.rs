@@ -0,0 +1,84 @@
+//! Compares the allocations of `snapshot` and `snapshot_into`.
+//!
+//! Kept out of the criterion benchmarks because counting needs its own global allocator.
+
+use pricelevel::{Order, OrderCommon, OrderId, PriceLevel, PriceLevelSnapshot, Side, TimeInForce};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// System allocator that counts allocations
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+const SNAPSHOTS: usize = 1000;
+
+fn main() {
+    let price_level = setup_level(100);
+
+    let allocating = count_allocations(|| {
+        for _ in 0..SNAPSHOTS {
+            black_box(price_level.snapshot());
+        }
+    });
+
+    let mut snapshot = PriceLevelSnapshot::new(0);
+    let reusing = count_allocations(|| {
+        for _ in 0..SNAPSHOTS {
+            price_level.snapshot_into(black_box(&mut snapshot));
+        }
+    });
+
+    println!("snapshot: {allocating} allocations for {SNAPSHOTS} snapshots");
+    println!("snapshot_into: {reusing} allocations for {SNAPSHOTS} snapshots");
+}
+
+/// Count the allocations made while running `f`
+fn count_allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn setup_level(order_count: u64) -> PriceLevel {
+    let mut price_level = PriceLevel::new(10000);
+    for id in 0..order_count {
+        price_level
+            .add_order(Order::Standard {
+                common: OrderCommon {
+                    id: OrderId::from_u64(id),
+                    price: 10000,
+                    display_quantity: 10,
+                    side: Side::Buy,
+                    timestamp: 1616823000000 + id,
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
+                },
+            })
+            .unwrap();
+    }
+    price_level
+}
@@ -1,8 +1,8 @@
 // examples/src/bin/hft_simulation.rs - High-Frequency Trading Simulation (Single-Threaded)
 
 use pricelevel::{
-    Order, OrderCommon, OrderId, OrderUpdate, PriceLevel, Side, TimeInForce, UuidGenerator,
-    setup_logger,
+    IcebergRefresh, Order, OrderCommon, OrderId, OrderUpdate, PriceLevel, Side, TimeInForce,
+    UuidGenerator, setup_logger,
 };
 use std::time::Instant;
 use tracing::info;
@@ -53,7 +53,7 @@ fn main() {
         let batch_start = Instant::now();
         for i in 0..ORDERS_PER_BATCH {
             let order = create_market_order(order_id_counter, i);
-            price_level.add_order(order);
+            price_level.add_order(order).unwrap();
             order_id_counter += 1;
             operation_count += 1;
 
@@ -161,10 +161,16 @@ fn main() {
                 timestamp: get_current_timestamp(),
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 40,
+            original_display: 10,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
         operation_count += 1;
     }
 
@@ -179,13 +185,20 @@ fn main() {
                 timestamp: get_current_timestamp(),
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 25,
             replenish_threshold: 3,
             replenish_amount: Some(8),
             auto_replenish: true,
+            auto_replenish_interval_ms: None,
+            last_reveal_ms: None,
+            max_total_display: None,
+            revealed_quantity: 0,
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
         operation_count += 1;
     }
 
@@ -279,7 +292,7 @@ fn setup_initial_orders(price_level: &mut PriceLevel, count: u64) {
             _ => create_reserve_order(i),
         };
 
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 }
 
@@ -299,6 +312,9 @@ fn create_market_order(base_id: u64, pattern: usize) -> Order<()> {
                 timestamp: current_time,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         },
         1 => Order::IcebergOrder {
@@ -310,8 +326,14 @@ fn create_market_order(base_id: u64, pattern: usize) -> Order<()> {
                 timestamp: current_time,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 15 + (pattern % 20) as u64,
+            original_display: 3 + (pattern % 7) as u64,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         },
         2 => Order::PostOnly {
             common: OrderCommon {
@@ -322,6 +344,9 @@ fn create_market_order(base_id: u64, pattern: usize) -> Order<()> {
                 timestamp: current_time,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         },
         3 => Order::ReserveOrder {
@@ -333,11 +358,18 @@ fn create_market_order(base_id: u64, pattern: usize) -> Order<()> {
                 timestamp: current_time,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 12 + (pattern % 15) as u64,
             replenish_threshold: 2 + (pattern % 3) as u64,
             replenish_amount: Some(4 + (pattern % 6) as u64),
             auto_replenish: pattern.is_multiple_of(2),
+            auto_replenish_interval_ms: None,
+            last_reveal_ms: None,
+            max_total_display: None,
+            revealed_quantity: 0,
         },
         4 => Order::Standard {
             common: OrderCommon {
@@ -348,6 +380,9 @@ fn create_market_order(base_id: u64, pattern: usize) -> Order<()> {
                 timestamp: current_time,
                 time_in_force: TimeInForce::Ioc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         },
         _ => Order::IcebergOrder {
@@ -359,8 +394,14 @@ fn create_market_order(base_id: u64, pattern: usize) -> Order<()> {
                 timestamp: current_time,
                 time_in_force: TimeInForce::Fok,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 25 + (pattern % 30) as u64,
+            original_display: 6 + (pattern % 10) as u64,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         },
     }
 }
@@ -376,6 +417,9 @@ fn create_standard_order(id: u64) -> Order<()> {
             timestamp: get_current_timestamp(),
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
     }
 }
@@ -390,8 +434,14 @@ fn create_iceberg_order(id: u64) -> Order<()> {
             timestamp: get_current_timestamp(),
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
         reserve_quantity: 20,
+        original_display: 5,
+        refresh_strategy: IcebergRefresh::default(),
+        last_reveal_ms: None,
     }
 }
 
@@ -405,6 +455,9 @@ fn create_post_only_order(id: u64) -> Order<()> {
             timestamp: get_current_timestamp(),
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
     }
 }
@@ -419,11 +472,18 @@ fn create_reserve_order(id: u64) -> Order<()> {
             timestamp: get_current_timestamp(),
             time_in_force: TimeInForce::Gtc,
             extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
         },
         reserve_quantity: 18,
         replenish_threshold: 3,
         replenish_amount: Some(7),
         auto_replenish: true,
+        auto_replenish_interval_ms: None,
+        last_reveal_ms: None,
+        max_total_display: None,
+        revealed_quantity: 0,
     }
 }
 
@@ -0,0 +1,54 @@
+// examples/src/bin/metrics_prometheus.rs - Exporting price level metrics to Prometheus
+//
+// Run with: cargo run -p examples --bin metrics_prometheus --features metrics
+
+use metrics_exporter_prometheus::PrometheusBuilder;
+use pricelevel::{
+    Order, OrderCommon, OrderId, OrderUpdate, PriceLevel, Side, TimeInForce, UuidGenerator,
+    setup_logger,
+};
+use tracing::info;
+use uuid::Uuid;
+
+fn main() {
+    setup_logger();
+
+    // Install the Prometheus recorder globally; every PriceLevel now reports through it.
+    let handle = PrometheusBuilder::new()
+        .install_recorder()
+        .expect("failed to install Prometheus recorder");
+
+    let mut price_level = PriceLevel::new(10000);
+    let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+    let tx_id_generator = UuidGenerator::new(namespace);
+
+    for id in 0..20 {
+        price_level
+            .add_order(Order::Standard {
+                common: OrderCommon {
+                    id: OrderId::from_u64(id),
+                    price: 10000,
+                    display_quantity: 10,
+                    side: Side::Sell,
+                    timestamp: 1616823000000 + id,
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
+                },
+            })
+            .unwrap();
+    }
+
+    for taker in 0..5 {
+        price_level.match_order(25, OrderId::from_u64(1000 + taker), &tx_id_generator);
+    }
+
+    let _ = price_level.update_order(OrderUpdate::Cancel {
+        order_id: OrderId::from_u64(19),
+    });
+
+    // In a real service this text would be served on a `/metrics` endpoint.
+    info!("Prometheus scrape output:\n{}", handle.render());
+}
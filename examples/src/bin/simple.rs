@@ -1,8 +1,8 @@
 // examples/src/bin/simple.rs - Single-threaded Price Level Example
 
 use pricelevel::{
-    Order, OrderCommon, OrderId, OrderUpdate, PriceLevel, Side, TimeInForce, UuidGenerator,
-    setup_logger,
+    IcebergRefresh, Order, OrderCommon, OrderId, OrderUpdate, PriceLevel, Side, TimeInForce,
+    UuidGenerator, setup_logger,
 };
 use std::time::Instant;
 use tracing::info;
@@ -38,7 +38,7 @@ fn main() {
     for i in 0..50 {
         let order_id = 1000 + i;
         let order = create_order(0, order_id); // Use thread_id 0 pattern
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
     let add_time = add_start.elapsed();
     info!("Added 50 orders in {:?}", add_time);
@@ -143,10 +143,16 @@ fn main() {
                 timestamp: get_current_timestamp(),
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 25,
+            original_display: 8,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 
     // Match against iceberg orders
@@ -225,9 +231,12 @@ fn setup_initial_orders(price_level: &mut PriceLevel) {
                 timestamp: 1616823000000 + i,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 
     // Add some iceberg orders
@@ -241,10 +250,16 @@ fn setup_initial_orders(price_level: &mut PriceLevel) {
                 timestamp: 1616823000000 + i,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 15,
+            original_display: 5,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 
     // Add some reserve orders
@@ -258,13 +273,20 @@ fn setup_initial_orders(price_level: &mut PriceLevel) {
                 timestamp: 1616823000000 + i,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 15,
             replenish_threshold: 2,
             replenish_amount: Some(5),
             auto_replenish: true,
+            auto_replenish_interval_ms: None,
+            last_reveal_ms: None,
+            max_total_display: None,
+            revealed_quantity: 0,
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
     }
 }
 
@@ -283,6 +305,9 @@ fn create_order(pattern: usize, order_id: u64) -> Order<()> {
                 timestamp: current_time,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         },
         1 => Order::IcebergOrder {
@@ -294,8 +319,14 @@ fn create_order(pattern: usize, order_id: u64) -> Order<()> {
                 timestamp: current_time,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 15,
+            original_display: 5,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         },
         2 => Order::PostOnly {
             common: OrderCommon {
@@ -306,6 +337,9 @@ fn create_order(pattern: usize, order_id: u64) -> Order<()> {
                 timestamp: current_time,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         },
         _ => Order::ReserveOrder {
@@ -317,11 +351,18 @@ fn create_order(pattern: usize, order_id: u64) -> Order<()> {
                 timestamp: current_time,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 15,
             replenish_threshold: 2,
             replenish_amount: Some(5),
             auto_replenish: true,
+            auto_replenish_interval_ms: None,
+            last_reveal_ms: None,
+            max_total_display: None,
+            revealed_quantity: 0,
         },
     }
 }
@@ -1,3 +1,4 @@
+use crate::order::OrderId;
 use std::fmt::{Debug, Display, Formatter, Result};
 
 /// Represents errors that can occur when processing price levels in trading operations.
@@ -82,6 +83,68 @@ pub enum PriceLevelError {
         /// The checksum that was computed from the provided payload
         actual: String,
     },
+
+    /// Error raised when a snapshot's stored aggregates disagree with its order list.
+    InconsistentSnapshot {
+        /// The aggregate field that failed verification
+        field: String,
+        /// The value stored in the snapshot
+        stored: u64,
+        /// The value recomputed from the snapshot orders
+        computed: u64,
+    },
+
+    /// Error raised when an order quantity is not a multiple of the level's lot size.
+    InvalidLotSize {
+        /// The offending quantity
+        quantity: u64,
+        /// The lot size configured on the level
+        lot_size: u64,
+    },
+
+    /// Error raised when an order price falls outside the level's configured price band.
+    PriceOutOfBand {
+        /// The rejected order price
+        price: u64,
+        /// The reference price the band is centered on
+        reference: u64,
+        /// The band width in basis points
+        band_bps: u32,
+    },
+
+    /// Error raised when an order's notional value exceeds the level's cap.
+    NotionalTooLarge {
+        /// The order notional (price times total quantity)
+        notional: u128,
+        /// The maximum notional accepted by the level
+        max_notional: u128,
+    },
+
+    /// Error raised when one order in a serialized price level cannot be parsed.
+    OrderParseError {
+        /// Zero-based position of the order in the level's order list
+        order_index: usize,
+        /// The text of the offending order
+        input: String,
+        /// Why the order could not be parsed
+        message: String,
+    },
+
+    /// Error raised when one snapshot package in a batch fails verification.
+    SnapshotBatchError {
+        /// Zero-based position of the package in the batch
+        index: usize,
+        /// Why the package was rejected
+        message: String,
+    },
+
+    /// Error raised when the event trace of an order breaks its lifecycle.
+    InvalidLifecycle {
+        /// The order whose trace was checked
+        order_id: OrderId,
+        /// The first violation found
+        message: String,
+    },
 }
 impl Display for PriceLevelError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
@@ -107,6 +170,54 @@ impl Display for PriceLevelError {
             PriceLevelError::ChecksumMismatch { expected, actual } => {
                 write!(f, "Checksum mismatch: expected {expected}, got {actual}")
             }
+            PriceLevelError::InconsistentSnapshot {
+                field,
+                stored,
+                computed,
+            } => {
+                write!(
+                    f,
+                    "Inconsistent snapshot: {field} is {stored} but orders sum to {computed}"
+                )
+            }
+            PriceLevelError::InvalidLotSize { quantity, lot_size } => {
+                write!(
+                    f,
+                    "Invalid lot size: quantity {quantity} is not a multiple of {lot_size}"
+                )
+            }
+            PriceLevelError::PriceOutOfBand {
+                price,
+                reference,
+                band_bps,
+            } => {
+                write!(
+                    f,
+                    "Price out of band: {price} is more than {band_bps} bps from {reference}"
+                )
+            }
+            PriceLevelError::NotionalTooLarge {
+                notional,
+                max_notional,
+            } => {
+                write!(
+                    f,
+                    "Notional too large: {notional} exceeds the maximum of {max_notional}"
+                )
+            }
+            PriceLevelError::OrderParseError {
+                order_index,
+                input,
+                message,
+            } => {
+                write!(f, "Invalid order {order_index} ({message}): '{input}'")
+            }
+            PriceLevelError::SnapshotBatchError { index, message } => {
+                write!(f, "Invalid snapshot package {index}: {message}")
+            }
+            PriceLevelError::InvalidLifecycle { order_id, message } => {
+                write!(f, "Invalid lifecycle of order {order_id}: {message}")
+            }
         }
     }
 }
@@ -135,6 +246,54 @@ impl Debug for PriceLevelError {
             PriceLevelError::ChecksumMismatch { expected, actual } => {
                 write!(f, "Checksum mismatch: expected {expected}, got {actual}")
             }
+            PriceLevelError::InconsistentSnapshot {
+                field,
+                stored,
+                computed,
+            } => {
+                write!(
+                    f,
+                    "Inconsistent snapshot: {field} is {stored} but orders sum to {computed}"
+                )
+            }
+            PriceLevelError::InvalidLotSize { quantity, lot_size } => {
+                write!(
+                    f,
+                    "Invalid lot size: quantity {quantity} is not a multiple of {lot_size}"
+                )
+            }
+            PriceLevelError::PriceOutOfBand {
+                price,
+                reference,
+                band_bps,
+            } => {
+                write!(
+                    f,
+                    "Price out of band: {price} is more than {band_bps} bps from {reference}"
+                )
+            }
+            PriceLevelError::NotionalTooLarge {
+                notional,
+                max_notional,
+            } => {
+                write!(
+                    f,
+                    "Notional too large: {notional} exceeds the maximum of {max_notional}"
+                )
+            }
+            PriceLevelError::OrderParseError {
+                order_index,
+                input,
+                message,
+            } => {
+                write!(f, "Invalid order {order_index} ({message}): '{input}'")
+            }
+            PriceLevelError::SnapshotBatchError { index, message } => {
+                write!(f, "Invalid snapshot package {index}: {message}")
+            }
+            PriceLevelError::InvalidLifecycle { order_id, message } => {
+                write!(f, "Invalid lifecycle of order {order_id}: {message}")
+            }
         }
     }
 }
@@ -0,0 +1,99 @@
+use crate::execution::transaction::Transaction;
+use serde::{Deserialize, Serialize};
+
+/// Basis points in one whole
+const BPS_SCALE: i128 = 10_000;
+
+/// Maker and taker fee rates, in basis points of the traded value.
+///
+/// A negative rate is a rebate: a maker rate of `-2` pays the maker 0.02% of the value of
+/// every execution. Fees are truncated toward zero, and saturate at the `i128` range for
+/// values too large to represent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    /// Rate charged to the resting (maker) order
+    pub maker_bps: i32,
+
+    /// Rate charged to the incoming (taker) order
+    pub taker_bps: i32,
+}
+
+impl FeeSchedule {
+    /// Create a fee schedule from maker and taker rates in basis points
+    pub fn new(maker_bps: i32, taker_bps: i32) -> Self {
+        Self {
+            maker_bps,
+            taker_bps,
+        }
+    }
+
+    /// Compute the `(maker_fee, taker_fee)` for a transaction
+    pub fn fees_for(&self, transaction: &Transaction) -> (i128, i128) {
+        let value = i128::try_from(transaction.value()).unwrap_or(i128::MAX);
+        (
+            value.saturating_mul(self.maker_bps as i128) / BPS_SCALE,
+            value.saturating_mul(self.taker_bps as i128) / BPS_SCALE,
+        )
+    }
+
+    /// Set the maker and taker fees of a transaction according to this schedule
+    pub fn apply(&self, transaction: &mut Transaction) {
+        (transaction.maker_fee, transaction.taker_fee) = self.fees_for(transaction);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::execution::{FeeSchedule, Transaction};
+    use crate::order::{OrderId, Side};
+    use uuid::Uuid;
+
+    #[test]
+    fn test_fees_for() {
+        let transaction = Transaction::new(
+            Uuid::nil(),
+            OrderId::from_u64(1),
+            OrderId::from_u64(2),
+            10_000,
+            15,
+            Side::Buy,
+        );
+
+        // Value is 150_000: 2 bps rebate to the maker, 5 bps charged to the taker
+        let schedule = FeeSchedule::new(-2, 5);
+        assert_eq!(schedule.fees_for(&transaction), (-30, 75));
+
+        // Fractional fees truncate toward zero for both charges and rebates
+        let schedule = FeeSchedule::new(-3, 7);
+        assert_eq!(schedule.fees_for(&transaction), (-45, 105));
+        let small = Transaction::new(
+            Uuid::nil(),
+            OrderId::from_u64(1),
+            OrderId::from_u64(2),
+            3,
+            1,
+            Side::Sell,
+        );
+        assert_eq!(schedule.fees_for(&small), (0, 0));
+
+        assert_eq!(FeeSchedule::default().fees_for(&transaction), (0, 0));
+
+        // A value beyond the i128 range saturates instead of wrapping to a negative fee
+        let huge = Transaction::new(
+            Uuid::nil(),
+            OrderId::from_u64(1),
+            OrderId::from_u64(2),
+            u64::MAX,
+            u64::MAX,
+            Side::Sell,
+        );
+        assert_eq!(
+            FeeSchedule::new(-2, 5).fees_for(&huge),
+            (i128::MIN / 10_000, i128::MAX / 10_000)
+        );
+        assert_eq!(
+            FeeSchedule::new(0, 10_000).fees_for(&huge),
+            (0, i128::MAX / 10_000)
+        );
+    }
+}
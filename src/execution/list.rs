@@ -164,6 +164,8 @@ mod tests {
                 quantity: 5,
                 taker_side: Side::Buy,
                 timestamp: 1616823000000,
+                maker_fee: 0,
+                taker_fee: 0,
             },
             Transaction {
                 transaction_id: transaction_id_generator.next(),
@@ -173,6 +175,8 @@ mod tests {
                 quantity: 10,
                 taker_side: Side::Sell,
                 timestamp: 1616823000001,
+                maker_fee: 0,
+                taker_fee: 0,
             },
         ]
     }
@@ -425,6 +429,8 @@ mod transaction_list_serialization_tests {
                 quantity: 5,
                 taker_side: Side::Buy,
                 timestamp: 1616823000000,
+                maker_fee: 0,
+                taker_fee: 0,
             },
             Transaction {
                 transaction_id: transaction_id_generator.next(),
@@ -434,6 +440,8 @@ mod transaction_list_serialization_tests {
                 quantity: 10,
                 taker_side: Side::Sell,
                 timestamp: 1616823000001,
+                maker_fee: 0,
+                taker_fee: 0,
             },
         ]
     }
@@ -1,8 +1,9 @@
 use crate::errors::PriceLevelError;
 use crate::execution::list::TransactionList;
 use crate::execution::transaction::Transaction;
-use crate::order::OrderId;
+use crate::order::{OrderId, Quantity};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
 
@@ -16,24 +17,54 @@ pub struct MatchResult {
     pub transactions: TransactionList,
 
     /// Remaining quantity of the incoming order after matching
-    pub remaining_quantity: u64,
+    pub remaining_quantity: Quantity,
 
     /// Whether the order was completely filled
     pub is_complete: bool,
 
     /// Any orders that were completely filled and removed from the book
     pub filled_order_ids: Vec<OrderId>,
+
+    /// The participation cap that limited this match, if one was applied and bound
+    #[serde(default)]
+    pub participation_cap: Option<Quantity>,
+
+    /// One-cancels-other groups of makers that traded in this match, in execution order and
+    /// without duplicates. The book should cancel the remaining legs of these groups.
+    #[serde(default)]
+    pub triggered_oco_groups: Vec<u64>,
+
+    /// Quantities moved from reserve to display during the match, in execution order. Each
+    /// entry immediately follows the transaction against the same maker that triggered it.
+    #[serde(default)]
+    pub replenishments: Vec<(OrderId, Quantity)>,
+
+    /// Quantity of the incoming order canceled instead of resting, such as the unfilled
+    /// remainder of an immediate-or-cancel order. It is included in `remaining_quantity`.
+    #[serde(default)]
+    pub cancelled_quantity: Quantity,
+
+    /// Makers in the order they were touched during the sweep, partially or fully, with one
+    /// entry per execution. Unlike `filled_order_ids` it includes makers that stay in the
+    /// book, so it shows how the queue was consumed.
+    #[serde(default)]
+    pub depletion_order: Vec<OrderId>,
 }
 
 impl MatchResult {
     /// Create a new empty match result
-    pub fn new(order_id: OrderId, initial_quantity: u64) -> Self {
+    pub fn new(order_id: OrderId, initial_quantity: Quantity) -> Self {
         Self {
             order_id,
             transactions: TransactionList::new(),
             remaining_quantity: initial_quantity,
             is_complete: false,
             filled_order_ids: Vec::new(),
+            participation_cap: None,
+            triggered_oco_groups: Vec::new(),
+            replenishments: Vec::new(),
+            cancelled_quantity: 0,
+            depletion_order: Vec::new(),
         }
     }
 
@@ -44,23 +75,55 @@ impl MatchResult {
         self.transactions.add(transaction);
     }
 
+    /// Record that a maker belonging to an OCO group traded
+    pub fn add_triggered_oco_group(&mut self, group: u64) {
+        if !self.triggered_oco_groups.contains(&group) {
+            self.triggered_oco_groups.push(group);
+        }
+    }
+
+    /// Record that `quantity` was revealed from a maker's reserve
+    pub fn add_replenishment(&mut self, order_id: OrderId, quantity: Quantity) {
+        self.replenishments.push((order_id, quantity));
+    }
+
+    /// Record that a maker was touched by an execution
+    pub fn add_depleted_maker(&mut self, order_id: OrderId) {
+        self.depletion_order.push(order_id);
+    }
+
     /// Add a filled order ID to track orders removed from the book
     pub fn add_filled_order_id(&mut self, order_id: OrderId) {
         self.filled_order_ids.push(order_id);
     }
 
+    /// Group the transactions by the maker order they executed against.
+    ///
+    /// A reserve or iceberg maker can fill in several transactions within one match; this
+    /// collects them under a single key while keeping each group in execution order.
+    pub fn by_maker(&self) -> HashMap<OrderId, Vec<&Transaction>> {
+        let mut groups: HashMap<OrderId, Vec<&Transaction>> = HashMap::new();
+        for transaction in self.transactions.as_vec() {
+            groups
+                .entry(transaction.maker_order_id)
+                .or_default()
+                .push(transaction);
+        }
+        groups
+    }
+
     /// Get the total executed quantity
-    pub fn executed_quantity(&self) -> u64 {
+    pub fn executed_quantity(&self) -> Quantity {
         self.transactions.as_vec().iter().map(|t| t.quantity).sum()
     }
 
-    /// Get the total value executed
-    pub fn executed_value(&self) -> u64 {
+    /// Get the total value executed, in `u128` so it cannot overflow
+    pub fn executed_value(&self) -> u128 {
         self.transactions
             .as_vec()
             .iter()
-            .map(|t| t.price * t.quantity)
-            .sum()
+            .map(|t| t.value())
+            .fold(0, u128::saturating_add)
     }
 
     /// Calculate the average execution price
@@ -89,7 +152,41 @@ impl fmt::Display for MatchResult {
             }
             write!(f, "{order_id}")?;
         }
-        write!(f, "]")
+        write!(f, "]")?;
+        if let Some(cap) = self.participation_cap {
+            write!(f, ";participation_cap={cap}")?;
+        }
+        if !self.triggered_oco_groups.is_empty() {
+            write!(f, ";triggered_oco_groups=")?;
+            for (i, group) in self.triggered_oco_groups.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "{group}")?;
+            }
+        }
+        if !self.replenishments.is_empty() {
+            write!(f, ";replenishments=")?;
+            for (i, (order_id, quantity)) in self.replenishments.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "{order_id}:{quantity}")?;
+            }
+        }
+        if self.cancelled_quantity > 0 {
+            write!(f, ";cancelled_quantity={}", self.cancelled_quantity)?;
+        }
+        if !self.depletion_order.is_empty() {
+            write!(f, ";depletion_order=")?;
+            for (i, order_id) in self.depletion_order.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ",")?;
+                }
+                write!(f, "{order_id}")?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -128,6 +225,11 @@ impl FromStr for MatchResult {
         let mut is_complete_str = None;
         let mut transactions_str = None;
         let mut filled_order_ids_str = None;
+        let mut participation_cap_str = None;
+        let mut triggered_oco_groups_str = None;
+        let mut replenishments_str = None;
+        let mut cancelled_quantity_str = None;
+        let mut depletion_order_str = None;
 
         let mut pos = "MatchResult:".len();
 
@@ -238,6 +340,31 @@ impl FromStr for MatchResult {
                         pos += 1;
                     }
                 }
+                "participation_cap" => {
+                    let (value, next_pos) = find_next_field(s, pos)?;
+                    participation_cap_str = Some(value);
+                    pos = next_pos;
+                }
+                "triggered_oco_groups" => {
+                    let (value, next_pos) = find_next_field(s, pos)?;
+                    triggered_oco_groups_str = Some(value);
+                    pos = next_pos;
+                }
+                "replenishments" => {
+                    let (value, next_pos) = find_next_field(s, pos)?;
+                    replenishments_str = Some(value);
+                    pos = next_pos;
+                }
+                "cancelled_quantity" => {
+                    let (value, next_pos) = find_next_field(s, pos)?;
+                    cancelled_quantity_str = Some(value);
+                    pos = next_pos;
+                }
+                "depletion_order" => {
+                    let (value, next_pos) = find_next_field(s, pos)?;
+                    depletion_order_str = Some(value);
+                    pos = next_pos;
+                }
                 _ => {
                     return Err(PriceLevelError::InvalidFormat(
                         "Unknown field in match result".to_string(),
@@ -300,12 +427,86 @@ impl FromStr for MatchResult {
             }
         };
 
+        let participation_cap = participation_cap_str
+            .map(|value| {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| PriceLevelError::InvalidFieldValue {
+                        field: "participation_cap".to_string(),
+                        value: value.to_string(),
+                    })
+            })
+            .transpose()?;
+
+        let triggered_oco_groups = match triggered_oco_groups_str {
+            None => Vec::new(),
+            Some(value) => value
+                .split(',')
+                .map(|group| {
+                    group
+                        .parse::<u64>()
+                        .map_err(|_| PriceLevelError::InvalidFieldValue {
+                            field: "triggered_oco_groups".to_string(),
+                            value: group.to_string(),
+                        })
+                })
+                .collect::<Result<Vec<u64>, PriceLevelError>>()?,
+        };
+
+        let replenishments = match replenishments_str {
+            None => Vec::new(),
+            Some(value) => value
+                .split(',')
+                .map(|entry| {
+                    let invalid = || PriceLevelError::InvalidFieldValue {
+                        field: "replenishments".to_string(),
+                        value: entry.to_string(),
+                    };
+                    let (order_id, quantity) = entry.split_once(':').ok_or_else(invalid)?;
+                    Ok((
+                        OrderId::from_str(order_id).map_err(|_| invalid())?,
+                        quantity.parse::<u64>().map_err(|_| invalid())?,
+                    ))
+                })
+                .collect::<Result<Vec<(OrderId, Quantity)>, PriceLevelError>>()?,
+        };
+
+        let cancelled_quantity = match cancelled_quantity_str {
+            None => 0,
+            Some(value) => {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| PriceLevelError::InvalidFieldValue {
+                        field: "cancelled_quantity".to_string(),
+                        value: value.to_string(),
+                    })?
+            }
+        };
+
+        let depletion_order = match depletion_order_str {
+            None => Vec::new(),
+            Some(value) => value
+                .split(',')
+                .map(|id_str| {
+                    OrderId::from_str(id_str).map_err(|_| PriceLevelError::InvalidFieldValue {
+                        field: "depletion_order".to_string(),
+                        value: id_str.to_string(),
+                    })
+                })
+                .collect::<Result<Vec<OrderId>, PriceLevelError>>()?,
+        };
+
         Ok(MatchResult {
             order_id,
             transactions,
             remaining_quantity,
             is_complete,
             filled_order_ids,
+            participation_cap,
+            triggered_oco_groups,
+            replenishments,
+            cancelled_quantity,
+            depletion_order,
         })
     }
 }
@@ -337,6 +538,8 @@ mod tests {
             quantity,
             taker_side: Side::Buy,
             timestamp: 1616823000000, // + id, // Create unique timestamps
+            maker_fee: 0,
+            taker_fee: 0,
         }
     }
 
@@ -710,4 +913,60 @@ mod tests {
 
         assert_eq!(complete_str, "false");
     }
+
+    #[test]
+    fn test_by_maker_groups_transactions() {
+        let mut result = MatchResult::new(OrderId::from_u64(123), 100);
+        let uuid = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+
+        result.add_transaction(create_test_transaction(uuid, 123, 456, 1000, 30));
+        result.add_transaction(create_test_transaction(uuid, 123, 456, 1000, 20));
+        result.add_transaction(create_test_transaction(uuid, 123, 789, 1000, 50));
+
+        let groups = result.by_maker();
+        assert_eq!(groups.len(), 2);
+
+        let first_maker = &groups[&OrderId::from_u64(456)];
+        let quantities: Vec<u64> = first_maker.iter().map(|t| t.quantity).collect();
+        assert_eq!(quantities, vec![30, 20]);
+
+        assert_eq!(groups[&OrderId::from_u64(789)].len(), 1);
+        assert!(
+            MatchResult::new(OrderId::from_u64(1), 10)
+                .by_maker()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_cancelled_quantity_roundtrip() {
+        let mut result = MatchResult::new(OrderId::from_u64(1), 50);
+        assert!(!result.to_string().contains("cancelled_quantity"));
+
+        result.cancelled_quantity = 50;
+        let display = result.to_string();
+        assert!(display.ends_with(";cancelled_quantity=50"));
+        let parsed = MatchResult::from_str(&display).unwrap();
+        assert_eq!(parsed.cancelled_quantity, 50);
+        assert_eq!(parsed.remaining_quantity, 50);
+
+        let parsed = MatchResult::from_str(&display.replace(";cancelled_quantity=50", "")).unwrap();
+        assert_eq!(parsed.cancelled_quantity, 0);
+    }
+
+    #[test]
+    fn test_depletion_order_roundtrip() {
+        let mut result = MatchResult::new(OrderId::from_u64(1), 50);
+        assert!(!result.to_string().contains("depletion_order"));
+
+        for id in [2, 3, 2] {
+            result.add_depleted_maker(OrderId::from_u64(id));
+        }
+        let parsed = MatchResult::from_str(&result.to_string()).unwrap();
+        assert_eq!(parsed.depletion_order, result.depletion_order);
+
+        let json = serde_json::to_string(&result).unwrap();
+        let parsed: MatchResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.depletion_order, result.depletion_order);
+    }
 }
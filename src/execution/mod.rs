@@ -1,6 +1,10 @@
+mod fees;
 mod list;
 mod match_result;
 mod transaction;
+mod verify;
 
+pub use fees::FeeSchedule;
 pub use match_result::MatchResult;
 pub use transaction::Transaction;
+pub use verify::verify_match;
@@ -1,5 +1,5 @@
 use crate::errors::PriceLevelError;
-use crate::order::{OrderId, Side};
+use crate::order::{OrderId, Price, Quantity, Side, Timestamp};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
@@ -19,16 +19,24 @@ pub struct Transaction {
     pub maker_order_id: OrderId,
 
     /// Price at which the transaction occurred
-    pub price: u64,
+    pub price: Price,
 
     /// Quantity that was traded
-    pub quantity: u64,
+    pub quantity: Quantity,
 
     /// Side of the taker order
     pub taker_side: Side,
 
     /// Timestamp when the transaction occurred
-    pub timestamp: u64,
+    pub timestamp: Timestamp,
+
+    /// Fee charged to the maker, in price units; negative values are rebates
+    #[serde(default)]
+    pub maker_fee: i128,
+
+    /// Fee charged to the taker, in price units; negative values are rebates
+    #[serde(default)]
+    pub taker_fee: i128,
 }
 
 impl Transaction {
@@ -37,8 +45,8 @@ impl Transaction {
         transaction_id: Uuid,
         taker_order_id: OrderId,
         maker_order_id: OrderId,
-        price: u64,
-        quantity: u64,
+        price: Price,
+        quantity: Quantity,
         taker_side: Side,
     ) -> Self {
         let timestamp = SystemTime::now()
@@ -54,6 +62,8 @@ impl Transaction {
             quantity,
             taker_side,
             timestamp,
+            maker_fee: 0,
+            taker_fee: 0,
         }
     }
 
@@ -69,6 +79,11 @@ impl Transaction {
     pub fn total_value(&self) -> u64 {
         self.price * self.quantity
     }
+
+    /// Returns the total value of this transaction, widened so it cannot overflow
+    pub fn value(&self) -> u128 {
+        self.price as u128 * self.quantity as u128
+    }
 }
 
 impl fmt::Display for Transaction {
@@ -83,7 +98,15 @@ impl fmt::Display for Transaction {
             self.quantity,
             self.taker_side,
             self.timestamp
-        )
+        )?;
+        if self.maker_fee != 0 || self.taker_fee != 0 {
+            write!(
+                f,
+                ";maker_fee={};taker_fee={}",
+                self.maker_fee, self.taker_fee
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -174,6 +197,20 @@ impl FromStr for Transaction {
         let timestamp_str = get_field("timestamp")?;
         let timestamp = parse_u64("timestamp", timestamp_str)?;
 
+        // Fees are only written when set
+        let parse_fee = |field: &str| -> Result<i128, PriceLevelError> {
+            fields.get(field).map_or(Ok(0), |value| {
+                value
+                    .parse::<i128>()
+                    .map_err(|_| PriceLevelError::InvalidFieldValue {
+                        field: field.to_string(),
+                        value: value.to_string(),
+                    })
+            })
+        };
+        let maker_fee = parse_fee("maker_fee")?;
+        let taker_fee = parse_fee("taker_fee")?;
+
         Ok(Transaction {
             transaction_id,
             taker_order_id,
@@ -182,6 +219,8 @@ impl FromStr for Transaction {
             quantity,
             taker_side,
             timestamp,
+            maker_fee,
+            taker_fee,
         })
     }
 }
@@ -206,6 +245,8 @@ mod tests {
             quantity: 5,
             taker_side: Side::Buy,
             timestamp: 1616823000000,
+            maker_fee: 0,
+            taker_fee: 0,
         }
     }
 
@@ -458,6 +499,8 @@ mod transaction_serialization_tests {
             quantity: 5,
             taker_side: Side::Buy,
             timestamp: 1616823000000,
+            maker_fee: 0,
+            taker_fee: 0,
         }
     }
 
@@ -0,0 +1,182 @@
+use crate::execution::match_result::MatchResult;
+use crate::order::{OrderId, Quantity};
+use crate::price_level::PriceLevelSnapshot;
+use std::collections::{HashMap, HashSet};
+
+/// Check that a match result accounts exactly for the change between two level snapshots.
+///
+/// `before` and `after` are snapshots taken around a single `match_order` call that produced
+/// `result`. The following invariants are checked:
+///
+/// * every transaction executed against an order present in `before`;
+/// * for orders still resting in `after`, the executed quantity equals the drop in their
+///   total (display + reserve) quantity;
+/// * orders that left the level had their whole visible quantity executed, and
+///   `filled_order_ids` lists exactly those orders;
+/// * the change in the level aggregates equals the executed quantity plus any reserve that
+///   was discarded together with a removed order.
+///
+/// Intended for tests and property checks of the matching engine; the error describes the
+/// first violated invariant.
+pub fn verify_match(
+    before: &PriceLevelSnapshot,
+    after: &PriceLevelSnapshot,
+    result: &MatchResult,
+) -> Result<(), String> {
+    let before_orders: HashMap<OrderId, _> = before
+        .orders
+        .iter()
+        .map(|order| (order.id(), order))
+        .collect();
+    let after_orders: HashMap<OrderId, _> = after
+        .orders
+        .iter()
+        .map(|order| (order.id(), order))
+        .collect();
+
+    let mut executed: HashMap<OrderId, Quantity> = HashMap::new();
+    for transaction in result.transactions.as_vec() {
+        if !before_orders.contains_key(&transaction.maker_order_id) {
+            return Err(format!(
+                "transaction {} executed against unknown maker {}",
+                transaction.transaction_id, transaction.maker_order_id
+            ));
+        }
+        *executed.entry(transaction.maker_order_id).or_default() += transaction.quantity;
+    }
+
+    let mut removed: HashSet<OrderId> = HashSet::new();
+    let mut discarded: Quantity = 0;
+    for (id, order) in &before_orders {
+        let before_total = order.display_quantity() + order.reserve_quantity();
+        let executed_qty = executed.get(id).copied().unwrap_or(0);
+
+        match after_orders.get(id) {
+            Some(remaining) => {
+                let after_total = remaining.display_quantity() + remaining.reserve_quantity();
+                if before_total.checked_sub(after_total) != Some(executed_qty) {
+                    return Err(format!(
+                        "order {id} went from {before_total} to {after_total} but {executed_qty} was executed"
+                    ));
+                }
+            }
+            None => {
+                if executed_qty < order.display_quantity() || executed_qty > before_total {
+                    return Err(format!(
+                        "order {id} left the level after executing {executed_qty} of {before_total}"
+                    ));
+                }
+                discarded += before_total - executed_qty;
+                removed.insert(*id);
+            }
+        }
+    }
+
+    if let Some(id) = after_orders
+        .keys()
+        .find(|id| !before_orders.contains_key(id))
+    {
+        return Err(format!("order {id} appeared during the match"));
+    }
+
+    let filled: HashSet<OrderId> = result.filled_order_ids.iter().copied().collect();
+    if filled.len() != result.filled_order_ids.len() {
+        return Err("filled_order_ids contains duplicates".to_string());
+    }
+    if filled != removed {
+        return Err(format!(
+            "filled_order_ids lists {} orders but {} left the level",
+            filled.len(),
+            removed.len()
+        ));
+    }
+
+    let before_total = before.display_quantity + before.reserve_quantity;
+    let after_total = after.display_quantity + after.reserve_quantity;
+    let executed_total = result.executed_quantity();
+    if before_total.checked_sub(after_total) != Some(executed_total + discarded) {
+        return Err(format!(
+            "level total went from {before_total} to {after_total} but {executed_total} was executed and {discarded} discarded"
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::execution::verify_match;
+    use crate::order::{IcebergRefresh, Order, OrderCommon, OrderId, Side, TimeInForce};
+    use crate::price_level::PriceLevel;
+    use crate::utils::UuidGenerator;
+    use uuid::Uuid;
+
+    fn create_order(id: u64, quantity: u64, reserve: u64) -> Order<()> {
+        let common = OrderCommon {
+            id: OrderId::from_u64(id),
+            price: 10000,
+            display_quantity: quantity,
+            side: Side::Sell,
+            timestamp: 1616823000000 + id,
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
+        };
+        if reserve == 0 {
+            Order::Standard { common }
+        } else {
+            Order::IcebergOrder {
+                original_display: common.display_quantity,
+                common,
+                reserve_quantity: reserve,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_match_accepts_real_matches() {
+        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+        let generator = UuidGenerator::new(namespace);
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level.add_order(create_order(1, 10, 20)).unwrap();
+        price_level.add_order(create_order(2, 15, 0)).unwrap();
+        price_level.add_order(create_order(3, 50, 0)).unwrap();
+
+        for (taker, quantity) in [(100, 12), (101, 30), (102, 70)] {
+            let before = price_level.snapshot();
+            let result = price_level.match_order(quantity, OrderId::from_u64(taker), &generator);
+            let after = price_level.snapshot();
+            assert_eq!(verify_match(&before, &after, &result), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_verify_match_detects_inconsistencies() {
+        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+        let generator = UuidGenerator::new(namespace);
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level.add_order(create_order(1, 10, 0)).unwrap();
+        price_level.add_order(create_order(2, 20, 0)).unwrap();
+
+        let before = price_level.snapshot();
+        let result = price_level.match_order(15, OrderId::from_u64(100), &generator);
+        let after = price_level.snapshot();
+
+        let mut missing_fill = result.clone();
+        missing_fill.filled_order_ids.clear();
+        assert!(verify_match(&before, &after, &missing_fill).is_err());
+
+        let mut drifted = after.clone();
+        drifted.display_quantity += 1;
+        assert!(verify_match(&before, &drifted, &result).is_err());
+
+        // Comparing against the wrong starting state is caught too
+        assert!(verify_match(&after, &after, &result).is_err());
+    }
+}
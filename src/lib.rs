@@ -142,9 +142,19 @@ mod price_level;
 mod utils;
 
 pub use errors::PriceLevelError;
-pub use execution::{MatchResult, Transaction};
+pub use execution::{FeeSchedule, MatchResult, Transaction, verify_match};
 pub use order::DEFAULT_RESERVE_REPLENISH_AMOUNT;
-pub use order::PegReferenceType;
-pub use order::{Order, OrderCommon, OrderId, OrderUpdate, Side, TimeInForce};
-pub use price_level::{OrderQueue, PriceLevel, PriceLevelData, PriceLevelSnapshot};
+pub use order::{
+    Annotations, CancelReason, IcebergRefresh, Order, OrderCommon, OrderId, OrderUpdate,
+    OrderVisitor, Price, Quantity, Side, TimeInForce, Timestamp,
+};
+pub use order::{PegReferenceType, PegRepricePolicy, RepricedOrder};
+pub use price_level::{
+    ArithmeticPolicy, DeterminismLog, DomRow, LevelEvent, LevelOp, LifecycleSummary,
+    LifecycleValidator, OrderQueue, PressureFormula, PriceLevel, PriceLevelAggregate,
+    PriceLevelData, PriceLevelSnapshot, PriceLevelSnapshotPackage, PriceLevelSnapshotWithStats,
+    PriceLevelStatistics, SignedMatchResult, SignedPriceLevel, SignedTransaction,
+    WAITING_TIME_SAMPLES, apply_match_result, crossed_quantity, decode_signed_price,
+    encode_signed_price, move_order, sum_contributions, verify_snapshot_batch,
+};
 pub use utils::{UuidGenerator, setup_logger};
@@ -0,0 +1,68 @@
+use crate::order::Order;
+use std::collections::HashMap;
+
+/// Free-form key-value metadata carried in an order's extra fields.
+///
+/// Use `Order<Annotations>` to attach routing or strategy tags without defining a custom
+/// extra-fields type. Annotations round-trip through serde, but the compact string format
+/// drops them and parsing yields none. Price levels hold `Order<()>`, so annotations are
+/// stripped by [`Order::erase_extra`] before an order rests and are not part of level
+/// snapshots; reattach them with [`Order::map_extra_fields`].
+pub type Annotations = HashMap<String, String>;
+
+impl Order<Annotations> {
+    /// Get the annotation stored under `key`
+    pub fn annotation(&self, key: &str) -> Option<&str> {
+        self.extra_fields().get(key).map(String::as_str)
+    }
+
+    /// Set the annotation under `key`, returning the value it replaces
+    pub fn annotate(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        self.extra_fields_mut().insert(key.into(), value.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::order::{
+        Annotations, IcebergRefresh, Order, OrderCommon, OrderId, Side, TimeInForce,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    fn test_annotations_round_trip() {
+        let mut order = Order::<Annotations>::IcebergOrder {
+            common: OrderCommon {
+                id: OrderId::from_u64(1),
+                price: 10000,
+                display_quantity: 10,
+                side: Side::Buy,
+                timestamp: 1616823000000,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: Annotations::new(),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+            reserve_quantity: 40,
+            original_display: 10,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
+        };
+        assert_eq!(order.annotate("strategy", "twap"), None);
+        assert_eq!(order.annotate("venue", "primary"), None);
+        assert_eq!(order.annotate("venue", "dark"), Some("primary".to_string()));
+
+        let json = serde_json::to_string(&order).unwrap();
+        let restored: Order<Annotations> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, order);
+        assert_eq!(restored.annotation("strategy"), Some("twap"));
+        assert_eq!(restored.annotation("venue"), Some("dark"));
+        assert_eq!(restored.annotation("desk"), None);
+
+        // The compact string format keeps the order but not its annotations
+        let parsed = Order::<Annotations>::from_str(&order.to_string()).unwrap();
+        assert!(parsed.extra_fields().is_empty());
+        assert_eq!(parsed.erased_clone(), order.erased_clone());
+    }
+}
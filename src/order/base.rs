@@ -41,6 +41,28 @@ impl Side {
             Side::Sell => Side::Buy,
         }
     }
+
+    /// Compact numeric code for binary formats: `Buy = 0`, `Sell = 1`.
+    ///
+    /// These assignments are part of the wire format and must never change.
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            Side::Buy => 0,
+            Side::Sell => 1,
+        }
+    }
+
+    /// Decode a side from its [`as_u8`](Self::as_u8) code.
+    pub fn from_u8(code: u8) -> Result<Self, PriceLevelError> {
+        match code {
+            0 => Ok(Side::Buy),
+            1 => Ok(Side::Sell),
+            _ => Err(PriceLevelError::InvalidFieldValue {
+                field: "side".to_string(),
+                value: code.to_string(),
+            }),
+        }
+    }
 }
 
 impl FromStr for Side {
@@ -285,6 +307,17 @@ mod tests_side {
         assert_eq!(serde_json::to_string(&Side::Buy).unwrap().len(), 5); // "BUY"
         assert_eq!(serde_json::to_string(&Side::Sell).unwrap().len(), 6); // "SELL"
     }
+
+    #[test]
+    fn test_side_u8_round_trip() {
+        assert_eq!(Side::Buy.as_u8(), 0);
+        assert_eq!(Side::Sell.as_u8(), 1);
+        for side in [Side::Buy, Side::Sell] {
+            assert_eq!(Side::from_u8(side.as_u8()).unwrap(), side);
+        }
+        assert!(Side::from_u8(2).is_err());
+        assert!(Side::from_u8(u8::MAX).is_err());
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,98 @@
+use crate::errors::PriceLevelError;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Why an order was removed from a price level without being filled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CancelReason {
+    /// The owner asked for the order to be canceled
+    UserRequested,
+
+    /// The order's time in force ran out
+    Expired,
+
+    /// The trading session ended and session-bound orders were dropped
+    SessionRollover,
+
+    /// The order would have traded against another order of the same owner
+    SelfTradePrevention,
+
+    /// A risk control pulled the order
+    RiskTrip,
+
+    /// The order was replaced by a new one
+    Replaced,
+
+    /// Another leg of the order's one-cancels-other group traded
+    OcoTriggered,
+}
+
+impl CancelReason {
+    /// Get the label used for this reason in text formats and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CancelReason::UserRequested => "USER_REQUESTED",
+            CancelReason::Expired => "EXPIRED",
+            CancelReason::SessionRollover => "SESSION_ROLLOVER",
+            CancelReason::SelfTradePrevention => "SELF_TRADE_PREVENTION",
+            CancelReason::RiskTrip => "RISK_TRIP",
+            CancelReason::Replaced => "REPLACED",
+            CancelReason::OcoTriggered => "OCO_TRIGGERED",
+        }
+    }
+}
+
+impl FromStr for CancelReason {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "USER_REQUESTED" => Ok(CancelReason::UserRequested),
+            "EXPIRED" => Ok(CancelReason::Expired),
+            "SESSION_ROLLOVER" => Ok(CancelReason::SessionRollover),
+            "SELF_TRADE_PREVENTION" => Ok(CancelReason::SelfTradePrevention),
+            "RISK_TRIP" => Ok(CancelReason::RiskTrip),
+            "REPLACED" => Ok(CancelReason::Replaced),
+            "OCO_TRIGGERED" => Ok(CancelReason::OcoTriggered),
+            _ => Err(PriceLevelError::ParseError {
+                message: format!("Invalid CancelReason: {s}"),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for CancelReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::order::CancelReason;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_cancel_reason_round_trip() {
+        let reasons = [
+            CancelReason::UserRequested,
+            CancelReason::Expired,
+            CancelReason::SessionRollover,
+            CancelReason::SelfTradePrevention,
+            CancelReason::RiskTrip,
+            CancelReason::Replaced,
+            CancelReason::OcoTriggered,
+        ];
+        for reason in reasons {
+            assert_eq!(CancelReason::from_str(&reason.to_string()).unwrap(), reason);
+            let json = serde_json::to_string(&reason).unwrap();
+            assert_eq!(serde_json::from_str::<CancelReason>(&json).unwrap(), reason);
+        }
+
+        assert_eq!(
+            CancelReason::from_str("risk_trip").unwrap(),
+            CancelReason::RiskTrip
+        );
+        assert!(CancelReason::from_str("UNKNOWN").is_err());
+    }
+}
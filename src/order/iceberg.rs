@@ -0,0 +1,76 @@
+use crate::errors::PriceLevelError;
+use crate::order::Quantity;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// How an iceberg order sizes the slice it reveals from its reserve once its display is
+/// fully consumed. The slice is always capped at the remaining reserve.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IcebergRefresh {
+    /// Reveal the display size the order was entered with
+    #[default]
+    OriginalDisplay,
+    /// Reveal a fixed quantity, at least 1
+    Fixed(Quantity),
+}
+
+impl IcebergRefresh {
+    /// Get the slice to reveal for an order entered with `original_display`
+    pub fn slice(&self, original_display: Quantity) -> Quantity {
+        match self {
+            IcebergRefresh::OriginalDisplay => original_display,
+            IcebergRefresh::Fixed(quantity) => (*quantity).max(1),
+        }
+    }
+}
+
+impl FromStr for IcebergRefresh {
+    type Err = PriceLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "OriginalDisplay" | "ORIGINALDISPLAY" | "originaldisplay" => {
+                Ok(IcebergRefresh::OriginalDisplay)
+            }
+            _ => ["Fixed(", "FIXED(", "fixed("]
+                .iter()
+                .find_map(|prefix| s.strip_prefix(prefix)?.strip_suffix(')'))
+                .and_then(|quantity| quantity.parse().ok())
+                .map(IcebergRefresh::Fixed)
+                .ok_or_else(|| PriceLevelError::ParseError {
+                    message: s.to_string(),
+                }),
+        }
+    }
+}
+
+impl fmt::Display for IcebergRefresh {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IcebergRefresh::OriginalDisplay => write!(f, "OriginalDisplay"),
+            IcebergRefresh::Fixed(quantity) => write!(f, "Fixed({quantity})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::order::IcebergRefresh;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_iceberg_refresh_round_trips_and_sizes_slices() {
+        for refresh in [IcebergRefresh::OriginalDisplay, IcebergRefresh::Fixed(25)] {
+            assert_eq!(
+                IcebergRefresh::from_str(&refresh.to_string()).unwrap(),
+                refresh
+            );
+        }
+        assert!(IcebergRefresh::from_str("Fixed(x)").is_err());
+
+        assert_eq!(IcebergRefresh::OriginalDisplay.slice(10), 10);
+        assert_eq!(IcebergRefresh::Fixed(25).slice(10), 25);
+        assert_eq!(IcebergRefresh::Fixed(0).slice(10), 1);
+    }
+}
@@ -1,8 +1,12 @@
+mod annotations;
 mod base;
+mod cancel_reason;
+mod iceberg;
 mod pegged;
 mod status;
 mod time_in_force;
 mod update;
+mod visitor;
 
 use crate::errors::PriceLevelError;
 use serde::{Deserialize, Serialize};
@@ -10,13 +14,26 @@ use std::fmt;
 use std::str::FromStr;
 
 // Re-exports
+pub use annotations::Annotations;
 pub use base::{OrderId, Side};
-pub use pegged::PegReferenceType;
+pub use cancel_reason::CancelReason;
+pub use iceberg::IcebergRefresh;
+pub use pegged::{PegReferenceType, PegRepricePolicy, RepricedOrder};
 pub use time_in_force::TimeInForce;
 pub use update::OrderUpdate;
+pub use visitor::OrderVisitor;
+
+/// Price of an order or price level, in the instrument's minor units.
+pub type Price = u64;
+
+/// Quantity of an order or price level.
+pub type Quantity = u64;
+
+/// Timestamp in milliseconds since the Unix epoch.
+pub type Timestamp = u64;
 
 /// Default amount to replenish the reserve with.
-pub const DEFAULT_RESERVE_REPLENISH_AMOUNT: u64 = 80;
+pub const DEFAULT_RESERVE_REPLENISH_AMOUNT: Quantity = 80;
 
 /// Common fields for all order types
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -24,17 +41,27 @@ pub struct OrderCommon<T> {
     /// The order ID
     pub id: OrderId,
     /// The price of the order
-    pub price: u64,
+    pub price: Price,
     /// The quantity of the order
-    pub display_quantity: u64,
+    pub display_quantity: Quantity,
     /// The side of the order (buy or sell)
     pub side: Side,
     /// When the order was created
-    pub timestamp: u64,
+    pub timestamp: Timestamp,
     /// Time-in-force policy
     pub time_in_force: TimeInForce,
     /// Additional custom fields
     pub extra_fields: T,
+    /// The parent order this order was sliced from, for attributing fills back to it
+    #[serde(default)]
+    pub parent_id: Option<OrderId>,
+    /// The one-cancels-other group this order belongs to, if any
+    #[serde(default)]
+    pub oco_group: Option<u64>,
+    /// Keep the order matchable but leave it out of market data, such as
+    /// `PriceLevel::public_display_quantity` and `PriceLevel::public_snapshot`
+    #[serde(default)]
+    pub suppress_in_feed: bool,
 }
 
 impl<T: Clone> OrderCommon<T> {
@@ -57,7 +84,17 @@ impl<T> fmt::Display for OrderCommon<T> {
             format!("{}", self.side).to_uppercase(),
             self.timestamp,
             self.time_in_force
-        )
+        )?;
+        if let Some(parent_id) = self.parent_id {
+            write!(f, ";parent_id={parent_id}")?;
+        }
+        if let Some(oco_group) = self.oco_group {
+            write!(f, ";oco_group={oco_group}")?;
+        }
+        if self.suppress_in_feed {
+            write!(f, ";suppress_in_feed=true")?;
+        }
+        Ok(())
     }
 }
 
@@ -71,11 +108,26 @@ pub enum Order<T> {
     },
 
     /// Iceberg order with display and reserve quantities
+    ///
+    /// Once the display is fully consumed, a new slice sized by `refresh_strategy` is revealed
+    /// from the reserve. The refresh state is serialized with the order, so a snapshot of a
+    /// partially revealed iceberg restores to an order that refreshes like the live one.
     IcebergOrder {
         #[serde(flatten)]
         common: OrderCommon<T>,
         /// The reserve quantity of the order
         reserve_quantity: u64,
+        /// The display size the order was entered with. If 0, the consumed slice is used;
+        /// `PriceLevel` sets it from the display when the order is added
+        #[serde(default)]
+        original_display: u64,
+        /// How each refreshed slice is sized
+        #[serde(default)]
+        refresh_strategy: IcebergRefresh,
+        /// Time of the last refresh from the reserve, taken from the execution that consumed
+        /// the previous slice. If None, the order has not refreshed yet
+        #[serde(default)]
+        last_reveal_ms: Option<Timestamp>,
     },
 
     /// Post-only order that won't match immediately
@@ -115,6 +167,8 @@ pub enum Order<T> {
     /// if `auto_replenish` is false, and visible quantity is below threshold, it will not replenish
     /// if `auto_replenish` is false and visible quantity is zero it will be removed from the book
     /// if `auto_replenish` is true, and replenish_threshold is 0, it will use 1
+    /// if `max_total_display` is set, no more than that is ever revealed from the reserve; once
+    /// it is reached the order stops replenishing and is removed when its visible part is consumed
     ReserveOrder {
         #[serde(flatten)]
         common: OrderCommon<T>,
@@ -126,20 +180,50 @@ pub enum Order<T> {
         replenish_amount: Option<u64>,
         /// Whether to replenish automatically when below threshold. If false, only replenish on next match
         auto_replenish: bool,
+        /// If set, a new display slice is revealed every this many milliseconds by
+        /// `PriceLevel::tick_replenish`, independently of matching
+        #[serde(default)]
+        auto_replenish_interval_ms: Option<u64>,
+        /// Time of the last timer-driven reveal. If None, the order timestamp is used
+        #[serde(default)]
+        last_reveal_ms: Option<Timestamp>,
+        /// Cap on the cumulative quantity revealed from the reserve. The initial display is not
+        /// counted. If None, the whole reserve may be revealed
+        #[serde(default)]
+        max_total_display: Option<u64>,
+        /// Cumulative quantity revealed from the reserve so far
+        #[serde(default)]
+        revealed_quantity: u64,
     },
 }
 
 impl<T: Clone> Order<T> {
     /// Create a new standard order with reduced quantity
-    pub fn with_reduced_quantity(&self, new_quantity: u64) -> Self {
+    pub fn with_reduced_quantity(&self, new_quantity: Quantity) -> Self {
         let mut new = self.clone();
         new.common_mut().display_quantity = new_quantity;
 
         new
     }
 
+    /// Create a copy of the order at a different price
+    pub fn with_price(&self, new_price: Price) -> Self {
+        let mut new = self.clone();
+        new.common_mut().price = new_price;
+
+        new
+    }
+
+    /// Create a copy of the order with a different timestamp
+    pub fn with_timestamp(&self, timestamp: Timestamp) -> Self {
+        let mut new = self.clone();
+        new.common_mut().timestamp = timestamp;
+
+        new
+    }
+
     /// Update an iceberg order, refreshing display part from reserve
-    pub fn refresh_iceberg(&self, refresh_amount: u64) -> (Self, u64) {
+    pub fn refresh_iceberg(&self, refresh_amount: Quantity) -> (Self, Quantity) {
         let mut new = self.clone();
         let used_hidden = match &mut new {
             Self::IcebergOrder {
@@ -162,6 +246,12 @@ impl<T: Clone> Order<T> {
             }
             _ => 0, // Non-iceberg orders don't refresh
         };
+        if let Self::ReserveOrder {
+            revealed_quantity, ..
+        } = &mut new
+        {
+            *revealed_quantity += used_hidden;
+        }
 
         (new, used_hidden)
     }
@@ -175,7 +265,10 @@ impl<T: Clone> Order<T> {
     /// - Optionally, an updated version of this order (if partially filled)
     /// - The quantity that was reduced from hidden portion (for iceberg/reserve orders)
     /// - The remaining quantity of the incoming order
-    pub fn match_against(&self, incoming_quantity: u64) -> (u64, Option<Self>, u64, u64) {
+    pub fn match_against(
+        &self,
+        incoming_quantity: Quantity,
+    ) -> (Quantity, Option<Self>, Quantity, Quantity) {
         match self {
             Self::Standard { common } => {
                 let display_quantity = common.display_quantity;
@@ -205,6 +298,9 @@ impl<T: Clone> Order<T> {
             Self::IcebergOrder {
                 common,
                 reserve_quantity,
+                original_display,
+                refresh_strategy,
+                last_reveal_ms,
             } => {
                 let display_quantity = common.display_quantity;
                 if display_quantity > incoming_quantity {
@@ -214,6 +310,9 @@ impl<T: Clone> Order<T> {
                         Some(Self::IcebergOrder {
                             common: common.map_display(|quantity| quantity - incoming_quantity),
                             reserve_quantity: *reserve_quantity,
+                            original_display: *original_display,
+                            refresh_strategy: *refresh_strategy,
+                            last_reveal_ms: *last_reveal_ms,
                         }),
                         0,
                         0,
@@ -228,13 +327,21 @@ impl<T: Clone> Order<T> {
                     return (display_quantity, None, 0, remaining);
                 }
 
-                let refresh_qty = std::cmp::min(*reserve_quantity, display_quantity);
+                let entered = if *original_display == 0 {
+                    display_quantity
+                } else {
+                    *original_display
+                };
+                let refresh_qty = refresh_strategy.slice(entered).min(*reserve_quantity);
 
                 (
                     display_quantity,
                     Some(Self::IcebergOrder {
                         common: common.map_display(|_| refresh_qty),
                         reserve_quantity: *reserve_quantity - refresh_qty,
+                        original_display: *original_display,
+                        refresh_strategy: *refresh_strategy,
+                        last_reveal_ms: *last_reveal_ms,
                     }),
                     refresh_qty,
                     remaining,
@@ -248,6 +355,10 @@ impl<T: Clone> Order<T> {
                 replenish_threshold,
                 replenish_amount,
                 auto_replenish,
+                auto_replenish_interval_ms,
+                last_reveal_ms,
+                max_total_display,
+                revealed_quantity,
             } => {
                 let display_quantity = common.display_quantity;
                 // Ensure the threshold is never 0 if auto_replenish is true
@@ -259,15 +370,15 @@ impl<T: Clone> Order<T> {
 
                 let replenish_qty = replenish_amount
                     .unwrap_or(DEFAULT_RESERVE_REPLENISH_AMOUNT)
-                    .min(*reserve_quantity);
+                    .min(self.revealable_reserve());
 
                 // Full match of the visible part
                 if display_quantity <= incoming_quantity {
                     let consumed = display_quantity;
                     let remaining = incoming_quantity - consumed;
 
-                    // No auto-replenishment or hidden quantity, delete the order
-                    if *reserve_quantity == 0 || !*auto_replenish {
+                    // No auto-replenishment or revealable hidden quantity, delete the order
+                    if replenish_qty == 0 || !*auto_replenish {
                         return (consumed, None, 0, remaining);
                     }
 
@@ -279,6 +390,10 @@ impl<T: Clone> Order<T> {
                             replenish_threshold: *replenish_threshold,
                             replenish_amount: *replenish_amount,
                             auto_replenish: *auto_replenish,
+                            auto_replenish_interval_ms: *auto_replenish_interval_ms,
+                            last_reveal_ms: *last_reveal_ms,
+                            max_total_display: *max_total_display,
+                            revealed_quantity: *revealed_quantity + replenish_qty,
                         }),
                         replenish_qty,
                         remaining,
@@ -289,7 +404,7 @@ impl<T: Clone> Order<T> {
                 let new_display = display_quantity - incoming_quantity;
 
                 // Replenish  (we fell below the threshold)
-                if new_display < safe_threshold && *reserve_quantity > 0 && *auto_replenish {
+                if new_display < safe_threshold && replenish_qty > 0 && *auto_replenish {
                     return (
                         incoming_quantity,
                         Some(Self::ReserveOrder {
@@ -298,6 +413,10 @@ impl<T: Clone> Order<T> {
                             replenish_threshold: *replenish_threshold,
                             replenish_amount: *replenish_amount,
                             auto_replenish: *auto_replenish,
+                            auto_replenish_interval_ms: *auto_replenish_interval_ms,
+                            last_reveal_ms: *last_reveal_ms,
+                            max_total_display: *max_total_display,
+                            revealed_quantity: *revealed_quantity + replenish_qty,
                         }),
                         replenish_qty,
                         0,
@@ -313,6 +432,10 @@ impl<T: Clone> Order<T> {
                         replenish_threshold: *replenish_threshold,
                         replenish_amount: *replenish_amount,
                         auto_replenish: *auto_replenish,
+                        auto_replenish_interval_ms: *auto_replenish_interval_ms,
+                        last_reveal_ms: *last_reveal_ms,
+                        max_total_display: *max_total_display,
+                        revealed_quantity: *revealed_quantity,
                     }),
                     0,
                     0,
@@ -375,18 +498,33 @@ impl<T> Order<T> {
         self.common().id
     }
 
+    /// Get the parent order ID, if this order was sliced from one
+    pub fn parent_id(&self) -> Option<OrderId> {
+        self.common().parent_id
+    }
+
+    /// Get the one-cancels-other group, if this order is linked to siblings at other levels
+    pub fn oco_group(&self) -> Option<u64> {
+        self.common().oco_group
+    }
+
+    /// Check if the order is matchable but hidden from market data
+    pub fn is_suppressed_in_feed(&self) -> bool {
+        self.common().suppress_in_feed
+    }
+
     /// Get the price
-    pub fn price(&self) -> u64 {
+    pub fn price(&self) -> Price {
         self.common().price
     }
 
     /// Get the visible quantity
-    pub fn display_quantity(&self) -> u64 {
+    pub fn display_quantity(&self) -> Quantity {
         self.common().display_quantity
     }
 
     /// Get the reserve quantity
-    pub fn reserve_quantity(&self) -> u64 {
+    pub fn reserve_quantity(&self) -> Quantity {
         match self {
             Self::IcebergOrder {
                 reserve_quantity, ..
@@ -398,6 +536,49 @@ impl<T> Order<T> {
         }
     }
 
+    /// Get the quantity an incoming order could actually execute against.
+    ///
+    /// This is the visible quantity plus any reserve that matching would reveal. Iceberg
+    /// orders and auto-replenishing reserve orders expose their revealable reserve; a reserve
+    /// order without auto-replenish is removed once its visible part is consumed, so its
+    /// reserve is never matchable.
+    pub fn fillable_quantity(&self) -> Quantity {
+        let revealable = match self {
+            Self::IcebergOrder {
+                reserve_quantity, ..
+            } => *reserve_quantity,
+            Self::ReserveOrder {
+                auto_replenish: true,
+                ..
+            } => self.revealable_reserve(),
+            _ => 0,
+        };
+
+        self.display_quantity().saturating_add(revealable)
+    }
+
+    /// Get the reserve quantity that may still be revealed.
+    ///
+    /// This is the whole reserve, except for reserve orders whose `max_total_display` leaves
+    /// less to reveal. Non-reserve orders have none.
+    pub fn revealable_reserve(&self) -> Quantity {
+        match self {
+            Self::IcebergOrder {
+                reserve_quantity, ..
+            } => *reserve_quantity,
+            Self::ReserveOrder {
+                reserve_quantity,
+                max_total_display,
+                revealed_quantity,
+                ..
+            } => max_total_display.map_or(*reserve_quantity, |cap| {
+                cap.saturating_sub(*revealed_quantity)
+                    .min(*reserve_quantity)
+            }),
+            _ => 0,
+        }
+    }
+
     /// Get the order side
     pub fn side(&self) -> Side {
         self.common().side
@@ -409,7 +590,7 @@ impl<T> Order<T> {
     }
 
     /// Get the timestamp
-    pub fn timestamp(&self) -> u64 {
+    pub fn timestamp(&self) -> Timestamp {
         self.common().timestamp
     }
 
@@ -451,6 +632,9 @@ impl<T> Order<T> {
                                     timestamp,
                                     time_in_force,
                                     extra_fields,
+                                    parent_id,
+                                    oco_group,
+                                    suppress_in_feed,
                                 }| {
             OrderCommon {
                 id,
@@ -460,6 +644,9 @@ impl<T> Order<T> {
                 timestamp,
                 time_in_force,
                 extra_fields: f(extra_fields),
+                parent_id,
+                oco_group,
+                suppress_in_feed,
             }
         };
 
@@ -470,9 +657,15 @@ impl<T> Order<T> {
             Order::IcebergOrder {
                 common,
                 reserve_quantity,
+                original_display,
+                refresh_strategy,
+                last_reveal_ms,
             } => Order::IcebergOrder {
                 common: map_common_extra(common),
                 reserve_quantity,
+                original_display,
+                refresh_strategy,
+                last_reveal_ms,
             },
             Order::PostOnly { common } => Order::PostOnly {
                 common: map_common_extra(common),
@@ -504,17 +697,211 @@ impl<T> Order<T> {
                 replenish_threshold,
                 replenish_amount,
                 auto_replenish,
+                auto_replenish_interval_ms,
+                last_reveal_ms,
+                max_total_display,
+                revealed_quantity,
             } => Order::ReserveOrder {
                 common: map_common_extra(common),
                 reserve_quantity,
                 replenish_threshold,
                 replenish_amount,
                 auto_replenish,
+                auto_replenish_interval_ms,
+                last_reveal_ms,
+                max_total_display,
+                revealed_quantity,
             },
         }
     }
 }
 
+impl<T> Order<T> {
+    /// Discard the extra fields, keeping every other field of the order.
+    ///
+    /// Equivalent to `map_extra_fields(|_| ())`, but documents the intent at call sites
+    /// that only need to strip metadata (e.g. before adding the order to a [`crate::PriceLevel`]).
+    pub fn erase_extra(self) -> Order<()> {
+        self.erased_clone()
+    }
+
+    /// Copy the order without its extra fields, leaving `self` untouched.
+    ///
+    /// The extra fields are never cloned, so this does not allocate even when `T` does.
+    pub fn erased_clone(&self) -> Order<()> {
+        let common = self.common();
+        let common = OrderCommon {
+            id: common.id,
+            price: common.price,
+            display_quantity: common.display_quantity,
+            side: common.side,
+            timestamp: common.timestamp,
+            time_in_force: common.time_in_force,
+            extra_fields: (),
+            parent_id: common.parent_id,
+            oco_group: common.oco_group,
+            suppress_in_feed: common.suppress_in_feed,
+        };
+
+        match *self {
+            Order::Standard { .. } => Order::Standard { common },
+            Order::IcebergOrder {
+                reserve_quantity,
+                original_display,
+                refresh_strategy,
+                last_reveal_ms,
+                ..
+            } => Order::IcebergOrder {
+                common,
+                reserve_quantity,
+                original_display,
+                refresh_strategy,
+                last_reveal_ms,
+            },
+            Order::PostOnly { .. } => Order::PostOnly { common },
+            Order::TrailingStop {
+                trail_amount,
+                last_reference_price,
+                ..
+            } => Order::TrailingStop {
+                common,
+                trail_amount,
+                last_reference_price,
+            },
+            Order::PeggedOrder {
+                reference_price_offset,
+                reference_price_type,
+                ..
+            } => Order::PeggedOrder {
+                common,
+                reference_price_offset,
+                reference_price_type,
+            },
+            Order::MarketToLimit { .. } => Order::MarketToLimit { common },
+            Order::ReserveOrder {
+                reserve_quantity,
+                replenish_threshold,
+                replenish_amount,
+                auto_replenish,
+                auto_replenish_interval_ms,
+                last_reveal_ms,
+                max_total_display,
+                revealed_quantity,
+                ..
+            } => Order::ReserveOrder {
+                common,
+                reserve_quantity,
+                replenish_threshold,
+                replenish_amount,
+                auto_replenish,
+                auto_replenish_interval_ms,
+                last_reveal_ms,
+                max_total_display,
+                revealed_quantity,
+            },
+        }
+    }
+
+    /// Get the notional value of the order, `price * (display + reserve quantity)`.
+    ///
+    /// Computed in `u128` so that it cannot overflow for any price and quantity.
+    pub fn notional(&self) -> u128 {
+        let total = self.display_quantity() as u128 + self.reserve_quantity() as u128;
+        self.price() as u128 * total
+    }
+
+    /// Get the signed price improvement the order receives when executing at
+    /// `execution_price`, relative to its stored limit price.
+    ///
+    /// Improvement is positive when the execution is better for the order's side: a lower
+    /// price for a buy, a higher price for a sell. The result saturates at the `i64` range.
+    pub fn price_improvement(&self, execution_price: Price) -> i64 {
+        let difference = match self.side() {
+            Side::Buy => self.price() as i128 - execution_price as i128,
+            Side::Sell => execution_price as i128 - self.price() as i128,
+        };
+        difference.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    /// Check whether the order price is within `band_bps` basis points of `reference`.
+    ///
+    /// This is the usual fat-finger check; a price exactly at the edge of the band passes.
+    pub fn passes_price_band(&self, reference: Price, band_bps: u32) -> bool {
+        let distance = self.price().abs_diff(reference) as u128;
+        distance * 10_000 <= reference as u128 * band_bps as u128
+    }
+
+    /// Get the price a pegged order should rest at: its reference price plus its offset.
+    ///
+    /// `bids` and `asks` are the level prices of each side of the book, best first (see
+    /// [`PegReferenceType::reference_price`]). Returns `None` for orders that are not pegged,
+    /// when the book does not provide the reference, or when the offset would take the price
+    /// out of range.
+    pub fn resolve_pegged_price(
+        &self,
+        bids: &[Price],
+        asks: &[Price],
+        last_trade: Option<Price>,
+    ) -> Option<Price> {
+        let Order::PeggedOrder {
+            common,
+            reference_price_offset,
+            reference_price_type,
+        } = self
+        else {
+            return None;
+        };
+        reference_price_type
+            .reference_price(common.side, bids, asks, last_trade)?
+            .checked_add_signed(*reference_price_offset)
+    }
+
+    /// Reprice a marketable post-only order so it rests one tick inside the opposite best.
+    ///
+    /// A buy crosses when its price is at or above `best_opposite` and is moved to
+    /// `best_opposite - tick`; a sell crosses when its price is at or below `best_opposite`
+    /// and is moved to `best_opposite + tick`. Orders that are not post-only, or that do not
+    /// cross, are returned unchanged.
+    pub fn reprice_post_only(&self, best_opposite: Price, tick: Price) -> Order<()> {
+        let mut order = self.erased_clone();
+        if !order.is_post_only() {
+            return order;
+        }
+
+        let price = order.price();
+        let repriced = match order.side() {
+            Side::Buy if price >= best_opposite => best_opposite.saturating_sub(tick),
+            Side::Sell if price <= best_opposite => best_opposite.saturating_add(tick),
+            _ => price,
+        };
+        order.common_mut().price = repriced;
+        order
+    }
+
+    /// Canonicalize the order's replenishment settings before it is added to a level.
+    ///
+    /// For reserve orders, `replenish_threshold` is clamped to the display quantity, and a
+    /// threshold of 0 with `auto_replenish` becomes 1, the value matching would use anyway.
+    /// Reserve quantity only exists on iceberg and reserve orders, so other variants have no
+    /// reserve to clear and are left unchanged. Matching still applies the threshold rule to
+    /// orders that were never normalized; normalizing just makes the stored fields agree
+    /// with it.
+    pub fn normalize(&mut self) {
+        if let Order::ReserveOrder {
+            common,
+            replenish_threshold,
+            auto_replenish,
+            ..
+        } = self
+        {
+            *replenish_threshold = (*replenish_threshold).min(common.display_quantity);
+            if *auto_replenish && *replenish_threshold == 0 {
+                *replenish_threshold = 1;
+            }
+        }
+    }
+}
+
 /// Expected string format:
 /// ORDER_TYPE:id=`<id>`;price=`<price>`;quantity=`<qty>`;side=<BUY|SELL>;timestamp=`<ts>`;time_in_force=`<tif>`;[additional fields]
 ///
@@ -582,6 +969,36 @@ impl<T: Default> FromStr for Order<T> {
         let timestamp = parse_u64("timestamp")?;
         let time_in_force = TimeInForce::from_str(get_field("time_in_force")?)?;
         let display_quantity = parse_u64("display_quantity")?;
+        let parent_id = fields
+            .get("parent_id")
+            .map(|parent_str| {
+                OrderId::from_str(parent_str).map_err(|_| PriceLevelError::InvalidFieldValue {
+                    field: "parent_id".to_string(),
+                    value: parent_str.to_string(),
+                })
+            })
+            .transpose()?;
+        let oco_group = fields
+            .get("oco_group")
+            .map(|group_str| {
+                group_str
+                    .parse::<u64>()
+                    .map_err(|_| PriceLevelError::InvalidFieldValue {
+                        field: "oco_group".to_string(),
+                        value: group_str.to_string(),
+                    })
+            })
+            .transpose()?;
+        let suppress_in_feed = match fields.get("suppress_in_feed") {
+            None | Some(&"false") => false,
+            Some(&"true") => true,
+            Some(value) => {
+                return Err(PriceLevelError::InvalidFieldValue {
+                    field: "suppress_in_feed".to_string(),
+                    value: value.to_string(),
+                });
+            }
+        };
 
         let common = OrderCommon {
             id,
@@ -591,6 +1008,9 @@ impl<T: Default> FromStr for Order<T> {
             timestamp,
             time_in_force,
             extra_fields: T::default(),
+            parent_id,
+            oco_group,
+            suppress_in_feed,
         };
 
         // Parse specific order types
@@ -598,10 +1018,34 @@ impl<T: Default> FromStr for Order<T> {
             "Standard" => Ok(Order::Standard { common }),
             "IcebergOrder" => {
                 let reserve_quantity = parse_u64("reserve_quantity")?;
+                // Refresh fields are optional so strings written before they existed still parse
+                let original_display = match fields.get("original_display") {
+                    None => display_quantity,
+                    Some(_) => parse_u64("original_display")?,
+                };
+                let refresh_strategy = fields
+                    .get("refresh_strategy")
+                    .map(|strategy| {
+                        IcebergRefresh::from_str(strategy).map_err(|_| {
+                            PriceLevelError::InvalidFieldValue {
+                                field: "refresh_strategy".to_string(),
+                                value: strategy.to_string(),
+                            }
+                        })
+                    })
+                    .transpose()?
+                    .unwrap_or_default();
+                let last_reveal_ms = match fields.get("last_reveal_ms") {
+                    None => None,
+                    Some(_) => Some(parse_u64("last_reveal_ms")?),
+                };
 
                 Ok(Order::IcebergOrder {
                     common,
                     reserve_quantity,
+                    original_display,
+                    refresh_strategy,
+                    last_reveal_ms,
                 })
             }
             "PostOnly" => Ok(Order::PostOnly { common }),
@@ -618,18 +1062,11 @@ impl<T: Default> FromStr for Order<T> {
             "PeggedOrder" => {
                 let reference_price_offset = parse_i64("reference_price_offset")?;
                 let reference_price_type_str = get_field("reference_price_type")?;
-                let reference_price_type = match reference_price_type_str {
-                    "BestBid" => PegReferenceType::BestBid,
-                    "BestAsk" => PegReferenceType::BestAsk,
-                    "MidPrice" => PegReferenceType::MidPrice,
-                    "LastTrade" => PegReferenceType::LastTrade,
-                    _ => {
-                        return Err(PriceLevelError::InvalidFieldValue {
-                            field: "reference_price_type".to_string(),
-                            value: reference_price_type_str.to_string(),
-                        });
-                    }
-                };
+                let reference_price_type = PegReferenceType::from_str(reference_price_type_str)
+                    .map_err(|_| PriceLevelError::InvalidFieldValue {
+                        field: "reference_price_type".to_string(),
+                        value: reference_price_type_str.to_string(),
+                    })?;
 
                 Ok(Order::PeggedOrder {
                     common,
@@ -658,6 +1095,17 @@ impl<T: Default> FromStr for Order<T> {
                         });
                     }
                 };
+                // Timer fields are optional so strings written before they existed still parse
+                let parse_optional_u64 = |field: &str| -> Result<Option<u64>, PriceLevelError> {
+                    match fields.get(field) {
+                        None | Some(&"None") => Ok(None),
+                        Some(_) => parse_u64(field).map(Some),
+                    }
+                };
+                let auto_replenish_interval_ms = parse_optional_u64("auto_replenish_interval_ms")?;
+                let last_reveal_ms = parse_optional_u64("last_reveal_ms")?;
+                let max_total_display = parse_optional_u64("max_total_display")?;
+                let revealed_quantity = parse_optional_u64("revealed_quantity")?.unwrap_or(0);
 
                 Ok(Order::ReserveOrder {
                     common,
@@ -665,6 +1113,10 @@ impl<T: Default> FromStr for Order<T> {
                     replenish_threshold,
                     replenish_amount,
                     auto_replenish,
+                    auto_replenish_interval_ms,
+                    last_reveal_ms,
+                    max_total_display,
+                    revealed_quantity,
                 })
             }
             _ => Err(PriceLevelError::UnknownOrderType(order_type.to_string())),
@@ -672,6 +1124,181 @@ impl<T: Default> FromStr for Order<T> {
     }
 }
 
+impl<T: Default> Order<T> {
+    /// Parse an order from its string form without building an intermediate field map.
+    ///
+    /// The fields are read positionally in the exact order [`Display`](fmt::Display) writes
+    /// them. Any deviation (reordered or optional fields, unexpected values) falls back to
+    /// the tolerant [`FromStr`] parser, so the result is always identical to `from_str`.
+    pub fn from_str_fast(s: &str) -> Result<Self, PriceLevelError> {
+        match Self::parse_positional(s) {
+            Some(order) => Ok(order),
+            None => Self::from_str(s),
+        }
+    }
+
+    fn parse_positional(s: &str) -> Option<Self> {
+        let (order_type, fields_str) = s.split_once(':')?;
+        let mut fields = fields_str.split(';');
+        let mut next = |key: &str| -> Option<&str> {
+            let (field, value) = fields.next()?.split_once('=')?;
+            (field == key).then_some(value)
+        };
+
+        let common = OrderCommon {
+            id: OrderId::from_str(next("id")?).ok()?,
+            price: next("price")?.parse().ok()?,
+            display_quantity: next("display_quantity")?.parse().ok()?,
+            side: Side::from_str(next("side")?).ok()?,
+            timestamp: next("timestamp")?.parse().ok()?,
+            time_in_force: TimeInForce::from_str(next("time_in_force")?).ok()?,
+            extra_fields: T::default(),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
+        };
+
+        let order = match order_type {
+            "Standard" => Order::Standard { common },
+            "IcebergOrder" => Order::IcebergOrder {
+                original_display: common.display_quantity,
+                common,
+                reserve_quantity: next("reserve_quantity")?.parse().ok()?,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
+            },
+            "PostOnly" => Order::PostOnly { common },
+            "TrailingStop" => Order::TrailingStop {
+                common,
+                trail_amount: next("trail_amount")?.parse().ok()?,
+                last_reference_price: next("last_reference_price")?.parse().ok()?,
+            },
+            "PeggedOrder" => Order::PeggedOrder {
+                common,
+                reference_price_offset: next("reference_price_offset")?.parse().ok()?,
+                reference_price_type: next("reference_price_type")?.parse().ok()?,
+            },
+            "MarketToLimit" => Order::MarketToLimit { common },
+            "ReserveOrder" => Order::ReserveOrder {
+                common,
+                reserve_quantity: next("reserve_quantity")?.parse().ok()?,
+                replenish_threshold: next("replenish_threshold")?.parse().ok()?,
+                auto_replenish: next("auto_replenish")?.parse().ok()?,
+                replenish_amount: match next("replenish_amount")? {
+                    "None" => None,
+                    amount => Some(amount.parse().ok()?),
+                },
+                auto_replenish_interval_ms: None,
+                last_reveal_ms: None,
+                max_total_display: None,
+                revealed_quantity: 0,
+            },
+            _ => return None,
+        };
+
+        // Trailing fields (e.g. optional ones) are left to the tolerant parser
+        if fields.next().is_some() {
+            return None;
+        }
+        Some(order)
+    }
+}
+
+/// Format an optional canonical field, writing `null` when it is absent
+fn canonical_option<V: fmt::Display>(value: Option<V>) -> String {
+    value.map_or_else(|| "null".to_string(), |value| value.to_string())
+}
+
+impl<T> Order<T> {
+    /// Get the canonical text form of the order, the cross-language contract for hashing.
+    ///
+    /// Unlike [`Display`](fmt::Display), which omits unset optional fields, every field is
+    /// always written, in a fixed order, as `key=value` pairs separated by `;` after the
+    /// variant name and a `:`:
+    ///
+    /// `id`, `price`, `display_quantity`, `reserve_quantity` (0 for orders without a reserve),
+    /// `side`, `timestamp`, `time_in_force`, `parent_id`, `oco_group`, `suppress_in_feed`,
+    /// followed by the variant's own fields in declaration order. Absent values are written
+    /// as `null`, numbers in decimal, booleans as `true`/`false`, and the remaining values as
+    /// their `Display` forms. Extra fields are not included. The form only changes together
+    /// with the snapshot package version, whose checksum is computed over it.
+    pub fn canonical_string(&self) -> String {
+        self.canonical_string_with(true)
+    }
+
+    /// Get the canonical text form, leaving out the iceberg refresh state when
+    /// `iceberg_state` is false as snapshot format version 2 did
+    pub(crate) fn canonical_string_with(&self, iceberg_state: bool) -> String {
+        let common = self.common();
+        let kind = match self {
+            Order::Standard { .. } => "Standard",
+            Order::IcebergOrder { .. } => "IcebergOrder",
+            Order::PostOnly { .. } => "PostOnly",
+            Order::TrailingStop { .. } => "TrailingStop",
+            Order::PeggedOrder { .. } => "PeggedOrder",
+            Order::MarketToLimit { .. } => "MarketToLimit",
+            Order::ReserveOrder { .. } => "ReserveOrder",
+        };
+        let mut canonical = format!(
+            "{kind}:id={};price={};display_quantity={};reserve_quantity={};side={};timestamp={};time_in_force={};parent_id={};oco_group={};suppress_in_feed={}",
+            common.id,
+            common.price,
+            common.display_quantity,
+            self.reserve_quantity(),
+            common.side,
+            common.timestamp,
+            common.time_in_force,
+            canonical_option(common.parent_id),
+            canonical_option(common.oco_group),
+            common.suppress_in_feed
+        );
+        let variant = match self {
+            Order::TrailingStop {
+                trail_amount,
+                last_reference_price,
+                ..
+            } => {
+                format!(";trail_amount={trail_amount};last_reference_price={last_reference_price}")
+            }
+            Order::PeggedOrder {
+                reference_price_offset,
+                reference_price_type,
+                ..
+            } => format!(
+                ";reference_price_offset={reference_price_offset};reference_price_type={reference_price_type}"
+            ),
+            Order::ReserveOrder {
+                replenish_threshold,
+                replenish_amount,
+                auto_replenish,
+                auto_replenish_interval_ms,
+                last_reveal_ms,
+                max_total_display,
+                revealed_quantity,
+                ..
+            } => format!(
+                ";replenish_threshold={replenish_threshold};replenish_amount={};auto_replenish={auto_replenish};auto_replenish_interval_ms={};last_reveal_ms={};max_total_display={};revealed_quantity={revealed_quantity}",
+                canonical_option(*replenish_amount),
+                canonical_option(*auto_replenish_interval_ms),
+                canonical_option(*last_reveal_ms),
+                canonical_option(*max_total_display)
+            ),
+            Order::IcebergOrder {
+                original_display,
+                refresh_strategy,
+                last_reveal_ms,
+                ..
+            } if iceberg_state => format!(
+                ";original_display={original_display};refresh_strategy={refresh_strategy};last_reveal_ms={}",
+                canonical_option(*last_reveal_ms)
+            ),
+            _ => String::new(),
+        };
+        canonical.push_str(&variant);
+        canonical
+    }
+}
+
 impl<T> fmt::Display for Order<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -681,11 +1308,26 @@ impl<T> fmt::Display for Order<T> {
             Order::IcebergOrder {
                 common,
                 reserve_quantity,
+                original_display,
+                refresh_strategy,
+                last_reveal_ms,
             } => {
                 write!(
                     f,
                     "IcebergOrder:{common};reserve_quantity={reserve_quantity}"
-                )
+                )?;
+                // Refresh fields are only written when they differ from a freshly entered
+                // order, keeping the classic format unchanged
+                if *original_display != common.display_quantity {
+                    write!(f, ";original_display={original_display}")?;
+                }
+                if *refresh_strategy != IcebergRefresh::default() {
+                    write!(f, ";refresh_strategy={refresh_strategy}")?;
+                }
+                if let Some(last_reveal) = last_reveal_ms {
+                    write!(f, ";last_reveal_ms={last_reveal}")?;
+                }
+                Ok(())
             }
             Order::PostOnly { common } => {
                 write!(f, "PostOnly:{common}")
@@ -719,13 +1361,32 @@ impl<T> fmt::Display for Order<T> {
                 replenish_threshold,
                 replenish_amount,
                 auto_replenish,
+                auto_replenish_interval_ms,
+                last_reveal_ms,
+                max_total_display,
+                revealed_quantity,
             } => {
                 let replenish_amount =
                     replenish_amount.map_or("None".to_string(), |v| v.to_string());
                 write!(
                     f,
                     "ReserveOrder:{common};reserve_quantity={reserve_quantity};replenish_threshold={replenish_threshold};auto_replenish={auto_replenish};replenish_amount={replenish_amount}",
-                )
+                )?;
+                // Timer fields are only written when set, keeping the classic format unchanged
+                if let Some(interval) = auto_replenish_interval_ms {
+                    write!(f, ";auto_replenish_interval_ms={interval}")?;
+                }
+                if let Some(last_reveal) = last_reveal_ms {
+                    write!(f, ";last_reveal_ms={last_reveal}")?;
+                }
+                // Likewise for the reveal cap and its progress
+                if let Some(max_total_display) = max_total_display {
+                    write!(f, ";max_total_display={max_total_display}")?;
+                }
+                if *revealed_quantity > 0 {
+                    write!(f, ";revealed_quantity={revealed_quantity}")?;
+                }
+                Ok(())
             }
         }
     }
@@ -749,7 +1410,7 @@ pub struct OrderMetadata {
 #[cfg(test)]
 mod tests {
     use crate::order::time_in_force::TimeInForce;
-    use crate::order::{Order, OrderCommon, OrderId, PegReferenceType, Side};
+    use crate::order::{IcebergRefresh, Order, OrderCommon, OrderId, PegReferenceType, Side};
     use std::str::FromStr;
     use tracing::info;
 
@@ -763,6 +1424,9 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         }
     }
@@ -778,8 +1442,14 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 4,
+            original_display: 1,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         }
     }
 
@@ -794,6 +1464,9 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         }
     }
@@ -809,6 +1482,9 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             trail_amount: 100,
             last_reference_price: 10100,
@@ -826,6 +1502,9 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reference_price_offset: -10,
             reference_price_type: PegReferenceType::BestBid,
@@ -843,6 +1522,9 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         }
     }
@@ -858,11 +1540,18 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 4,
             replenish_threshold: 1,
             replenish_amount: Some(2),
             auto_replenish: true,
+            auto_replenish_interval_ms: None,
+            last_reveal_ms: None,
+            max_total_display: None,
+            revealed_quantity: 0,
         }
     }
 
@@ -1231,8 +1920,10 @@ mod tests {
                     timestamp,
                     time_in_force,
                     extra_fields: _,
+                    ..
                 },
             reserve_quantity,
+            ..
         } = order
         {
             assert_eq!(id, OrderId::from_u64(124));
@@ -1262,6 +1953,7 @@ mod tests {
                     timestamp,
                     time_in_force,
                     extra_fields: _,
+                    ..
                 },
             trail_amount,
             last_reference_price,
@@ -1295,6 +1987,7 @@ mod tests {
                     timestamp,
                     time_in_force,
                     extra_fields: _,
+                    ..
                 },
             reference_price_offset,
             reference_price_type,
@@ -1495,6 +2188,9 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
 
@@ -1529,6 +2225,9 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reference_price_offset: -50,
             reference_price_type: PegReferenceType::BestAsk,
@@ -1565,6 +2264,9 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             trail_amount: 100,
             last_reference_price: 1100,
@@ -1601,6 +2303,9 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
 
@@ -1634,6 +2339,9 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             trail_amount: 100,
             last_reference_price: 1100,
@@ -1661,12 +2369,263 @@ mod tests {
         assert_eq!(hidden_reduced, 0);
         assert_eq!(remaining, 5); // 15 - 10 = 5 remaining
     }
+
+    #[test]
+    fn test_erase_extra_fields() {
+        let order = Order::<String>::ReserveOrder {
+            common: OrderCommon {
+                id: OrderId::from_u64(130),
+                price: 10000,
+                display_quantity: 10,
+                side: Side::Sell,
+                timestamp: 1616823000000,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: "strategy-a".to_string(),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+            reserve_quantity: 40,
+            replenish_threshold: 5,
+            replenish_amount: Some(10),
+            auto_replenish: true,
+            auto_replenish_interval_ms: None,
+            last_reveal_ms: None,
+            max_total_display: None,
+            revealed_quantity: 0,
+        };
+
+        let erased = order.erased_clone();
+        assert_eq!(order.extra_fields(), "strategy-a");
+        assert_eq!(erased, order.clone().map_extra_fields(|_| ()));
+        assert_eq!(order.erase_extra(), erased);
+
+        let pegged = create_pegged_order();
+        assert_eq!(pegged.erased_clone(), pegged);
+    }
+
+    #[test]
+    fn test_reprice_post_only() {
+        // Buy post-only at 10000 crossing an ask at 9990
+        let buy = create_post_only_order();
+        let repriced = buy.reprice_post_only(9990, 5);
+        assert_eq!(repriced.price(), 9985);
+        assert_eq!(repriced.id(), buy.id());
+        assert!(repriced.is_post_only());
+
+        // Not crossing: unchanged
+        assert_eq!(buy.reprice_post_only(10010, 5).price(), 10000);
+
+        // Sell post-only crossing a bid at 10005
+        let mut sell = create_post_only_order();
+        if let Order::PostOnly { common } = &mut sell {
+            common.side = Side::Sell;
+        }
+        assert_eq!(sell.reprice_post_only(10005, 5).price(), 10010);
+        assert_eq!(sell.reprice_post_only(9995, 5).price(), 10000);
+
+        // Non post-only orders are never repriced
+        assert_eq!(
+            create_standard_order().reprice_post_only(9990, 5).price(),
+            10000
+        );
+    }
+
+    #[test]
+    fn test_parent_id_round_trip() {
+        let mut order = create_iceberg_order();
+        assert_eq!(order.parent_id(), None);
+        assert!(!order.to_string().contains("parent_id"));
+
+        if let Order::IcebergOrder { common, .. } = &mut order {
+            common.parent_id = Some(OrderId::from_u64(7));
+        }
+        assert_eq!(order.parent_id(), Some(OrderId::from_u64(7)));
+
+        let parsed = Order::<()>::from_str(&order.to_string()).unwrap();
+        assert_eq!(parsed, order);
+
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(serde_json::from_str::<Order<()>>(&json).unwrap(), order);
+
+        // JSON written before the field existed still deserializes
+        let legacy = serde_json::to_string(&create_standard_order())
+            .unwrap()
+            .replace(",\"parent_id\":null", "");
+        assert!(!legacy.contains("parent_id"));
+        let legacy_order: Order<()> = serde_json::from_str(&legacy).unwrap();
+        assert_eq!(legacy_order.parent_id(), None);
+    }
+
+    #[test]
+    fn test_from_str_fast_matches_from_str() {
+        let mut with_parent = create_standard_order();
+        if let Order::Standard { common } = &mut with_parent {
+            common.parent_id = Some(OrderId::from_u64(1));
+        }
+
+        let mut inputs: Vec<String> = [
+            create_standard_order(),
+            create_iceberg_order(),
+            create_post_only_order(),
+            create_trailing_stop_order(),
+            create_pegged_order(),
+            create_market_to_limit_order(),
+            create_reserve_order(),
+            with_parent,
+        ]
+        .iter()
+        .map(|order| order.to_string())
+        .collect();
+        inputs.extend(
+            [
+                // Reordered fields only the tolerant parser accepts
+                "Standard:price=10000;id=00000000-0000-007b-0000-000000000000;display_quantity=5;side=BUY;timestamp=1616823000000;time_in_force=GTC",
+                "Standard:id=00000000-0000-007b-0000-000000000000;price=abc;display_quantity=5;side=BUY;timestamp=1616823000000;time_in_force=GTC",
+                "Standard:id=00000000-0000-007b-0000-000000000000;price=10000",
+                "Unknown:id=00000000-0000-007b-0000-000000000000;price=10000;display_quantity=5;side=BUY;timestamp=1616823000000;time_in_force=GTC",
+                "no separator",
+            ]
+            .iter()
+            .map(|s| s.to_string()),
+        );
+
+        for input in &inputs {
+            let slow = Order::<()>::from_str(input);
+            let fast = Order::<()>::from_str_fast(input);
+            assert_eq!(format!("{fast:?}"), format!("{slow:?}"), "input: {input}");
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let with_threshold = |display_quantity, threshold, auto| {
+            let mut order = create_reserve_order();
+            if let Order::ReserveOrder {
+                common,
+                replenish_threshold,
+                auto_replenish,
+                ..
+            } = &mut order
+            {
+                common.display_quantity = display_quantity;
+                *replenish_threshold = threshold;
+                *auto_replenish = auto;
+            }
+            order.normalize();
+            match order {
+                Order::ReserveOrder {
+                    replenish_threshold,
+                    ..
+                } => replenish_threshold,
+                _ => unreachable!(),
+            }
+        };
+
+        assert_eq!(with_threshold(10, 25, true), 10);
+        assert_eq!(with_threshold(10, 4, true), 4);
+        assert_eq!(with_threshold(10, 0, true), 1);
+        assert_eq!(with_threshold(10, 0, false), 0);
+        assert_eq!(with_threshold(0, 5, false), 0);
+        assert_eq!(with_threshold(0, 5, true), 1);
+
+        // Normalizing is idempotent and leaves other variants alone
+        let mut reserve = create_reserve_order();
+        reserve.normalize();
+        assert_eq!(reserve, create_reserve_order());
+        for order in [
+            create_standard_order(),
+            create_iceberg_order(),
+            create_pegged_order(),
+            create_trailing_stop_order(),
+        ] {
+            let mut normalized = order;
+            normalized.normalize();
+            assert_eq!(normalized, order);
+        }
+    }
+
+    #[test]
+    fn test_passes_price_band() {
+        // create_standard_order is priced at 10000; the band is a share of the reference
+        let order = create_standard_order();
+        assert!(order.passes_price_band(10000, 0));
+        assert!(order.passes_price_band(10100, 100));
+        assert!(order.passes_price_band(9900, 102));
+        assert!(!order.passes_price_band(10110, 100));
+        assert!(!order.passes_price_band(9899, 100));
+        assert!(!order.passes_price_band(0, u32::MAX));
+        assert!(order.passes_price_band(u64::MAX, u32::MAX));
+    }
+
+    #[test]
+    fn test_oco_group_round_trip() {
+        let mut order = create_standard_order();
+        assert_eq!(order.oco_group(), None);
+        assert!(!order.to_string().contains("oco_group"));
+
+        if let Order::Standard { common } = &mut order {
+            common.oco_group = Some(42);
+        }
+        assert_eq!(order.oco_group(), Some(42));
+        assert!(order.to_string().ends_with(";oco_group=42"));
+
+        let parsed = Order::<()>::from_str(&order.to_string()).unwrap();
+        assert_eq!(parsed, order);
+
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(serde_json::from_str::<Order<()>>(&json).unwrap(), order);
+    }
+
+    #[test]
+    fn test_suppress_in_feed_round_trip() {
+        let mut order = create_standard_order();
+        assert!(!order.is_suppressed_in_feed());
+        assert!(!order.to_string().contains("suppress_in_feed"));
+
+        if let Order::Standard { common } = &mut order {
+            common.suppress_in_feed = true;
+        }
+        assert!(order.is_suppressed_in_feed());
+        assert!(order.to_string().ends_with(";suppress_in_feed=true"));
+        assert_eq!(Order::<()>::from_str(&order.to_string()).unwrap(), order);
+        assert_eq!(
+            Order::<()>::from_str_fast(&order.to_string()).unwrap(),
+            order
+        );
+
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(serde_json::from_str::<Order<()>>(&json).unwrap(), order);
+
+        let invalid = order.to_string().replace("=true", "=maybe");
+        assert!(Order::<()>::from_str(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_price_improvement() {
+        // create_standard_order is a buy at 10000
+        let buy = create_standard_order();
+        assert_eq!(buy.side(), Side::Buy);
+        assert_eq!(buy.price_improvement(9990), 10);
+        assert_eq!(buy.price_improvement(10005), -5);
+        assert_eq!(buy.price_improvement(10000), 0);
+
+        let mut sell = create_standard_order();
+        if let Order::Standard { common } = &mut sell {
+            common.side = Side::Sell;
+        }
+        assert_eq!(sell.price_improvement(10010), 10);
+        assert_eq!(sell.price_improvement(9995), -5);
+        assert_eq!(sell.price_improvement(10000), 0);
+
+        assert_eq!(sell.price_improvement(u64::MAX), i64::MAX);
+    }
 }
 
 #[cfg(test)]
 mod test_order_type_display {
     use crate::order::time_in_force::TimeInForce;
-    use crate::order::{Order, OrderCommon, OrderId, PegReferenceType, Side};
+    use crate::order::{IcebergRefresh, Order, OrderCommon, OrderId, PegReferenceType, Side};
     use std::str::FromStr;
 
     #[test]
@@ -1680,6 +2639,9 @@ mod test_order_type_display {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
 
@@ -1724,8 +2686,14 @@ mod test_order_type_display {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 4,
+            original_display: 1,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         };
 
         let display_str = order.to_string();
@@ -1761,6 +2729,57 @@ mod test_order_type_display {
         }
     }
 
+    #[test]
+    fn test_iceberg_order_refresh_state_round_trips() {
+        let order = Order::<()>::IcebergOrder {
+            common: OrderCommon {
+                id: OrderId::from_u64(124),
+                price: 10000,
+                display_quantity: 2,
+                side: Side::Sell,
+                timestamp: 1616823000000,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+            reserve_quantity: 4,
+            original_display: 5,
+            refresh_strategy: IcebergRefresh::Fixed(3),
+            last_reveal_ms: Some(1616823000500),
+        };
+
+        let display_str = order.to_string();
+        assert!(display_str.ends_with(
+            ";reserve_quantity=4;original_display=5;refresh_strategy=Fixed(3);last_reveal_ms=1616823000500"
+        ));
+        assert_eq!(Order::<()>::from_str(&display_str).unwrap(), order);
+
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(serde_json::from_str::<Order<()>>(&json).unwrap(), order);
+
+        // Orders serialized before the refresh state existed get the defaults
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let fields = value["IcebergOrder"].as_object_mut().unwrap();
+        for field in ["original_display", "refresh_strategy", "last_reveal_ms"] {
+            fields.remove(field).unwrap();
+        }
+        match serde_json::from_value::<Order<()>>(value).unwrap() {
+            Order::IcebergOrder {
+                original_display,
+                refresh_strategy,
+                last_reveal_ms,
+                ..
+            } => {
+                assert_eq!(original_display, 0);
+                assert_eq!(refresh_strategy, IcebergRefresh::OriginalDisplay);
+                assert_eq!(last_reveal_ms, None);
+            }
+            other => panic!("Expected IcebergOrder, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_post_only_order_display() {
         let order = Order::<()>::PostOnly {
@@ -1772,6 +2791,9 @@ mod test_order_type_display {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
 
@@ -1807,6 +2829,9 @@ mod test_order_type_display {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             trail_amount: 100,
             last_reference_price: 10100,
@@ -1841,6 +2866,9 @@ mod test_order_type_display {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reference_price_offset: -50,
             reference_price_type: PegReferenceType::BestAsk,
@@ -1875,6 +2903,9 @@ mod test_order_type_display {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
 
@@ -1905,11 +2936,18 @@ mod test_order_type_display {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 4,
             replenish_threshold: 0,
             replenish_amount: Some(1),
             auto_replenish: false,
+            auto_replenish_interval_ms: None,
+            last_reveal_ms: None,
+            max_total_display: None,
+            revealed_quantity: 0,
         };
 
         let display_str = order.to_string();
@@ -1923,6 +2961,56 @@ mod test_order_type_display {
         assert!(display_str.contains("replenish_threshold=0"));
         assert!(display_str.contains("auto_replenish=false"));
         assert!(display_str.contains("replenish_amount=1"));
+        assert!(!display_str.contains("max_total_display"));
+        assert!(!display_str.contains("revealed_quantity"));
+    }
+
+    #[test]
+    fn test_reserve_order_max_total_display_round_trip() {
+        let order = Order::<()>::ReserveOrder {
+            common: OrderCommon {
+                id: OrderId::from_u64(129),
+                price: 10000,
+                display_quantity: 5,
+                side: Side::Sell,
+                timestamp: 1616823000000,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+            reserve_quantity: 80,
+            replenish_threshold: 0,
+            replenish_amount: Some(10),
+            auto_replenish: true,
+            auto_replenish_interval_ms: None,
+            last_reveal_ms: None,
+            max_total_display: Some(25),
+            revealed_quantity: 20,
+        };
+        assert_eq!(order.revealable_reserve(), 5);
+        assert_eq!(order.fillable_quantity(), 10);
+
+        let display_str = order.to_string();
+        assert!(display_str.ends_with(";max_total_display=25;revealed_quantity=20"));
+        assert_eq!(Order::<()>::from_str(&display_str).unwrap(), order);
+        assert_eq!(Order::<()>::from_str_fast(&display_str).unwrap(), order);
+
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(serde_json::from_str::<Order<()>>(&json).unwrap(), order);
+
+        // Consuming the visible part reveals only what the cap allows
+        let (consumed, updated, hidden_reduced, remaining) = order.match_against(5);
+        assert_eq!((consumed, hidden_reduced, remaining), (5, 5, 0));
+        let updated = updated.unwrap();
+        assert_eq!(updated.display_quantity(), 5);
+        assert_eq!(updated.revealable_reserve(), 0);
+
+        // The cap is exhausted, so the order is removed with its reserve
+        let (consumed, updated, hidden_reduced, _) = updated.match_against(5);
+        assert_eq!((consumed, hidden_reduced), (5, 0));
+        assert!(updated.is_none());
     }
 }
 
@@ -2171,6 +3259,49 @@ mod from_str_specific_tests {
         }
     }
 
+    #[test]
+    fn test_resolve_pegged_price_level_references() {
+        let pegged =
+            |side, reference_price_offset, reference_price_type| Order::<()>::PeggedOrder {
+                common: OrderCommon {
+                    id: OrderId::from_u64(127),
+                    price: 10000,
+                    display_quantity: 5,
+                    side,
+                    timestamp: 1616823000000,
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
+                },
+                reference_price_offset,
+                reference_price_type,
+            };
+        let bids = [10000, 9990, 9980];
+        let asks = [10010, 10020, 10030];
+
+        let order = pegged(Side::Buy, -5, PegReferenceType::Level(2));
+        assert_eq!(order.resolve_pegged_price(&bids, &asks, None), Some(9975));
+        let order = pegged(Side::Sell, 5, PegReferenceType::PrimaryPeg);
+        assert_eq!(order.resolve_pegged_price(&bids, &asks, None), Some(10015));
+        let order = pegged(Side::Sell, -1, PegReferenceType::MarketPeg);
+        assert_eq!(order.resolve_pegged_price(&bids, &asks, None), Some(9999));
+        let order = pegged(Side::Buy, 0, PegReferenceType::Level(3));
+        assert_eq!(order.resolve_pegged_price(&bids, &asks, None), None);
+        let order = pegged(Side::Buy, -10001, PegReferenceType::BestBid);
+        assert_eq!(order.resolve_pegged_price(&bids, &asks, None), None);
+
+        // The new reference types survive both text forms
+        let order = pegged(Side::Buy, -5, PegReferenceType::Level(2));
+        assert!(order.to_string().contains("reference_price_type=Level(2)"));
+        assert_eq!(Order::<()>::from_str(&order.to_string()).unwrap(), order);
+        assert_eq!(
+            Order::<()>::from_str_fast(&order.to_string()).unwrap(),
+            order
+        );
+    }
+
     #[test]
     fn test_from_str_invalid_reserve_order_auto_replenish() {
         let input = "ReserveOrder:id=00000000-0000-0081-0000-000000000000;price=10000;display_quantity=1;reserve_quantity=4;side=SELL;timestamp=1616823000000;time_in_force=GTC;replenish_threshold=0;replenish_amount=1;auto_replenish=invalid";
@@ -2270,11 +3401,18 @@ mod from_str_specific_tests {
                     timestamp: 1616823000000,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
                 reserve_quantity: 4,
                 replenish_threshold: 0,
                 replenish_amount: Some(1),
                 auto_replenish: false,
+                auto_replenish_interval_ms: None,
+                last_reveal_ms: None,
+                max_total_display: None,
+                revealed_quantity: 0,
             },
             Order::MarketToLimit {
                 common: OrderCommon {
@@ -2285,6 +3423,9 @@ mod from_str_specific_tests {
                     timestamp: 1616823000000,
                     time_in_force: TimeInForce::Ioc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
             },
             Order::PeggedOrder {
@@ -2296,6 +3437,9 @@ mod from_str_specific_tests {
                     timestamp: 1616823000000,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
                 reference_price_offset: -50,
                 reference_price_type: PegReferenceType::BestAsk,
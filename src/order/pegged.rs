@@ -1,4 +1,5 @@
 use crate::errors::PriceLevelError;
+use crate::order::{Order, Price, Side, Timestamp};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
@@ -14,6 +15,44 @@ pub enum PegReferenceType {
     MidPrice,
     /// Pegged to last trade price
     LastTrade,
+    /// Pegged to the best price on the order's own side
+    PrimaryPeg,
+    /// Pegged to the best price on the opposite side
+    MarketPeg,
+    /// Pegged to the Nth price level on the order's own side, counting away from the
+    /// spread; `Level(0)` is the best price
+    Level(u8),
+}
+
+impl PegReferenceType {
+    /// Get the reference price for an order on `side`, or `None` if the book does not have it.
+    ///
+    /// `bids` and `asks` are the level prices of each side, best first: descending bids and
+    /// ascending asks. The mid price is truncated to a whole price.
+    pub fn reference_price(
+        &self,
+        side: Side,
+        bids: &[Price],
+        asks: &[Price],
+        last_trade: Option<Price>,
+    ) -> Option<Price> {
+        let (same, opposite) = match side {
+            Side::Buy => (bids, asks),
+            Side::Sell => (asks, bids),
+        };
+        match self {
+            PegReferenceType::BestBid => bids.first().copied(),
+            PegReferenceType::BestAsk => asks.first().copied(),
+            PegReferenceType::MidPrice => {
+                let (bid, ask) = (*bids.first()?, *asks.first()?);
+                Some(((bid as u128 + ask as u128) / 2) as Price)
+            }
+            PegReferenceType::LastTrade => last_trade,
+            PegReferenceType::PrimaryPeg => same.first().copied(),
+            PegReferenceType::MarketPeg => opposite.first().copied(),
+            PegReferenceType::Level(level) => same.get(*level as usize).copied(),
+        }
+    }
 }
 
 impl FromStr for PegReferenceType {
@@ -25,9 +64,16 @@ impl FromStr for PegReferenceType {
             "BestAsk" | "BESTASK" | "bestask" => Ok(PegReferenceType::BestAsk),
             "MidPrice" | "MIDPRICE" | "midprice" => Ok(PegReferenceType::MidPrice),
             "LastTrade" | "LASTTRADE" | "lasttrade" => Ok(PegReferenceType::LastTrade),
-            _ => Err(PriceLevelError::ParseError {
-                message: s.to_string(),
-            }),
+            "PrimaryPeg" | "PRIMARYPEG" | "primarypeg" => Ok(PegReferenceType::PrimaryPeg),
+            "MarketPeg" | "MARKETPEG" | "marketpeg" => Ok(PegReferenceType::MarketPeg),
+            _ => ["Level(", "LEVEL(", "level("]
+                .iter()
+                .find_map(|prefix| s.strip_prefix(prefix)?.strip_suffix(')'))
+                .and_then(|level| level.parse().ok())
+                .map(PegReferenceType::Level)
+                .ok_or_else(|| PriceLevelError::ParseError {
+                    message: s.to_string(),
+                }),
         }
     }
 }
@@ -39,14 +85,42 @@ impl fmt::Display for PegReferenceType {
             PegReferenceType::BestAsk => write!(f, "BestAsk"),
             PegReferenceType::MidPrice => write!(f, "MidPrice"),
             PegReferenceType::LastTrade => write!(f, "LastTrade"),
+            PegReferenceType::PrimaryPeg => write!(f, "PrimaryPeg"),
+            PegReferenceType::MarketPeg => write!(f, "MarketPeg"),
+            PegReferenceType::Level(level) => write!(f, "Level({level})"),
         }
     }
 }
 
+/// How a pegged order's time priority is treated when it reprices to a new level.
+///
+/// Exchanges differ here: some keep the order's original timestamp, so orders repriced to
+/// the same level keep their relative priority, while others treat the reprice as a new
+/// entry that joins the back of the queue.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PegRepricePolicy {
+    /// The repriced order keeps its original timestamp
+    #[default]
+    PreserveTimestamp,
+    /// The repriced order is stamped with the reprice time and loses its priority
+    NewTimestamp,
+}
+
+/// A pegged order removed from its level by
+/// [`PriceLevel::reprice_pegged`](crate::PriceLevel::reprice_pegged), ready to be added at its
+/// new price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepricedOrder {
+    /// The order at its new price, with its timestamp set according to the policy
+    pub order: Order<()>,
+    /// The timestamp the order had before repricing
+    pub original_timestamp: Timestamp,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::errors::PriceLevelError;
-    use crate::order::PegReferenceType;
+    use crate::order::{PegReferenceType, Side};
     use std::str::FromStr;
 
     #[test]
@@ -154,6 +228,61 @@ mod tests {
         assert_eq!(PegReferenceType::LastTrade.to_string(), "LastTrade");
     }
 
+    #[test]
+    fn test_peg_reference_type_level_variants() {
+        assert_eq!(PegReferenceType::PrimaryPeg.to_string(), "PrimaryPeg");
+        assert_eq!(PegReferenceType::MarketPeg.to_string(), "MarketPeg");
+        assert_eq!(PegReferenceType::Level(3).to_string(), "Level(3)");
+        assert_eq!(
+            PegReferenceType::from_str("level(7)").unwrap(),
+            PegReferenceType::Level(7)
+        );
+        for invalid in ["Level()", "Level(256)", "Level(-1)", "Level(3", "Level3"] {
+            assert!(PegReferenceType::from_str(invalid).is_err(), "{invalid}");
+        }
+
+        let serialized = serde_json::to_string(&PegReferenceType::Level(2)).unwrap();
+        let deserialized: PegReferenceType = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, PegReferenceType::Level(2));
+    }
+
+    #[test]
+    fn test_peg_reference_price_against_ladder() {
+        let bids = [10000, 9990, 9980];
+        let asks = [10010, 10020];
+
+        let resolve = |reference: PegReferenceType, side| {
+            reference.reference_price(side, &bids, &asks, Some(10005))
+        };
+        assert_eq!(resolve(PegReferenceType::BestBid, Side::Sell), Some(10000));
+        assert_eq!(resolve(PegReferenceType::BestAsk, Side::Buy), Some(10010));
+        assert_eq!(resolve(PegReferenceType::MidPrice, Side::Buy), Some(10005));
+        assert_eq!(resolve(PegReferenceType::LastTrade, Side::Buy), Some(10005));
+        assert_eq!(
+            resolve(PegReferenceType::PrimaryPeg, Side::Buy),
+            Some(10000)
+        );
+        assert_eq!(
+            resolve(PegReferenceType::PrimaryPeg, Side::Sell),
+            Some(10010)
+        );
+        assert_eq!(resolve(PegReferenceType::MarketPeg, Side::Buy), Some(10010));
+        assert_eq!(
+            resolve(PegReferenceType::MarketPeg, Side::Sell),
+            Some(10000)
+        );
+        assert_eq!(resolve(PegReferenceType::Level(0), Side::Buy), Some(10000));
+        assert_eq!(resolve(PegReferenceType::Level(2), Side::Buy), Some(9980));
+        assert_eq!(resolve(PegReferenceType::Level(1), Side::Sell), Some(10020));
+        assert_eq!(resolve(PegReferenceType::Level(2), Side::Sell), None);
+
+        // A one-sided book has no mid price
+        assert_eq!(
+            PegReferenceType::MidPrice.reference_price(Side::Buy, &bids, &[], None),
+            None
+        );
+    }
+
     #[test]
     fn test_peg_reference_type_error_display() {
         let error = PriceLevelError::ParseError {
@@ -188,6 +317,10 @@ mod tests {
             PegReferenceType::BestAsk,
             PegReferenceType::MidPrice,
             PegReferenceType::LastTrade,
+            PegReferenceType::PrimaryPeg,
+            PegReferenceType::MarketPeg,
+            PegReferenceType::Level(0),
+            PegReferenceType::Level(255),
         ] {
             let string_representation = reference_type.to_string();
             let parsed_back = PegReferenceType::from_str(&string_representation).unwrap();
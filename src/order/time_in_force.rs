@@ -32,8 +32,17 @@ pub enum TimeInForce {
     #[serde(rename(serialize = "DAY"))]
     #[serde(alias = "day", alias = "Day", alias = "DAY")]
     Day,
+
+    /// Good 'Til Time - The order expires at a time of day, expressed as seconds since
+    /// midnight (UTC) of the session it was placed in. `Gtt(34200)` expires at 09:30.
+    #[serde(rename(serialize = "GTT"))]
+    #[serde(alias = "gtt", alias = "Gtt", alias = "GTT")]
+    Gtt(u32),
 }
 
+/// Milliseconds in a day, used to locate the session a GTT order belongs to
+const MILLIS_PER_DAY: u64 = 86_400_000;
+
 impl TimeInForce {
     /// Returns true if the order should be canceled after attempting to match
     pub fn is_immediate(&self) -> bool {
@@ -42,13 +51,34 @@ impl TimeInForce {
 
     /// Returns true if the order has a specific expiration time
     pub fn has_expiry(&self) -> bool {
-        matches!(self, Self::Gtd(_) | Self::Day)
+        matches!(self, Self::Gtd(_) | Self::Day | Self::Gtt(_))
     }
 
     /// Checks if an order with this time in force has expired
+    ///
+    /// Timestamps are in milliseconds since the epoch. A `Gtt` order is evaluated against the
+    /// UTC day of `current_timestamp`; use [`is_expired_in_session`](Self::is_expired_in_session)
+    /// when the order's session date is known.
     pub fn is_expired(&self, current_timestamp: u64, market_close_timestamp: Option<u64>) -> bool {
+        let session_date = current_timestamp - current_timestamp % MILLIS_PER_DAY;
+        self.is_expired_in_session(current_timestamp, session_date, market_close_timestamp)
+    }
+
+    /// Checks if an order placed in the session starting at `session_date` has expired
+    ///
+    /// `session_date` is midnight (UTC) of the order's session in milliseconds since the epoch;
+    /// it is only used by `Gtt`, which expires at `session_date` plus its time of day.
+    pub fn is_expired_in_session(
+        &self,
+        current_timestamp: u64,
+        session_date: u64,
+        market_close_timestamp: Option<u64>,
+    ) -> bool {
         match self {
             Self::Gtd(expiry) => current_timestamp >= *expiry,
+            Self::Gtt(seconds) => {
+                current_timestamp >= session_date.saturating_add(*seconds as u64 * 1000)
+            }
             Self::Day => {
                 if let Some(close) = market_close_timestamp {
                     current_timestamp >= close
@@ -59,6 +89,59 @@ impl TimeInForce {
             _ => false,
         }
     }
+
+    /// Compact numeric code for binary formats.
+    ///
+    /// | Code | Variant |
+    /// |------|---------|
+    /// | 0 | `Gtc` |
+    /// | 1 | `Ioc` |
+    /// | 2 | `Fok` |
+    /// | 3 | `Gtd` |
+    /// | 4 | `Day` |
+    /// | 5 | `Gtt` |
+    ///
+    /// The `Gtd` expiry and `Gtt` time of day are not part of the code; store
+    /// [`expiry`](Self::expiry) alongside it.
+    /// These assignments are part of the wire format and must never change.
+    pub fn as_u16(&self) -> u16 {
+        match self {
+            Self::Gtc => 0,
+            Self::Ioc => 1,
+            Self::Fok => 2,
+            Self::Gtd(_) => 3,
+            Self::Day => 4,
+            Self::Gtt(_) => 5,
+        }
+    }
+
+    /// Decode a time in force from its [`as_u16`](Self::as_u16) code.
+    ///
+    /// `expiry` is only used for `Gtd` and `Gtt` and ignored for every other code.
+    pub fn from_u16(code: u16, expiry: u64) -> Result<Self, PriceLevelError> {
+        match code {
+            0 => Ok(Self::Gtc),
+            1 => Ok(Self::Ioc),
+            2 => Ok(Self::Fok),
+            3 => Ok(Self::Gtd(expiry)),
+            4 => Ok(Self::Day),
+            5 => Ok(Self::Gtt(time_of_day(expiry)?)),
+            _ => Err(PriceLevelError::InvalidFieldValue {
+                field: "time_in_force".to_string(),
+                value: code.to_string(),
+            }),
+        }
+    }
+
+    /// Returns the expiry of a `Gtd` order (timestamp) or a `Gtt` order (seconds since
+    /// midnight)
+    pub fn expiry(&self) -> Option<u64> {
+        match self {
+            Self::Gtd(expiry) => Some(*expiry),
+            Self::Gtt(seconds) => Some(*seconds as u64),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for TimeInForce {
@@ -69,6 +152,7 @@ impl fmt::Display for TimeInForce {
             TimeInForce::Fok => write!(f, "FOK"),
             TimeInForce::Gtd(expiry) => write!(f, "GTD-{expiry}"),
             TimeInForce::Day => write!(f, "DAY"),
+            TimeInForce::Gtt(seconds) => write!(f, "GTT-{seconds}"),
         }
     }
 }
@@ -97,6 +181,14 @@ impl FromStr for TimeInForce {
                     }),
                 }
             }
+            s if s.starts_with("GTT-") => {
+                let seconds = s[4..]
+                    .parse::<u64>()
+                    .map_err(|_| PriceLevelError::ParseError {
+                        message: format!("Invalid time of day in GTT: {}", &s[4..]),
+                    })?;
+                Ok(TimeInForce::Gtt(time_of_day(seconds)?))
+            }
             _ => Err(PriceLevelError::ParseError {
                 message: format!("Invalid TimeInForce: {s}"),
             }),
@@ -104,6 +196,17 @@ impl FromStr for TimeInForce {
     }
 }
 
+/// Validate a GTT time of day, rejecting values past the end of the day
+fn time_of_day(seconds: u64) -> Result<u32, PriceLevelError> {
+    if seconds < MILLIS_PER_DAY / 1000 {
+        Ok(seconds as u32)
+    } else {
+        Err(PriceLevelError::ParseError {
+            message: format!("Invalid time of day in GTT: {seconds}"),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::errors::PriceLevelError;
@@ -436,4 +539,58 @@ mod tests {
         let time_in_force = TimeInForce::Day;
         assert_eq!(time_in_force.to_string(), "DAY");
     }
+
+    #[test]
+    fn test_time_in_force_u16_round_trip() {
+        let variants = [
+            TimeInForce::Gtc,
+            TimeInForce::Ioc,
+            TimeInForce::Fok,
+            TimeInForce::Gtd(0),
+            TimeInForce::Gtd(u64::MAX),
+            TimeInForce::Day,
+        ];
+        for tif in variants {
+            let decoded = TimeInForce::from_u16(tif.as_u16(), tif.expiry().unwrap_or(0)).unwrap();
+            assert_eq!(decoded, tif);
+        }
+
+        assert_eq!(TimeInForce::Gtd(u64::MAX).as_u16(), 3);
+        assert_eq!(TimeInForce::Gtd(u64::MAX).expiry(), Some(u64::MAX));
+        assert_eq!(TimeInForce::Day.expiry(), None);
+        // The expiry is ignored for codes without one
+        assert_eq!(TimeInForce::from_u16(0, 42).unwrap(), TimeInForce::Gtc);
+        assert!(TimeInForce::from_u16(6, 0).is_err());
+    }
+
+    #[test]
+    fn test_time_in_force_gtt() {
+        let gtt = TimeInForce::from_str("GTT-34200").unwrap();
+        assert_eq!(gtt, TimeInForce::Gtt(34200));
+        assert_eq!(gtt.to_string(), "GTT-34200");
+        assert_eq!(TimeInForce::from_str("gtt-0").unwrap(), TimeInForce::Gtt(0));
+        assert!(TimeInForce::from_str("GTT-86400").is_err());
+        assert!(TimeInForce::from_str("GTT-abc").is_err());
+        assert!(gtt.has_expiry());
+        assert!(!gtt.is_immediate());
+
+        let json = serde_json::to_string(&gtt).unwrap();
+        assert_eq!(serde_json::from_str::<TimeInForce>(&json).unwrap(), gtt);
+
+        let decoded = TimeInForce::from_u16(gtt.as_u16(), gtt.expiry().unwrap()).unwrap();
+        assert_eq!(decoded, gtt);
+        assert!(TimeInForce::from_u16(5, 86_400).is_err());
+
+        // 2021-03-27 00:00:00 UTC
+        let session = 1_616_803_200_000;
+        let nine_thirty = session + 34_200_000;
+        assert!(!gtt.is_expired_in_session(nine_thirty - 1, session, None));
+        assert!(gtt.is_expired_in_session(nine_thirty, session, None));
+        // An order from the previous session stays expired the next morning
+        assert!(gtt.is_expired_in_session(session + 86_400_000 + 1_000, session, None));
+
+        // Without a session date the current UTC day is used
+        assert!(!gtt.is_expired(nine_thirty - 1, None));
+        assert!(gtt.is_expired(nine_thirty, None));
+    }
 }
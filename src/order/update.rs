@@ -1,10 +1,11 @@
 use crate::errors::PriceLevelError;
+use crate::order::Order;
 use crate::order::base::{OrderId, Side};
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 /// Represents a request to update an existing order
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OrderUpdate {
     /// Update the price of an order
     UpdatePrice {
@@ -49,6 +50,19 @@ pub enum OrderUpdate {
         /// Side of the market (unchanged)
         side: Side,
     },
+
+    /// Replace an order wholesale, which may change its type, e.g. from standard to iceberg,
+    /// but not its side.
+    ///
+    /// This is a runtime operation: its string form is for logging and cannot be parsed back.
+    ReplaceFull {
+        /// ID of the order to replace
+        order_id: OrderId,
+        /// The replacement order, which must keep the same ID
+        new_order: Box<Order<()>>,
+        /// Keep the replaced order's queue position instead of moving to the back
+        preserve_priority: bool,
+    },
 }
 
 impl FromStr for OrderUpdate {
@@ -198,6 +212,16 @@ impl std::fmt::Display for OrderUpdate {
                     "Replace:order_id={order_id};price={price};quantity={quantity};side={side}"
                 )
             }
+            OrderUpdate::ReplaceFull {
+                order_id,
+                new_order,
+                preserve_priority,
+            } => {
+                write!(
+                    f,
+                    "ReplaceFull:order_id={order_id};preserve_priority={preserve_priority};new_order={new_order}"
+                )
+            }
         }
     }
 }
@@ -589,4 +613,32 @@ mod tests_order_update {
             "Cancel:order_id=00000000-0000-007b-0000-000000000000"
         );
     }
+
+    #[test]
+    fn test_replace_full_display_is_not_parseable() {
+        use crate::order::{Order, OrderCommon, TimeInForce};
+
+        let update = OrderUpdate::ReplaceFull {
+            order_id: OrderId::from_u64(1),
+            new_order: Box::new(Order::Standard {
+                common: OrderCommon {
+                    id: OrderId::from_u64(1),
+                    price: 10000,
+                    display_quantity: 5,
+                    side: Side::Buy,
+                    timestamp: 1616823000000,
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
+                },
+            }),
+            preserve_priority: true,
+        };
+        let display = update.to_string();
+        assert!(display.starts_with("ReplaceFull:order_id="));
+        assert!(display.contains(";preserve_priority=true;new_order=Standard:"));
+        assert!(OrderUpdate::from_str(&display).is_err());
+    }
 }
@@ -0,0 +1,173 @@
+use crate::order::Order;
+
+/// Type-specific handling of orders, dispatched by [`Order::accept`].
+///
+/// There is one method per order variant, each receiving the whole order. Every method
+/// does nothing by default, so a visitor only implements the variants it cares about.
+pub trait OrderVisitor<T = ()> {
+    /// Visit a standard limit order
+    fn visit_standard(&mut self, _order: &Order<T>) {}
+
+    /// Visit an iceberg order
+    fn visit_iceberg(&mut self, _order: &Order<T>) {}
+
+    /// Visit a post-only order
+    fn visit_post_only(&mut self, _order: &Order<T>) {}
+
+    /// Visit a trailing stop order
+    fn visit_trailing_stop(&mut self, _order: &Order<T>) {}
+
+    /// Visit a pegged order
+    fn visit_pegged(&mut self, _order: &Order<T>) {}
+
+    /// Visit a market-to-limit order
+    fn visit_market_to_limit(&mut self, _order: &Order<T>) {}
+
+    /// Visit a reserve order
+    fn visit_reserve(&mut self, _order: &Order<T>) {}
+}
+
+impl<T> Order<T> {
+    /// Call the method of `visitor` for this order's variant
+    pub fn accept(&self, visitor: &mut impl OrderVisitor<T>) {
+        match self {
+            Order::Standard { .. } => visitor.visit_standard(self),
+            Order::IcebergOrder { .. } => visitor.visit_iceberg(self),
+            Order::PostOnly { .. } => visitor.visit_post_only(self),
+            Order::TrailingStop { .. } => visitor.visit_trailing_stop(self),
+            Order::PeggedOrder { .. } => visitor.visit_pegged(self),
+            Order::MarketToLimit { .. } => visitor.visit_market_to_limit(self),
+            Order::ReserveOrder { .. } => visitor.visit_reserve(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::order::{
+        IcebergRefresh, Order, OrderCommon, OrderId, OrderVisitor, PegReferenceType, Quantity,
+        Side, TimeInForce,
+    };
+
+    /// Counts the orders of each variant, in declaration order
+    #[derive(Default)]
+    struct VariantCounter {
+        counts: [usize; 7],
+        reserve_quantity: Quantity,
+    }
+
+    impl OrderVisitor for VariantCounter {
+        fn visit_standard(&mut self, _order: &Order<()>) {
+            self.counts[0] += 1;
+        }
+
+        fn visit_iceberg(&mut self, order: &Order<()>) {
+            self.counts[1] += 1;
+            self.reserve_quantity += order.reserve_quantity();
+        }
+
+        fn visit_post_only(&mut self, _order: &Order<()>) {
+            self.counts[2] += 1;
+        }
+
+        fn visit_trailing_stop(&mut self, _order: &Order<()>) {
+            self.counts[3] += 1;
+        }
+
+        fn visit_pegged(&mut self, _order: &Order<()>) {
+            self.counts[4] += 1;
+        }
+
+        fn visit_market_to_limit(&mut self, _order: &Order<()>) {
+            self.counts[5] += 1;
+        }
+
+        fn visit_reserve(&mut self, order: &Order<()>) {
+            self.counts[6] += 1;
+            self.reserve_quantity += order.reserve_quantity();
+        }
+    }
+
+    /// Only cares about standard orders
+    #[derive(Default)]
+    struct StandardOnly {
+        seen: Vec<OrderId>,
+    }
+
+    impl OrderVisitor for StandardOnly {
+        fn visit_standard(&mut self, order: &Order<()>) {
+            self.seen.push(order.id());
+        }
+    }
+
+    fn orders() -> Vec<Order<()>> {
+        let common = |id| OrderCommon {
+            id: OrderId::from_u64(id),
+            price: 10000,
+            display_quantity: 10,
+            side: Side::Buy,
+            timestamp: 1616823000000,
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
+        };
+        vec![
+            Order::Standard { common: common(1) },
+            Order::IcebergOrder {
+                common: common(2),
+                reserve_quantity: 20,
+                original_display: 10,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
+            },
+            Order::PostOnly { common: common(3) },
+            Order::TrailingStop {
+                common: common(4),
+                trail_amount: 5,
+                last_reference_price: 10005,
+            },
+            Order::PeggedOrder {
+                common: common(5),
+                reference_price_offset: -5,
+                reference_price_type: PegReferenceType::BestBid,
+            },
+            Order::MarketToLimit { common: common(6) },
+            Order::ReserveOrder {
+                common: common(7),
+                reserve_quantity: 30,
+                replenish_threshold: 1,
+                replenish_amount: None,
+                auto_replenish: true,
+                auto_replenish_interval_ms: None,
+                last_reveal_ms: None,
+                max_total_display: None,
+                revealed_quantity: 0,
+            },
+            Order::Standard { common: common(8) },
+        ]
+    }
+
+    #[test]
+    fn test_visitor_counts_variants() {
+        let mut counter = VariantCounter::default();
+        for order in orders() {
+            order.accept(&mut counter);
+        }
+        assert_eq!(counter.counts, [2, 1, 1, 1, 1, 1, 1]);
+        assert_eq!(counter.reserve_quantity, 50);
+    }
+
+    #[test]
+    fn test_visitor_defaults_ignore_other_variants() {
+        let mut visitor = StandardOnly::default();
+        for order in orders() {
+            order.accept(&mut visitor);
+        }
+        assert_eq!(
+            visitor.seen,
+            vec![OrderId::from_u64(1), OrderId::from_u64(8)]
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use crate::order::{Price, Quantity};
+use serde::{Deserialize, Serialize};
+
+/// The aggregate totals of a price level, without its orders.
+///
+/// Returned by [`PriceLevel::aggregate`](crate::PriceLevel::aggregate) for the current state
+/// and by [`PriceLevel::preview_update`](crate::PriceLevel::preview_update) for the state an
+/// update would leave behind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceLevelAggregate {
+    /// The price of the level
+    pub price: Price,
+    /// Total visible quantity at the level
+    pub display_quantity: Quantity,
+    /// Total hidden (reserve) quantity at the level
+    pub reserve_quantity: Quantity,
+    /// Number of resting orders at the level
+    pub order_count: usize,
+}
+
+impl PriceLevelAggregate {
+    /// Get the total quantity (visible + hidden)
+    pub fn total_quantity(&self) -> Quantity {
+        self.display_quantity + self.reserve_quantity
+    }
+}
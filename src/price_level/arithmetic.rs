@@ -0,0 +1,133 @@
+use crate::order::Quantity;
+use serde::{Deserialize, Serialize};
+
+/// How a price level's display quantity, reserve quantity and order count aggregates handle
+/// overflow and underflow.
+///
+/// The aggregates can only leave the `Quantity` range if they have drifted from the queue
+/// contents or the orders themselves are extreme, so this chooses how such a bug surfaces.
+/// It is configuration set with
+/// [`PriceLevel::with_arithmetic_policy`](crate::PriceLevel::with_arithmetic_policy) and
+/// applies to adding, matching, updating, canceling and replaying orders. Levels built from
+/// orders or snapshots start with the default policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArithmeticPolicy {
+    /// Panic on overflow or underflow, so accounting bugs fail loudly
+    #[default]
+    Checked,
+    /// Clamp to `0..=Quantity::MAX`, so the level never panics
+    Saturating,
+    /// Wrap around modulo `2^64`
+    Wrapping,
+}
+
+impl ArithmeticPolicy {
+    /// Add `quantity` to the aggregate `total`
+    ///
+    /// # Panics
+    ///
+    /// Panics under [`Checked`](Self::Checked) if the sum overflows.
+    pub fn add(self, total: Quantity, quantity: Quantity) -> Quantity {
+        match self {
+            ArithmeticPolicy::Checked => total
+                .checked_add(quantity)
+                .unwrap_or_else(|| panic!("Aggregate overflow: {total} + {quantity}")),
+            ArithmeticPolicy::Saturating => total.saturating_add(quantity),
+            ArithmeticPolicy::Wrapping => total.wrapping_add(quantity),
+        }
+    }
+
+    /// Subtract `quantity` from the aggregate `total`
+    ///
+    /// # Panics
+    ///
+    /// Panics under [`Checked`](Self::Checked) if the difference underflows.
+    pub fn sub(self, total: Quantity, quantity: Quantity) -> Quantity {
+        match self {
+            ArithmeticPolicy::Checked => total
+                .checked_sub(quantity)
+                .unwrap_or_else(|| panic!("Aggregate underflow: {total} - {quantity}")),
+            ArithmeticPolicy::Saturating => total.saturating_sub(quantity),
+            ArithmeticPolicy::Wrapping => total.wrapping_sub(quantity),
+        }
+    }
+
+    /// Add `count` orders to the aggregate order count `total`
+    ///
+    /// # Panics
+    ///
+    /// Panics under [`Checked`](Self::Checked) if the sum overflows.
+    pub fn add_count(self, total: usize, count: usize) -> usize {
+        match self {
+            ArithmeticPolicy::Checked => total
+                .checked_add(count)
+                .unwrap_or_else(|| panic!("Order count overflow: {total} + {count}")),
+            ArithmeticPolicy::Saturating => total.saturating_add(count),
+            ArithmeticPolicy::Wrapping => total.wrapping_add(count),
+        }
+    }
+
+    /// Subtract `count` orders from the aggregate order count `total`
+    ///
+    /// # Panics
+    ///
+    /// Panics under [`Checked`](Self::Checked) if the difference underflows.
+    pub fn sub_count(self, total: usize, count: usize) -> usize {
+        match self {
+            ArithmeticPolicy::Checked => total
+                .checked_sub(count)
+                .unwrap_or_else(|| panic!("Order count underflow: {total} - {count}")),
+            ArithmeticPolicy::Saturating => total.saturating_sub(count),
+            ArithmeticPolicy::Wrapping => total.wrapping_sub(count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::price_level::ArithmeticPolicy;
+
+    #[test]
+    fn test_policies_at_the_boundary() {
+        assert_eq!(ArithmeticPolicy::default(), ArithmeticPolicy::Checked);
+        for policy in [
+            ArithmeticPolicy::Checked,
+            ArithmeticPolicy::Saturating,
+            ArithmeticPolicy::Wrapping,
+        ] {
+            assert_eq!(policy.add(u64::MAX - 1, 1), u64::MAX);
+            assert_eq!(policy.sub(1, 1), 0);
+        }
+
+        assert_eq!(ArithmeticPolicy::Saturating.add(u64::MAX, 2), u64::MAX);
+        assert_eq!(ArithmeticPolicy::Saturating.sub(1, 2), 0);
+        assert_eq!(ArithmeticPolicy::Wrapping.add(u64::MAX, 2), 1);
+        assert_eq!(ArithmeticPolicy::Wrapping.sub(1, 2), u64::MAX);
+
+        assert_eq!(ArithmeticPolicy::Saturating.sub_count(0, 1), 0);
+        assert_eq!(
+            ArithmeticPolicy::Saturating.add_count(usize::MAX, 1),
+            usize::MAX
+        );
+        assert_eq!(ArithmeticPolicy::Wrapping.sub_count(0, 1), usize::MAX);
+        assert_eq!(ArithmeticPolicy::Wrapping.add_count(usize::MAX, 1), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Order count underflow")]
+    fn test_checked_count_underflow_panics() {
+        ArithmeticPolicy::Checked.sub_count(0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Aggregate overflow")]
+    fn test_checked_add_overflow_panics() {
+        ArithmeticPolicy::Checked.add(u64::MAX, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Aggregate underflow")]
+    fn test_checked_sub_underflow_panics() {
+        ArithmeticPolicy::Checked.sub(0, 1);
+    }
+}
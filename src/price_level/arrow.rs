@@ -0,0 +1,337 @@
+use crate::order::Order;
+use crate::price_level::PriceLevel;
+use arrow_array::{ArrayRef, BooleanArray, Int64Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use std::sync::{Arc, LazyLock};
+
+/// The schema of [`PriceLevel::to_arrow`], built once
+static SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    let column =
+        |name: &str, data_type: DataType, nullable: bool| Field::new(name, data_type, nullable);
+    Arc::new(Schema::new(vec![
+        column("order_type", DataType::Utf8, false),
+        column("id", DataType::Utf8, false),
+        column("price", DataType::UInt64, false),
+        column("display_quantity", DataType::UInt64, false),
+        column("reserve_quantity", DataType::UInt64, false),
+        column("side", DataType::Utf8, false),
+        column("timestamp", DataType::UInt64, false),
+        column("time_in_force", DataType::Utf8, false),
+        column("parent_id", DataType::Utf8, true),
+        column("oco_group", DataType::UInt64, true),
+        column("suppress_in_feed", DataType::Boolean, false),
+        column("trail_amount", DataType::UInt64, true),
+        column("last_reference_price", DataType::UInt64, true),
+        column("reference_price_offset", DataType::Int64, true),
+        column("reference_price_type", DataType::Utf8, true),
+        column("replenish_threshold", DataType::UInt64, true),
+        column("replenish_amount", DataType::UInt64, true),
+        column("auto_replenish", DataType::Boolean, true),
+        column("auto_replenish_interval_ms", DataType::UInt64, true),
+        column("last_reveal_ms", DataType::UInt64, true),
+        column("max_total_display", DataType::UInt64, true),
+        column("revealed_quantity", DataType::UInt64, true),
+    ]))
+});
+
+/// Per-column values of the orders, in queue order
+#[derive(Default)]
+struct Columns {
+    order_type: Vec<&'static str>,
+    id: Vec<String>,
+    price: Vec<u64>,
+    display_quantity: Vec<u64>,
+    reserve_quantity: Vec<u64>,
+    side: Vec<String>,
+    timestamp: Vec<u64>,
+    time_in_force: Vec<String>,
+    parent_id: Vec<Option<String>>,
+    oco_group: Vec<Option<u64>>,
+    suppress_in_feed: Vec<bool>,
+    trail_amount: Vec<Option<u64>>,
+    last_reference_price: Vec<Option<u64>>,
+    reference_price_offset: Vec<Option<i64>>,
+    reference_price_type: Vec<Option<String>>,
+    replenish_threshold: Vec<Option<u64>>,
+    replenish_amount: Vec<Option<u64>>,
+    auto_replenish: Vec<Option<bool>>,
+    auto_replenish_interval_ms: Vec<Option<u64>>,
+    last_reveal_ms: Vec<Option<u64>>,
+    max_total_display: Vec<Option<u64>>,
+    revealed_quantity: Vec<Option<u64>>,
+}
+
+impl Columns {
+    fn push(&mut self, order: &Order<()>) {
+        self.id.push(order.id().to_string());
+        self.price.push(order.price());
+        self.display_quantity.push(order.display_quantity());
+        self.reserve_quantity.push(order.reserve_quantity());
+        self.side.push(order.side().to_string());
+        self.timestamp.push(order.timestamp());
+        self.time_in_force.push(order.time_in_force().to_string());
+        self.parent_id
+            .push(order.parent_id().map(|parent_id| parent_id.to_string()));
+        self.oco_group.push(order.oco_group());
+        self.suppress_in_feed.push(order.is_suppressed_in_feed());
+
+        let mut trailing = (None, None);
+        let mut pegged = (None, None);
+        let mut reserve = None;
+        let order_type = match order {
+            Order::Standard { .. } => "Standard",
+            Order::IcebergOrder { .. } => "IcebergOrder",
+            Order::PostOnly { .. } => "PostOnly",
+            Order::TrailingStop {
+                trail_amount,
+                last_reference_price,
+                ..
+            } => {
+                trailing = (Some(*trail_amount), Some(*last_reference_price));
+                "TrailingStop"
+            }
+            Order::PeggedOrder {
+                reference_price_offset,
+                reference_price_type,
+                ..
+            } => {
+                pegged = (
+                    Some(*reference_price_offset),
+                    Some(reference_price_type.to_string()),
+                );
+                "PeggedOrder"
+            }
+            Order::MarketToLimit { .. } => "MarketToLimit",
+            Order::ReserveOrder {
+                replenish_threshold,
+                replenish_amount,
+                auto_replenish,
+                auto_replenish_interval_ms,
+                last_reveal_ms,
+                max_total_display,
+                revealed_quantity,
+                ..
+            } => {
+                reserve = Some((
+                    *replenish_threshold,
+                    *replenish_amount,
+                    *auto_replenish,
+                    *auto_replenish_interval_ms,
+                    *last_reveal_ms,
+                    *max_total_display,
+                    *revealed_quantity,
+                ));
+                "ReserveOrder"
+            }
+        };
+        self.order_type.push(order_type);
+        self.trail_amount.push(trailing.0);
+        self.last_reference_price.push(trailing.1);
+        self.reference_price_offset.push(pegged.0);
+        self.reference_price_type.push(pegged.1);
+        self.replenish_threshold.push(reserve.map(|r| r.0));
+        self.replenish_amount.push(reserve.and_then(|r| r.1));
+        self.auto_replenish.push(reserve.map(|r| r.2));
+        self.auto_replenish_interval_ms
+            .push(reserve.and_then(|r| r.3));
+        self.last_reveal_ms.push(reserve.and_then(|r| r.4));
+        self.max_total_display.push(reserve.and_then(|r| r.5));
+        self.revealed_quantity.push(reserve.map(|r| r.6));
+    }
+
+    fn into_arrays(self) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(StringArray::from(self.order_type)),
+            Arc::new(StringArray::from(self.id)),
+            Arc::new(UInt64Array::from(self.price)),
+            Arc::new(UInt64Array::from(self.display_quantity)),
+            Arc::new(UInt64Array::from(self.reserve_quantity)),
+            Arc::new(StringArray::from(self.side)),
+            Arc::new(UInt64Array::from(self.timestamp)),
+            Arc::new(StringArray::from(self.time_in_force)),
+            Arc::new(StringArray::from(self.parent_id)),
+            Arc::new(UInt64Array::from(self.oco_group)),
+            Arc::new(BooleanArray::from(self.suppress_in_feed)),
+            Arc::new(UInt64Array::from(self.trail_amount)),
+            Arc::new(UInt64Array::from(self.last_reference_price)),
+            Arc::new(Int64Array::from(self.reference_price_offset)),
+            Arc::new(StringArray::from(self.reference_price_type)),
+            Arc::new(UInt64Array::from(self.replenish_threshold)),
+            Arc::new(UInt64Array::from(self.replenish_amount)),
+            Arc::new(BooleanArray::from(self.auto_replenish)),
+            Arc::new(UInt64Array::from(self.auto_replenish_interval_ms)),
+            Arc::new(UInt64Array::from(self.last_reveal_ms)),
+            Arc::new(UInt64Array::from(self.max_total_display)),
+            Arc::new(UInt64Array::from(self.revealed_quantity)),
+        ]
+    }
+}
+
+impl PriceLevel {
+    /// Get the schema of the record batches produced by [`to_arrow`](Self::to_arrow).
+    ///
+    /// There is one row per order, in queue order, with these columns:
+    ///
+    /// | Column | Type | Nullable | Content |
+    /// |---|---|---|---|
+    /// | `order_type` | Utf8 | no | Variant name, as in the text form (`Standard`, `IcebergOrder`, ...) |
+    /// | `id` | Utf8 | no | Order ID |
+    /// | `price` | UInt64 | no | Limit price |
+    /// | `display_quantity` | UInt64 | no | Displayed quantity |
+    /// | `reserve_quantity` | UInt64 | no | Hidden quantity, 0 for orders without a reserve |
+    /// | `side` | Utf8 | no | `BUY` or `SELL` |
+    /// | `timestamp` | UInt64 | no | Arrival time in milliseconds since the Unix epoch |
+    /// | `time_in_force` | Utf8 | no | Time in force, as in the text form |
+    /// | `parent_id` | Utf8 | yes | ID of the parent order |
+    /// | `oco_group` | UInt64 | yes | One-cancels-other group |
+    /// | `suppress_in_feed` | Boolean | no | Whether the order is left out of market data |
+    /// | `trail_amount`, `last_reference_price` | UInt64 | yes | Set for `TrailingStop` |
+    /// | `reference_price_offset` | Int64 | yes | Set for `PeggedOrder` |
+    /// | `reference_price_type` | Utf8 | yes | Set for `PeggedOrder` |
+    /// | `replenish_threshold`, `revealed_quantity` | UInt64 | yes | Set for `ReserveOrder` |
+    /// | `replenish_amount`, `auto_replenish_interval_ms`, `last_reveal_ms`, `max_total_display` | UInt64 | yes | Optional settings of `ReserveOrder` |
+    /// | `auto_replenish` | Boolean | yes | Set for `ReserveOrder` |
+    ///
+    /// Columns are only ever appended, so readers can rely on the existing names, types
+    /// and positions.
+    pub fn arrow_schema() -> SchemaRef {
+        SCHEMA.clone()
+    }
+
+    /// Export the orders of this level as an Arrow record batch with the
+    /// [`arrow_schema`](Self::arrow_schema), for analytics engines such as DataFusion or
+    /// Polars.
+    pub fn to_arrow(&self) -> RecordBatch {
+        let mut columns = Columns::default();
+        for order in &self.iter_orders() {
+            columns.push(order);
+        }
+        RecordBatch::try_new(Self::arrow_schema(), columns.into_arrays())
+            .expect("columns are built to match the schema")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::order::{
+        IcebergRefresh, Order, OrderCommon, OrderId, PegReferenceType, Side, TimeInForce,
+    };
+    use crate::price_level::PriceLevel;
+    use arrow_array::{Array, BooleanArray, Int64Array, StringArray, UInt64Array};
+
+    fn common(id: u64) -> OrderCommon<()> {
+        OrderCommon {
+            id: OrderId::from_u64(id),
+            price: 10000,
+            display_quantity: 10 * id,
+            side: Side::Sell,
+            timestamp: 1616823000000 + id,
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
+        }
+    }
+
+    #[test]
+    fn test_to_arrow_matches_iter_orders() {
+        let mut level = PriceLevel::new(10000);
+        level
+            .add_order(Order::Standard {
+                common: OrderCommon {
+                    oco_group: Some(7),
+                    ..common(1)
+                },
+            })
+            .unwrap();
+        level
+            .add_order(Order::IcebergOrder {
+                common: common(2),
+                reserve_quantity: 40,
+                original_display: 20,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
+            })
+            .unwrap();
+        level
+            .add_order(Order::PeggedOrder {
+                common: common(3),
+                reference_price_offset: -5,
+                reference_price_type: PegReferenceType::BestBid,
+            })
+            .unwrap();
+        level
+            .add_order(Order::ReserveOrder {
+                common: common(4),
+                reserve_quantity: 60,
+                replenish_threshold: 5,
+                replenish_amount: Some(20),
+                auto_replenish: true,
+                auto_replenish_interval_ms: None,
+                last_reveal_ms: None,
+                max_total_display: None,
+                revealed_quantity: 0,
+            })
+            .unwrap();
+
+        let batch = level.to_arrow();
+        assert_eq!(batch.schema(), PriceLevel::arrow_schema());
+        let orders = level.iter_orders();
+        assert_eq!(batch.num_rows(), orders.len());
+
+        let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+        let strings = |name: &str| {
+            column(name)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap()
+                .clone()
+        };
+        let numbers = |name: &str| {
+            column(name)
+                .as_any()
+                .downcast_ref::<UInt64Array>()
+                .unwrap()
+                .clone()
+        };
+
+        let ids = strings("id");
+        let display = numbers("display_quantity");
+        let reserve = numbers("reserve_quantity");
+        let timestamps = numbers("timestamp");
+        for (row, order) in orders.iter().enumerate() {
+            assert_eq!(ids.value(row), order.id().to_string());
+            assert_eq!(display.value(row), order.display_quantity());
+            assert_eq!(reserve.value(row), order.reserve_quantity());
+            assert_eq!(timestamps.value(row), order.timestamp());
+        }
+
+        let order_types = strings("order_type");
+        let order_types: Vec<&str> = order_types.iter().flatten().collect();
+        assert_eq!(
+            order_types,
+            vec!["Standard", "IcebergOrder", "PeggedOrder", "ReserveOrder"]
+        );
+        let oco_group = numbers("oco_group");
+        assert_eq!(oco_group.value(0), 7);
+        assert!(oco_group.is_null(1));
+
+        // Variant-specific columns are only set for their variant
+        let offsets = column("reference_price_offset");
+        let offsets = offsets.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(offsets.null_count(), 3);
+        assert_eq!(offsets.value(2), -5);
+        assert_eq!(strings("reference_price_type").value(2), "BestBid");
+        let auto_replenish = column("auto_replenish");
+        let auto_replenish = auto_replenish
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap();
+        assert!(auto_replenish.is_null(0));
+        assert!(auto_replenish.value(3));
+        assert_eq!(numbers("replenish_amount").value(3), 20);
+
+        assert_eq!(PriceLevel::new(10000).to_arrow().num_rows(), 0);
+    }
+}
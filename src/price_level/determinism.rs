@@ -0,0 +1,260 @@
+use crate::UuidGenerator;
+use crate::errors::PriceLevelError;
+use crate::execution::MatchResult;
+use crate::order::{CancelReason, Order, OrderId, Quantity, Timestamp};
+use crate::price_level::{PriceLevel, PriceLevelSnapshot};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An operation applied to a level, as recorded for [`PriceLevel::replay_with_log`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LevelOp {
+    /// Add an order with [`PriceLevel::add_order`]
+    Add(Order<()>),
+    /// Match an incoming order with [`PriceLevel::match_order`]
+    Match {
+        /// The quantity of the incoming order
+        quantity: Quantity,
+        /// The ID of the incoming order
+        taker_order_id: OrderId,
+    },
+    /// Cancel a resting order with [`PriceLevel::cancel_order`]
+    Cancel {
+        /// The order to cancel
+        order_id: OrderId,
+        /// Why the order is canceled
+        reason: CancelReason,
+    },
+}
+
+/// The non-deterministic inputs of a sequence of [`LevelOp`]s, in the order they were used.
+///
+/// Matching is deterministic given the level state, except for what each execution takes
+/// from outside: its transaction ID, drawn from the caller's [`UuidGenerator`], and its
+/// timestamp, read from the system clock. Recording both with
+/// [`PriceLevel::apply_logged`] lets [`PriceLevel::replay_with_log`] reproduce identical
+/// [`MatchResult`]s, and stamps refreshed icebergs with the same reveal times. Clock
+/// reads that only feed the level statistics are not logged, so the time-based statistics
+/// of a replayed level can differ.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeterminismLog {
+    transaction_ids: Vec<Uuid>,
+    clock_reads: Vec<Timestamp>,
+}
+
+impl DeterminismLog {
+    /// Create an empty log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the transaction IDs issued, in execution order
+    pub fn transaction_ids(&self) -> &[Uuid] {
+        &self.transaction_ids
+    }
+
+    /// Get the clock reads that timestamped each execution, in execution order
+    pub fn clock_reads(&self) -> &[Timestamp] {
+        &self.clock_reads
+    }
+
+    /// Get the number of executions recorded
+    pub fn len(&self) -> usize {
+        self.transaction_ids.len()
+    }
+
+    /// Check if no execution was recorded
+    pub fn is_empty(&self) -> bool {
+        self.transaction_ids.is_empty()
+    }
+
+    /// Record the inputs used by the executions of `result`
+    fn record(&mut self, result: &MatchResult) {
+        for transaction in result.transactions.as_vec() {
+            self.transaction_ids.push(transaction.transaction_id);
+            self.clock_reads.push(transaction.timestamp);
+        }
+    }
+}
+
+impl PriceLevel {
+    /// Apply `op` to the level, recording its non-deterministic inputs in `log`.
+    ///
+    /// Returns the match result of [`LevelOp::Match`] operations and `None` for the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`PriceLevel::add_order`] if a [`LevelOp::Add`] is rejected by the
+    /// level's limits; the level is unchanged in that case.
+    pub fn apply_logged(
+        &mut self,
+        op: &LevelOp,
+        transaction_id_generator: &UuidGenerator,
+        log: &mut DeterminismLog,
+    ) -> Result<Option<MatchResult>, PriceLevelError> {
+        match *op {
+            LevelOp::Add(order) => {
+                self.add_order(order)?;
+                Ok(None)
+            }
+            LevelOp::Match {
+                quantity,
+                taker_order_id,
+            } => {
+                let result = self.match_order(quantity, taker_order_id, transaction_id_generator);
+                log.record(&result);
+                Ok(Some(result))
+            }
+            LevelOp::Cancel { order_id, reason } => {
+                self.cancel_order(order_id, reason);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Rebuild a level from `snapshot` and re-apply `ops`, taking every transaction ID and
+    /// timestamp from `log` instead of a generator and the clock.
+    ///
+    /// With the snapshot the recording started from and the operations passed to
+    /// [`apply_logged`](Self::apply_logged), the returned match results, one per
+    /// [`LevelOp::Match`], are identical to the recorded ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the snapshot cannot be restored or an operation is rejected, or
+    /// [`PriceLevelError::InvalidOperation`] if the log does not hold exactly one entry per
+    /// execution of the replay.
+    pub fn replay_with_log(
+        snapshot: PriceLevelSnapshot,
+        ops: &[LevelOp],
+        log: &DeterminismLog,
+    ) -> Result<(PriceLevel, Vec<MatchResult>), PriceLevelError> {
+        let mut level = PriceLevel::from_snapshot(snapshot)?;
+        let generator = UuidGenerator::new(Uuid::nil());
+        let mut inputs = log.transaction_ids.iter().zip(&log.clock_reads);
+        let mut results = Vec::new();
+
+        for op in ops {
+            let Some(mut result) =
+                level.apply_logged(op, &generator, &mut DeterminismLog::new())?
+            else {
+                continue;
+            };
+            for transaction in &mut result.transactions.transactions {
+                let (transaction_id, timestamp) =
+                    inputs
+                        .next()
+                        .ok_or_else(|| PriceLevelError::InvalidOperation {
+                            message: "Determinism log ended before the replay".to_string(),
+                        })?;
+                transaction.transaction_id = *transaction_id;
+                transaction.timestamp = *timestamp;
+            }
+            level.stamp_iceberg_reveals(&result);
+            results.push(result);
+        }
+
+        if inputs.next().is_some() {
+            return Err(PriceLevelError::InvalidOperation {
+                message: "Determinism log has entries the replay did not use".to_string(),
+            });
+        }
+        Ok((level, results))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::UuidGenerator;
+    use crate::errors::PriceLevelError;
+    use crate::order::{
+        CancelReason, IcebergRefresh, Order, OrderCommon, OrderId, Side, TimeInForce,
+    };
+    use crate::price_level::{DeterminismLog, LevelOp, PriceLevel};
+    use uuid::Uuid;
+
+    fn create_order(id: u64, display_quantity: u64, reserve_quantity: u64) -> Order<()> {
+        let common = OrderCommon {
+            id: OrderId::from_u64(id),
+            price: 10000,
+            display_quantity,
+            side: Side::Sell,
+            timestamp: 1616823000000 + id,
+            time_in_force: TimeInForce::Gtc,
+            extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
+        };
+        if reserve_quantity == 0 {
+            Order::Standard { common }
+        } else {
+            Order::IcebergOrder {
+                original_display: common.display_quantity,
+                common,
+                reserve_quantity,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
+            }
+        }
+    }
+
+    fn ops() -> Vec<LevelOp> {
+        vec![
+            LevelOp::Match {
+                quantity: 15,
+                taker_order_id: OrderId::from_u64(100),
+            },
+            LevelOp::Add(create_order(4, 10, 0)),
+            LevelOp::Cancel {
+                order_id: OrderId::from_u64(2),
+                reason: CancelReason::UserRequested,
+            },
+            LevelOp::Match {
+                quantity: 40,
+                taker_order_id: OrderId::from_u64(101),
+            },
+            LevelOp::Match {
+                quantity: 5,
+                taker_order_id: OrderId::from_u64(102),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_replay_with_log_reproduces_results() {
+        let mut level = PriceLevel::new(10000);
+        level.add_order(create_order(1, 10, 30)).unwrap();
+        level.add_order(create_order(2, 20, 0)).unwrap();
+        level.add_order(create_order(3, 5, 0)).unwrap();
+        let before = level.snapshot();
+
+        let generator = UuidGenerator::new(Uuid::new_v4());
+        let mut log = DeterminismLog::new();
+        let recorded: Vec<_> = ops()
+            .iter()
+            .filter_map(|op| level.apply_logged(op, &generator, &mut log).unwrap())
+            .collect();
+        assert_eq!(log.len(), 8);
+        assert_eq!(log.clock_reads().len(), log.transaction_ids().len());
+
+        let (replayed, results) =
+            PriceLevel::replay_with_log(before.clone(), &ops(), &log).unwrap();
+        assert_eq!(
+            serde_json::to_string(&results).unwrap(),
+            serde_json::to_string(&recorded).unwrap()
+        );
+        assert_eq!(replayed.snapshot(), level.snapshot());
+
+        // The log must cover exactly the executions of the replay
+        let short = &ops()[..1];
+        assert!(matches!(
+            PriceLevel::replay_with_log(before.clone(), short, &log),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert!(matches!(
+            PriceLevel::replay_with_log(before, &ops(), &DeterminismLog::new()),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+    }
+}
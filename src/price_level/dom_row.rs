@@ -0,0 +1,61 @@
+use crate::order::{Price, Quantity, Side};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A flat, per-level record used to build a depth-of-market (DOM) grid.
+///
+/// Unlike [`PriceLevelSnapshot`](crate::PriceLevelSnapshot), a `DomRow` carries only the
+/// aggregates of a level and no orders, so it is cheap to produce for every row of a ladder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DomRow {
+    /// The price of the level
+    pub price: Price,
+    /// Total visible quantity at the level
+    pub visible_qty: Quantity,
+    /// Total hidden (reserve) quantity at the level
+    pub hidden_qty: Quantity,
+    /// Number of resting orders at the level
+    pub order_count: usize,
+    /// The book side the level belongs to
+    pub side: Side,
+}
+
+impl DomRow {
+    /// Get the total quantity (visible + hidden)
+    pub fn total_qty(&self) -> Quantity {
+        self.visible_qty + self.hidden_qty
+    }
+}
+
+impl fmt::Display for DomRow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "DomRow:price={};visible_qty={};hidden_qty={};order_count={};side={}",
+            self.price, self.visible_qty, self.hidden_qty, self.order_count, self.side
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::order::Side;
+    use crate::price_level::DomRow;
+
+    #[test]
+    fn test_dom_row_total_and_display() {
+        let row = DomRow {
+            price: 10000,
+            visible_qty: 150,
+            hidden_qty: 50,
+            order_count: 2,
+            side: Side::Sell,
+        };
+
+        assert_eq!(row.total_qty(), 200);
+        assert_eq!(
+            row.to_string(),
+            "DomRow:price=10000;visible_qty=150;hidden_qty=50;order_count=2;side=SELL"
+        );
+    }
+}
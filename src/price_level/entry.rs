@@ -314,9 +314,12 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
-        level.borrow_mut().add_order(order);
+        level.borrow_mut().add_order(order).unwrap();
 
         // Serialize the entry
         let json = serde_json::to_string(&entry).unwrap();
@@ -406,10 +409,13 @@ mod tests_order_book_entry {
                 timestamp: 1616823000000,
                 time_in_force: crate::order::TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
 
-        level1.borrow_mut().add_order(order_type);
+        level1.borrow_mut().add_order(order_type).unwrap();
         assert_eq!(entry1.order_count(), 1);
 
         // Add another order
@@ -423,10 +429,13 @@ mod tests_order_book_entry {
                 timestamp: 1616823000002,
                 time_in_force: crate::order::TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
 
-        level1.borrow_mut().add_order(order_type3);
+        level1.borrow_mut().add_order(order_type3).unwrap();
         assert_eq!(entry1.order_count(), 2);
     }
 
@@ -559,9 +568,12 @@ mod tests_order_book_entry {
                 timestamp: 1616823000000,
                 time_in_force: crate::order::TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
-        level.borrow_mut().add_order(standard_order);
+        level.borrow_mut().add_order(standard_order).unwrap();
 
         // Check quantities after adding order
         assert_eq!(entry.visible_quantity(), 10);
@@ -577,10 +589,16 @@ mod tests_order_book_entry {
                 timestamp: 1616823000001,
                 time_in_force: crate::order::TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 15,
+            original_display: 5,
+            refresh_strategy: crate::order::IcebergRefresh::default(),
+            last_reveal_ms: None,
         };
-        level.borrow_mut().add_order(iceberg_order);
+        level.borrow_mut().add_order(iceberg_order).unwrap();
 
         // Check quantities after adding iceberg order
         assert_eq!(entry.visible_quantity(), 15); // 10 + 5
@@ -0,0 +1,90 @@
+use crate::execution::Transaction;
+use crate::order::{CancelReason, Order, OrderId, Price, Quantity};
+use serde::{Deserialize, Serialize};
+
+/// A change to a price level, published to the sender configured with
+/// [`PriceLevel::with_event_sender`](crate::PriceLevel::with_event_sender).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LevelEvent {
+    /// An order was added to the level, as it now rests in the queue
+    OrderAdded {
+        /// The price of the level
+        price: Price,
+        /// The order that was added
+        order: Order<()>,
+    },
+
+    /// An order was canceled and left the level
+    OrderCancelled {
+        /// The price of the level
+        price: Price,
+        /// The canceled order
+        order_id: OrderId,
+        /// Why the order was canceled
+        reason: CancelReason,
+    },
+
+    /// A resting order traded, fully or partially
+    OrderFilled {
+        /// The price of the level
+        price: Price,
+        /// The execution against the resting (maker) order
+        transaction: Transaction,
+    },
+
+    /// A resting order was changed in place, e.g. by a quantity update or a replacement at the
+    /// same price
+    OrderUpdated {
+        /// The price of the level
+        price: Price,
+        /// The order as it now rests in the queue
+        order: Order<()>,
+    },
+
+    /// An order was repriced and left the level for another one
+    OrderMoved {
+        /// The price of the level
+        price: Price,
+        /// The moved order
+        order_id: OrderId,
+        /// The price of the level the order is moving to
+        new_price: Price,
+    },
+
+    /// Quantity was moved from a resting order's reserve to its display
+    OrderReplenished {
+        /// The price of the level
+        price: Price,
+        /// The replenished order
+        order_id: OrderId,
+        /// The quantity revealed
+        quantity: Quantity,
+    },
+}
+
+impl LevelEvent {
+    /// Get the price of the level the event happened at
+    pub fn price(&self) -> Price {
+        match self {
+            LevelEvent::OrderAdded { price, .. }
+            | LevelEvent::OrderCancelled { price, .. }
+            | LevelEvent::OrderFilled { price, .. }
+            | LevelEvent::OrderUpdated { price, .. }
+            | LevelEvent::OrderMoved { price, .. }
+            | LevelEvent::OrderReplenished { price, .. } => *price,
+        }
+    }
+
+    /// Get the ID of the resting order the event is about
+    pub fn order_id(&self) -> OrderId {
+        match self {
+            LevelEvent::OrderAdded { order, .. } | LevelEvent::OrderUpdated { order, .. } => {
+                order.id()
+            }
+            LevelEvent::OrderCancelled { order_id, .. }
+            | LevelEvent::OrderMoved { order_id, .. }
+            | LevelEvent::OrderReplenished { order_id, .. } => *order_id,
+            LevelEvent::OrderFilled { transaction, .. } => transaction.maker_order_id,
+        }
+    }
+}
@@ -1,26 +1,42 @@
 //! Core price level implementation
 
+use crate::DEFAULT_RESERVE_REPLENISH_AMOUNT;
 use crate::UuidGenerator;
 use crate::errors::PriceLevelError;
-use crate::execution::{MatchResult, Transaction};
-use crate::order::{Order, OrderId, OrderUpdate};
+use crate::execution::{FeeSchedule, MatchResult, Transaction};
+use crate::order::{
+    CancelReason, Order, OrderId, OrderUpdate, PegReferenceType, PegRepricePolicy, Price, Quantity,
+    RepricedOrder, Side, TimeInForce, Timestamp,
+};
+use crate::price_level::ArithmeticPolicy;
 use crate::price_level::order_queue::OrderQueue;
-use crate::price_level::{PriceLevelSnapshot, PriceLevelSnapshotPackage, PriceLevelStatistics};
+use crate::price_level::{
+    DomRow, LevelEvent, PressureFormula, PriceLevelAggregate, PriceLevelSnapshot,
+    PriceLevelSnapshotPackage, PriceLevelSnapshotWithStats, PriceLevelStatistics,
+};
+use crate::utils::metrics;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::str::FromStr;
+use std::sync::mpsc::Sender;
 
 /// A lock-free implementation of a price level in a limit order book
+///
+/// `PriceLevel` intentionally does not implement `Default`: a level is only meaningful at a
+/// specific price, and a silent price of 0 would be indistinguishable from a real level.
+/// Use [`PriceLevel::new`] instead.
 #[derive(Debug)]
 pub struct PriceLevel {
     /// The price of this level
-    price: u64,
+    price: Price,
 
     /// Total display quantity at this price level
-    display_quantity: u64,
+    display_quantity: Quantity,
 
     /// Total reserve quantity at this price level
-    reserve_quantity: u64,
+    reserve_quantity: Quantity,
 
     /// Number of orders at this price level
     order_count: usize,
@@ -30,11 +46,41 @@ pub struct PriceLevel {
 
     /// Statistics for this price level
     stats: PriceLevelStatistics,
+
+    /// Orders fully consumed by the most recent match, see [`PriceLevel::take_last_removed`]
+    last_removed: Vec<Order<()>>,
+
+    /// Reserve quantity revealed into each order's display that has not executed yet
+    revealed_unexecuted: HashMap<OrderId, Quantity>,
+
+    /// Quantities accepted by [`PriceLevel::add_order`] must be multiples of this
+    lot_size: Quantity,
+
+    /// Reference price and width in basis points enforced by [`PriceLevel::add_order`]
+    price_band: Option<(Price, u32)>,
+
+    /// Largest order notional accepted by [`PriceLevel::add_order`]
+    max_order_notional: Option<u128>,
+
+    /// Channel that receives a [`LevelEvent`] for every add, cancel, fill and replenishment
+    event_sender: Option<Sender<LevelEvent>>,
+
+    /// Whether debug builds assert on drop that the aggregates match the queue
+    check_on_drop: bool,
+
+    /// How the quantity aggregates handle overflow and underflow
+    arithmetic: ArithmeticPolicy,
 }
 
 impl PriceLevel {
     /// Reconstructs a price level directly from a snapshot.
+    ///
+    /// The restored level matches in FIFO order; a level that used a comparator needs it
+    /// reinstalled with [`with_comparator`](Self::with_comparator).
     pub fn from_snapshot(mut snapshot: PriceLevelSnapshot) -> Result<Self, PriceLevelError> {
+        for order in &mut snapshot.orders {
+            *order = reveal_initial_slice(*order);
+        }
         snapshot.refresh_aggregates();
 
         let order_count = snapshot.orders.len();
@@ -47,9 +93,128 @@ impl PriceLevel {
             order_count,
             orders,
             stats: PriceLevelStatistics::new(),
+            last_removed: Vec::new(),
+            revealed_unexecuted: HashMap::new(),
+            lot_size: 1,
+            price_band: None,
+            max_order_notional: None,
+            event_sender: None,
+            check_on_drop: true,
+            arithmetic: ArithmeticPolicy::default(),
         })
     }
 
+    /// Builds a price level from a plain list of orders, computing the aggregates in one pass.
+    ///
+    /// Orders keep the queue position given by the vector. Every order must rest at `price`;
+    /// otherwise [`PriceLevelError::InvalidFieldValue`] is returned for the first mismatch.
+    pub fn from_orders(price: Price, orders: Vec<Order<()>>) -> Result<Self, PriceLevelError> {
+        Self::from_iter_at(price, orders)
+    }
+
+    /// Builds a price level by consuming an iterator of orders, one at a time.
+    ///
+    /// Like [`from_orders`](Self::from_orders), but the orders never need to be collected
+    /// first, so large or lazily produced sequences (e.g. read from a stream) are queued as
+    /// they arrive. Every order must rest at `price`; the first mismatch stops the build
+    /// with [`PriceLevelError::InvalidFieldValue`].
+    pub fn from_iter_at(
+        price: Price,
+        orders: impl IntoIterator<Item = Order<()>>,
+    ) -> Result<Self, PriceLevelError> {
+        let orders = orders.into_iter();
+        let mut level = Self::new(price);
+        level.orders = OrderQueue::with_capacity(orders.size_hint().0);
+        let arithmetic = level.arithmetic;
+        for order in orders {
+            let order = reveal_initial_slice(order);
+            if order.price() != price {
+                return Err(PriceLevelError::InvalidFieldValue {
+                    field: "price".to_string(),
+                    value: format!("order {} has price {}", order.id(), order.price()),
+                });
+            }
+            // A repeated ID replaces the earlier order, so release its contribution first
+            if let Some(replaced) = level.orders.remove(&order.id()) {
+                level.display_quantity =
+                    arithmetic.sub(level.display_quantity, replaced.display_quantity());
+                level.reserve_quantity =
+                    arithmetic.sub(level.reserve_quantity, replaced.reserve_quantity());
+                level.order_count = arithmetic.sub_count(level.order_count, 1);
+            }
+            level.display_quantity =
+                arithmetic.add(level.display_quantity, order.display_quantity());
+            level.reserve_quantity =
+                arithmetic.add(level.reserve_quantity, order.reserve_quantity());
+            level.order_count = arithmetic.add_count(level.order_count, 1);
+            level.orders.push(order);
+        }
+        Ok(level)
+    }
+
+    /// Rebuilds the resting state of a level by replaying a trade tape against its initial
+    /// orders.
+    ///
+    /// Each transaction executes its quantity against its maker order exactly as
+    /// [`match_order`](Self::match_order) would, and is applied with [`apply_match_result`]:
+    /// a partially filled or refreshed maker is requeued where the level's priority puts it,
+    /// a fully filled one leaves the level together with any reserve it could no longer
+    /// reveal. Statistics are recorded with the transaction timestamps, and
+    /// [`take_last_removed`](Self::take_last_removed) returns every maker the tape removed.
+    ///
+    /// Returns [`PriceLevelError::InvalidOperation`] if a transaction references a maker that
+    /// is not resting at the level, or executes more than the maker displays at that point.
+    pub fn replay_transactions(
+        price: Price,
+        initial: Vec<Order<()>>,
+        transactions: &[Transaction],
+    ) -> Result<Self, PriceLevelError> {
+        let mut level = Self::from_orders(price, initial)?;
+        let mut removed = Vec::new();
+        for (index, transaction) in transactions.iter().enumerate() {
+            let maker_id = transaction.maker_order_id;
+            let Some(&maker) = level.orders.find(&maker_id) else {
+                return Err(PriceLevelError::InvalidOperation {
+                    message: format!("transaction {index}: unknown maker order {maker_id}"),
+                });
+            };
+            if transaction.quantity == 0 || transaction.quantity > maker.display_quantity() {
+                return Err(PriceLevelError::InvalidOperation {
+                    message: format!(
+                        "transaction {index}: quantity {} against {} displayed by order {maker_id}",
+                        transaction.quantity,
+                        maker.display_quantity()
+                    ),
+                });
+            }
+
+            // The result the live match would have produced for this execution alone
+            let (_, updated, hidden_reduced, _) = maker.match_against(transaction.quantity);
+            let mut result = MatchResult::new(transaction.taker_order_id, transaction.quantity);
+            result.add_transaction(*transaction);
+            match updated {
+                Some(_) if hidden_reduced > 0 => result.add_replenishment(maker_id, hidden_reduced),
+                Some(_) => {}
+                None => result.add_filled_order_id(maker_id),
+            }
+            apply_match_result(&mut level, &result)?;
+            removed.append(&mut level.last_removed);
+        }
+        level.last_removed = removed;
+        Ok(level)
+    }
+
+    /// Reconstructs a price level from a snapshot, rejecting it if its stored aggregates
+    /// disagree with its orders.
+    ///
+    /// [`from_snapshot`](Self::from_snapshot) silently recomputes the aggregates; this variant
+    /// surfaces the mismatch as [`PriceLevelError::InconsistentSnapshot`] instead so corrupted
+    /// snapshots are not masked.
+    pub fn from_snapshot_strict(snapshot: PriceLevelSnapshot) -> Result<Self, PriceLevelError> {
+        snapshot.verify_aggregates()?;
+        Self::from_snapshot(snapshot)
+    }
+
     /// Reconstructs a price level from a checksum-protected snapshot package.
     pub fn from_snapshot_package(
         package: PriceLevelSnapshotPackage,
@@ -58,13 +223,29 @@ impl PriceLevel {
         Self::from_snapshot(snapshot)
     }
 
+    /// Reconstructs a price level from a snapshot package, restoring its statistics instead
+    /// of starting from fresh counters.
+    pub fn from_snapshot_with_stats(
+        package: PriceLevelSnapshotWithStats,
+    ) -> Result<Self, PriceLevelError> {
+        let mut level = Self::from_snapshot_package(package.package)?;
+        level.stats = package.stats;
+        Ok(level)
+    }
+
     /// Restores a price level from its snapshot JSON representation.
     pub fn from_snapshot_json(data: &str) -> Result<Self, PriceLevelError> {
         let package = PriceLevelSnapshotPackage::from_json(data)?;
         Self::from_snapshot_package(package)
     }
     /// Create a new price level
-    pub fn new(price: u64) -> Self {
+    ///
+    /// The level starts with the default configuration: a lot size of 1, no price band or
+    /// notional cap, arrival-order priority, no event channel, the drop check enabled and
+    /// [`ArithmeticPolicy::Checked`]. The chainable `with_*` builders change it, e.g.
+    /// `PriceLevel::new(10000).with_lot_size(10).with_event_sender(sender)`. Configuration
+    /// is not carried in snapshots, so a restored level starts from the defaults again.
+    pub fn new(price: Price) -> Self {
         Self {
             price,
             display_quantity: 0,
@@ -72,62 +253,640 @@ impl PriceLevel {
             order_count: 0,
             orders: OrderQueue::new(),
             stats: PriceLevelStatistics::new(),
+            last_removed: Vec::new(),
+            revealed_unexecuted: HashMap::new(),
+            lot_size: 1,
+            price_band: None,
+            max_order_notional: None,
+            event_sender: None,
+            check_on_drop: true,
+            arithmetic: ArithmeticPolicy::default(),
+        }
+    }
+
+    /// Require order quantities to be multiples of `lot_size`.
+    ///
+    /// The lot size is enforced by [`add_order`](Self::add_order). A lot size of 0 is
+    /// treated as 1.
+    pub fn with_lot_size(mut self, lot_size: Quantity) -> Self {
+        self.lot_size = lot_size.max(1);
+        self
+    }
+
+    /// Reject orders priced more than `band_bps` basis points away from `reference`.
+    ///
+    /// The band is enforced by [`add_order`](Self::add_order) using
+    /// [`Order::passes_price_band`].
+    pub fn with_price_band(mut self, reference: Price, band_bps: u32) -> Self {
+        self.price_band = Some((reference, band_bps));
+        self
+    }
+
+    /// Reject orders whose [`notional`](Order::notional) exceeds `max_order_notional`.
+    ///
+    /// The cap is enforced by [`add_order`](Self::add_order); an order exactly at the
+    /// cap is accepted.
+    pub fn with_max_order_notional(mut self, max_order_notional: u128) -> Self {
+        self.max_order_notional = Some(max_order_notional);
+        self
+    }
+
+    /// Match orders in `cmp` order instead of by arrival.
+    ///
+    /// See [`OrderQueue::with_comparator`] for how ties and re-queued orders are handled.
+    /// Orders already resting are re-sorted. Snapshots do not carry the comparator, so a
+    /// level restored with [`from_snapshot`](Self::from_snapshot) or deserialized is FIFO
+    /// until this is called on it again.
+    pub fn with_comparator(
+        mut self,
+        cmp: impl Fn(&Order<()>, &Order<()>) -> Ordering + Send + Sync + 'static,
+    ) -> Self {
+        self.orders.set_comparator(cmp);
+        self
+    }
+
+    /// Publish a [`LevelEvent`] to `sender` for every order added, updated in place, moved to
+    /// another price, canceled, filled or replenished, e.g. for a consumer thread maintaining
+    /// a market data feed.
+    ///
+    /// `std::sync::mpsc` channels are unbounded, so sending never blocks the matching path:
+    /// a slow consumer only lets events queue up in memory. There is no back-pressure, and
+    /// events sent after the receiver is dropped are discarded. Fills carry the transaction
+    /// as produced by matching, before any fees from
+    /// [`match_order_with_fees`](Self::match_order_with_fees) are applied.
+    pub fn with_event_sender(mut self, sender: Sender<LevelEvent>) -> Self {
+        self.event_sender = Some(sender);
+        self
+    }
+
+    /// Enable or disable the drop-time consistency check.
+    ///
+    /// In debug builds every level asserts when dropped that its display quantity, reserve
+    /// quantity and order count match its orders, so accounting bugs surface in tests.
+    /// Levels deliberately put into an inconsistent state, e.g. to exercise recovery paths,
+    /// can opt out with `check_on_drop = false`. Release builds never check.
+    pub fn with_drop_check(mut self, check_on_drop: bool) -> Self {
+        self.check_on_drop = check_on_drop;
+        self
+    }
+
+    /// Make the quantity aggregates follow `policy` on overflow and underflow.
+    ///
+    /// The default, [`ArithmeticPolicy::Checked`], panics, which suits tests; production
+    /// books may prefer [`ArithmeticPolicy::Saturating`] so a drifted aggregate never takes
+    /// the process down.
+    pub fn with_arithmetic_policy(mut self, policy: ArithmeticPolicy) -> Self {
+        self.arithmetic = policy;
+        self
+    }
+
+    /// Get the overflow policy of the quantity aggregates
+    pub fn arithmetic_policy(&self) -> ArithmeticPolicy {
+        self.arithmetic
+    }
+
+    /// Whether the display quantity, reserve quantity and order count match the orders
+    /// in the queue
+    fn aggregates_match_queue(&self) -> bool {
+        let (display, reserve) = self.orders.iter().fold(
+            (0 as Quantity, 0 as Quantity),
+            |(display, reserve), order| {
+                (
+                    display.saturating_add(order.display_quantity()),
+                    reserve.saturating_add(order.reserve_quantity()),
+                )
+            },
+        );
+        display == self.display_quantity
+            && reserve == self.reserve_quantity
+            && self.orders.len() == self.order_count
+    }
+
+    fn emit(&self, event: LevelEvent) {
+        if let Some(sender) = &self.event_sender {
+            // A disconnected receiver only means nobody is listening anymore
+            let _ = sender.send(event);
+        }
+    }
+
+    /// Stamp the icebergs refreshed by `result` with the timestamp of the execution that
+    /// consumed their slice, for replays that override the execution timestamps.
+    pub(crate) fn stamp_iceberg_reveals(&mut self, result: &MatchResult) {
+        let mut replenishments = result.replenishments.iter().peekable();
+        for transaction in result.transactions.as_vec() {
+            let maker_id = transaction.maker_order_id;
+            if replenishments.next_if(|(id, _)| *id == maker_id).is_some()
+                && let Some(Order::IcebergOrder { last_reveal_ms, .. }) =
+                    self.orders.find_mut(&maker_id)
+            {
+                *last_reveal_ms = Some(transaction.timestamp);
+            }
         }
     }
 
+    /// Store `order` in place of the resting order with the same ID, keeping its queue
+    /// position, and return the order it replaced.
+    ///
+    /// With a comparator the position depends on the order's state, so the order is
+    /// re-sorted instead. Returns `None`, leaving the queue unchanged, if no order with that
+    /// ID rests here.
+    fn replace_in_place(&mut self, order: Order<()>) -> Option<Order<()>> {
+        if self.orders.has_comparator() {
+            let replaced = self.orders.remove(&order.id())?;
+            self.orders.push(order);
+            Some(replaced)
+        } else {
+            let slot = self.orders.find_mut(&order.id())?;
+            Some(std::mem::replace(slot, order))
+        }
+    }
+
+    /// Get the lot size of this level
+    pub fn lot_size(&self) -> Quantity {
+        self.lot_size
+    }
+
+    /// Get the total quantity expressed in whole lots
+    pub fn total_lots(&self) -> Quantity {
+        self.total_quantity() / self.lot_size
+    }
+
     /// Get the price of this level
-    pub fn price(&self) -> u64 {
+    pub fn price(&self) -> Price {
         self.price
     }
 
     /// Get the display quantity
-    pub fn display_quantity(&self) -> u64 {
+    pub fn display_quantity(&self) -> Quantity {
         self.display_quantity
     }
 
     /// Get the reserve quantity
-    pub fn reserve_quantity(&self) -> u64 {
+    pub fn reserve_quantity(&self) -> Quantity {
         self.reserve_quantity
     }
 
     /// Get the total quantity (visible + hidden)
-    pub fn total_quantity(&self) -> u64 {
+    pub fn total_quantity(&self) -> Quantity {
         self.display_quantity + self.reserve_quantity
     }
 
+    /// Get the aggregate totals of the level
+    pub fn aggregate(&self) -> PriceLevelAggregate {
+        PriceLevelAggregate {
+            price: self.price,
+            display_quantity: self.display_quantity,
+            reserve_quantity: self.reserve_quantity,
+            order_count: self.order_count,
+        }
+    }
+
+    /// Get the display quantity exposed to market data.
+    ///
+    /// Orders [suppressed in the feed](Order::is_suppressed_in_feed) are left out. They still
+    /// match and count toward [`display_quantity`](Self::display_quantity), which is the
+    /// quantity matching works with.
+    pub fn public_display_quantity(&self) -> Quantity {
+        self.display_quantity
+            .saturating_sub(self.suppressed_totals().0)
+    }
+
+    /// Get the reserve quantity exposed to market data, leaving out suppressed orders
+    pub fn public_reserve_quantity(&self) -> Quantity {
+        self.reserve_quantity
+            .saturating_sub(self.suppressed_totals().1)
+    }
+
+    /// Get the number of orders exposed to market data, leaving out suppressed orders
+    pub fn public_order_count(&self) -> usize {
+        self.order_count.saturating_sub(self.suppressed_totals().2)
+    }
+
+    /// Get the `(display, reserve, count)` of orders suppressed in the feed
+    fn suppressed_totals(&self) -> (Quantity, Quantity, usize) {
+        self.orders
+            .iter()
+            .filter(|order| order.is_suppressed_in_feed())
+            .fold((0, 0, 0), |(display, reserve, count), order| {
+                (
+                    display.saturating_add(order.display_quantity()),
+                    reserve.saturating_add(order.reserve_quantity()),
+                    count + 1,
+                )
+            })
+    }
+
+    /// Get the quantity an incoming order could fill at this level.
+    ///
+    /// Unlike [`total_quantity`](Self::total_quantity), this only counts reserve quantity that
+    /// matching would actually reveal (see [`Order::fillable_quantity`]).
+    pub fn fillable_quantity(&self) -> Quantity {
+        self.orders
+            .iter()
+            .map(|order| order.fillable_quantity())
+            .fold(0, Quantity::saturating_add)
+    }
+
+    /// Get the average price a taker of `quantity` would achieve at this level, or `None` if
+    /// the level cannot fill it completely.
+    ///
+    /// A level trades at a single price, so this is always [`price`](Self::price) when
+    /// `quantity <= fillable_quantity()`. It is computed as a volume-weighted average over
+    /// price tranches so that levels spanning several ticks could report their VWAP.
+    pub fn execution_price(&self, quantity: Quantity) -> Option<Price> {
+        volume_weighted_price(&[(self.price, self.fillable_quantity())], quantity)
+    }
+
+    /// Get the display quantity at the `pct` percentile of the resting orders' sizes, or
+    /// `None` if the level is empty.
+    ///
+    /// Uses the nearest-rank method, so the result is always the display quantity of some
+    /// order: p0 is the smallest, p50 the median and p100 the largest. `pct` above 100 is
+    /// treated as 100.
+    pub fn size_percentile(&self, pct: u8) -> Option<Quantity> {
+        let mut sizes: Vec<Quantity> = self
+            .orders
+            .iter()
+            .map(|order| order.display_quantity())
+            .collect();
+        if sizes.is_empty() {
+            return None;
+        }
+        sizes.sort_unstable();
+        let rank = (sizes.len() * pct.min(100) as usize).div_ceil(100);
+        Some(sizes[rank.saturating_sub(1)])
+    }
+
+    /// Get this level's contribution to a liquidity-weighted index: its price times its
+    /// display quantity, scaled by `weight`. An empty level contributes 0.0.
+    ///
+    /// See [`sum_contributions`] to combine several levels.
+    pub fn index_contribution(&self, weight: f64) -> f64 {
+        self.price as f64 * self.display_quantity as f64 * weight
+    }
+
+    /// Get the hidden quantity that matching would reveal, the hidden analog of
+    /// [`display_quantity`](Self::display_quantity).
+    ///
+    /// Unlike [`reserve_quantity`](Self::reserve_quantity), this leaves out reserve that
+    /// would be discarded when its order is removed, such as that of reserve orders without
+    /// auto-replenish. It equals `fillable_quantity() - display_quantity()`.
+    pub fn iceberg_overhang(&self) -> Quantity {
+        self.orders
+            .iter()
+            .map(|order| order.fillable_quantity() - order.display_quantity())
+            .fold(0, Quantity::saturating_add)
+    }
+
+    /// Get the quantity a taker on `side` with limit price `limit` could fill at this level.
+    ///
+    /// A buy taker accepts levels priced at or below its limit and a sell taker levels priced
+    /// at or above it; otherwise this returns 0.
+    pub fn quantity_at_or_better(&self, limit: Price, side: Side) -> Quantity {
+        let acceptable = match side {
+            Side::Buy => self.price <= limit,
+            Side::Sell => self.price >= limit,
+        };
+
+        if acceptable {
+            self.fillable_quantity()
+        } else {
+            0
+        }
+    }
+
+    /// Get the resting orders sliced from `parent`, in queue order.
+    pub fn children_of(&self, parent: OrderId) -> Vec<Order<()>> {
+        self.orders
+            .iter()
+            .filter(|order| order.parent_id() == Some(parent))
+            .copied()
+            .collect()
+    }
+
+    /// Get the `(oldest, newest)` order timestamps at the level, or `None` if it is empty.
+    ///
+    /// Timestamps are taken as stored on the orders, so with a custom queue order or restored
+    /// priority the oldest order is not necessarily at the head.
+    pub fn timestamp_range(&self) -> Option<(Timestamp, Timestamp)> {
+        self.orders.iter().fold(None, |range, order| {
+            let timestamp = order.timestamp();
+            Some(match range {
+                None => (timestamp, timestamp),
+                Some((oldest, newest)) => (oldest.min(timestamp), newest.max(timestamp)),
+            })
+        })
+    }
+
+    /// Shift every order's timestamp so the oldest becomes `new_base_ms`, keeping the gaps
+    /// between them.
+    ///
+    /// Orders restored from a snapshot carry the timestamps they were captured with, which
+    /// skews waiting-time statistics and minimum resting time checks after a restart. Queue
+    /// positions are not affected, and the last timer-driven reveal of reserve orders is
+    /// shifted by the same amount. Does nothing on an empty level.
+    pub fn rebase_timestamps(&mut self, new_base_ms: Timestamp) {
+        let Some((oldest, _)) = self.timestamp_range() else {
+            return;
+        };
+        let rebase =
+            |timestamp: Timestamp| new_base_ms.saturating_add(timestamp.saturating_sub(oldest));
+
+        let ids: Vec<OrderId> = self.orders.iter().map(|order| order.id()).collect();
+        for order_id in ids {
+            let Some(order) = self.orders.find(&order_id) else {
+                continue;
+            };
+            let mut order = order.with_timestamp(rebase(order.timestamp()));
+            if let Order::ReserveOrder {
+                last_reveal_ms: Some(last_reveal_ms),
+                ..
+            } = &mut order
+            {
+                *last_reveal_ms = rebase(*last_reveal_ms);
+            }
+            self.replace_in_place(order);
+        }
+    }
+
+    /// Get the IDs in `[start, end)` that are not resting at this level, in ascending order.
+    ///
+    /// For audits where order IDs are assigned contiguously per session and created with
+    /// [`OrderId::from_u64`]. Each ID is looked up in the queue's ID index, so the cost is
+    /// proportional to the size of the range, not to the number of orders.
+    pub fn missing_ids_in_range(&self, start: u64, end: u64) -> Vec<u64> {
+        (start..end)
+            .filter(|&id| self.orders.find(&OrderId::from_u64(id)).is_none())
+            .collect()
+    }
+
+    /// Get the `(display, reserve)` liquidity the level would lose if `order_id` were removed,
+    /// without removing it. Returns `None` if the order is not resting at this level.
+    pub fn impact_of_removing(&self, order_id: OrderId) -> Option<(Quantity, Quantity)> {
+        self.orders
+            .find(&order_id)
+            .map(|order| (order.display_quantity(), order.reserve_quantity()))
+    }
+
+    /// Find the resting order a taker of `cumulative_qty` would stop in, and how far into it.
+    ///
+    /// The taker is dry-run against the queue as in
+    /// [`fill_distribution`](Self::fill_distribution), so the walk follows the level's
+    /// priority and counts the reserve that matching would reveal. Returns the order, as it
+    /// rests now, that the last unit trades against, together with the total quantity taken
+    /// from it. `None` is returned if the level cannot fill `cumulative_qty` (or is empty); a
+    /// zero quantity stops at the front of the queue.
+    pub fn order_at_cumulative(&self, cumulative_qty: Quantity) -> Option<(Order<()>, Quantity)> {
+        if cumulative_qty == 0 {
+            return self.orders.iter().next().map(|order| (*order, 0));
+        }
+
+        let mut last: Option<OrderId> = None;
+        let mut taken: HashMap<OrderId, Quantity> = HashMap::new();
+        let remaining = run_match(
+            &mut self.orders.clone(),
+            cumulative_qty,
+            |_| false,
+            None,
+            |fill| {
+                if fill.consumed > 0 {
+                    last = Some(fill.order.id());
+                    *taken.entry(fill.order.id()).or_default() += fill.consumed;
+                }
+            },
+        );
+        if remaining > 0 {
+            return None;
+        }
+        let order_id = last?;
+        Some((*self.orders.find(&order_id)?, taken[&order_id]))
+    }
+
+    /// Get the liquidity-density score of the level using the default
+    /// [`PressureFormula::AverageOrderSize`].
+    pub fn pressure(&self) -> u64 {
+        self.pressure_with(PressureFormula::default())
+    }
+
+    /// Get the liquidity-density score of the level using the given formula.
+    ///
+    /// Only visible quantity is considered, since that is what spoofing tries to display.
+    pub fn pressure_with(&self, formula: PressureFormula) -> u64 {
+        formula.score(self.display_quantity, self.order_count)
+    }
+
+    /// Get the signed distance between this level's price and a reference mid price.
+    ///
+    /// Positive values mean the level is above the mid. This is the per-level building block
+    /// for effective and quoted spread calculations; the result saturates at the `i64` bounds.
+    pub fn half_spread_to(&self, reference_mid: Price) -> i64 {
+        let distance = self.price as i128 - reference_mid as i128;
+        distance.clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    /// Returns true if this level, resting on `side`, sits inside the opposite best price.
+    ///
+    /// A bid level is inside when it is priced below the best ask, and an ask level when it is
+    /// priced above the best bid. A level at or through the opposite best would cross the book.
+    pub fn is_inside(&self, best_opposite: Price, side: Side) -> bool {
+        match side {
+            Side::Buy => self.price < best_opposite,
+            Side::Sell => self.price > best_opposite,
+        }
+    }
+
     /// Get the number of orders
     pub fn order_count(&self) -> usize {
         self.order_count
     }
 
+    /// Returns true if there are no orders resting at this price level
+    pub fn is_empty(&self) -> bool {
+        self.order_count == 0
+    }
+
     /// Get the statistics for this price level
     pub fn stats(&self) -> &PriceLevelStatistics {
         &self.stats
     }
 
+    /// Get an owned point-in-time copy of the statistics for this price level.
+    ///
+    /// Unlike [`stats`](Self::stats), the copy does not borrow the level, so it can be kept
+    /// while the level keeps changing.
+    pub fn stats_snapshot(&self) -> PriceLevelStatistics {
+        self.stats.clone()
+    }
+
     /// Add an order to this price level
-    pub fn add_order(&mut self, order: Order<()>) -> &Order<()> {
+    ///
+    /// An iceberg or reserve order entered with nothing displayed but a non-zero reserve
+    /// could never trade, since slices are only revealed after the displayed quantity is
+    /// consumed. Such an order is accepted with an initial slice revealed from its reserve:
+    /// the order's replenish amount for reserve orders (at least 1), and
+    /// [`DEFAULT_RESERVE_REPLENISH_AMOUNT`] for icebergs, capped at the reserve. An iceberg
+    /// with no `original_display` records its displayed quantity there, as the size of later
+    /// slices. Levels built from orders or restored from snapshots do the same.
+    ///
+    /// # Errors
+    ///
+    /// Both the display and reserve quantity must be multiples of the level's lot size,
+    /// otherwise [`PriceLevelError::InvalidLotSize`] is returned. If a price band is
+    /// configured, orders outside it are rejected with [`PriceLevelError::PriceOutOfBand`], and
+    /// orders above the notional cap with [`PriceLevelError::NotionalTooLarge`].
+    /// On error the level is unchanged.
+    pub fn add_order(&mut self, order: Order<()>) -> Result<&Order<()>, PriceLevelError> {
+        self.check_limits(&order)?;
+        Ok(self.insert_order(order))
+    }
+
+    /// Queue an order that already passed [`check_limits`](Self::check_limits), updating the
+    /// aggregates and statistics as described on [`add_order`](Self::add_order).
+    fn insert_order(&mut self, order: Order<()>) -> &Order<()> {
+        let order = reveal_initial_slice(order);
+
+        // The queue replaces an order that reuses an existing ID, so its contribution
+        // must be released first or the aggregates would count it twice.
+        if let Some(replaced) = self.orders.remove(&order.id()) {
+            self.display_quantity = self
+                .arithmetic
+                .sub(self.display_quantity, replaced.display_quantity());
+            self.reserve_quantity = self
+                .arithmetic
+                .sub(self.reserve_quantity, replaced.reserve_quantity());
+            self.order_count = self.arithmetic.sub_count(self.order_count, 1);
+            self.revealed_unexecuted.remove(&replaced.id());
+        }
+
         // Calculate quantities
         let visible_qty = order.display_quantity();
         let hidden_qty = order.reserve_quantity();
 
         // Update counters
-        self.display_quantity += visible_qty;
-        self.reserve_quantity += hidden_qty;
-        self.order_count += 1;
+        self.display_quantity = self.arithmetic.add(self.display_quantity, visible_qty);
+        self.reserve_quantity = self.arithmetic.add(self.reserve_quantity, hidden_qty);
+        self.order_count = self.arithmetic.add_count(self.order_count, 1);
 
         // Update statistics
         self.stats.record_order_added();
+        self.stats.observe_display_quantity(self.display_quantity);
+        metrics::record_order_added(self.price);
+        self.emit(LevelEvent::OrderAdded {
+            price: self.price,
+            order,
+        });
 
         // Add to order queue
         self.orders.push(order)
     }
 
+    /// Check an order against the level's price band, notional cap and lot size, as
+    /// described on [`add_order`](Self::add_order).
+    fn check_limits(&self, order: &Order<()>) -> Result<(), PriceLevelError> {
+        if let Some((reference, band_bps)) = self.price_band
+            && !order.passes_price_band(reference, band_bps)
+        {
+            return Err(PriceLevelError::PriceOutOfBand {
+                price: order.price(),
+                reference,
+                band_bps,
+            });
+        }
+
+        if let Some(max_notional) = self.max_order_notional
+            && order.notional() > max_notional
+        {
+            return Err(PriceLevelError::NotionalTooLarge {
+                notional: order.notional(),
+                max_notional,
+            });
+        }
+
+        for quantity in [order.display_quantity(), order.reserve_quantity()] {
+            if quantity % self.lot_size != 0 {
+                return Err(PriceLevelError::InvalidLotSize {
+                    quantity,
+                    lot_size: self.lot_size,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the order that would match first, without copying it.
+    ///
+    /// This is the head of the queue, so it follows the level's priority, including a
+    /// comparator set with [`with_comparator`](Self::with_comparator). The borrow is tied to
+    /// `&self` and must end before the level is modified, e.g. by matching. Orders on either
+    /// side are returned; [`match_order_sided`](Self::match_order_sided) may skip it.
+    pub fn front(&self) -> Option<&Order<()>> {
+        self.orders.front()
+    }
+
+    /// Get the next `n` orders that would match, in priority order, without copying them.
+    ///
+    /// This extends [`front`](Self::front) for pre-trade checks that look past the first
+    /// maker, with the same borrowing rules.
+    pub fn next_to_match(&self, n: usize) -> Vec<&Order<()>> {
+        self.orders.peek_n(n)
+    }
+
     /// Creates an iterator over the orders in the price level.
+    ///
+    /// Orders are returned in queue order: the order they arrived at the level, or the
+    /// comparator's order on a level built [`with_comparator`](Self::with_comparator).
+    /// Timestamps are never used for ordering by default, so orders sharing a timestamp keep
+    /// their arrival order, including across snapshot round-trips.
     pub fn iter_orders(&self) -> Vec<Order<()>> {
         self.orders.to_vec()
     }
 
+    /// Visit the resting orders in queue order, stopping at the first error.
+    ///
+    /// Unlike [`iter_orders`](Self::iter_orders), nothing is collected, so scans that stop at
+    /// a boundary can return early with `Err` and skip the rest of the queue. The first error
+    /// is returned as is.
+    pub fn try_for_each_order<E>(
+        &self,
+        mut f: impl FnMut(&Order<()>) -> Result<(), E>,
+    ) -> Result<(), E> {
+        for order in self.orders.iter() {
+            f(order)?;
+        }
+        Ok(())
+    }
+
+    /// Get the resting orders exposed to market data in queue order, leaving out orders
+    /// [suppressed in the feed](Order::is_suppressed_in_feed).
+    pub fn iter_public_orders(&self) -> Vec<Order<()>> {
+        self.orders
+            .iter()
+            .filter(|order| !order.is_suppressed_in_feed())
+            .copied()
+            .collect()
+    }
+
+    /// Get owned copies of the resting orders in queue order.
+    ///
+    /// Orders are stored by value, so this is the same list as
+    /// [`iter_orders`](Self::iter_orders); it exists for serialization and inspection callers
+    /// that want to make explicit they are holding detached copies.
+    pub fn orders_owned(&self) -> Vec<Order<()>> {
+        self.orders.to_vec()
+    }
+
+    /// Get the orders in the exact sequence [`match_order`](Self::match_order) would consume
+    /// them.
+    ///
+    /// This is the single source of truth for "what matches next". The level matches in
+    /// queue order, which follows the comparator of a level built
+    /// [`with_comparator`](Self::with_comparator), so this equals
+    /// [`iter_orders`](Self::iter_orders); callers that care about matching priority should
+    /// use this method so they keep working if the priority scheme changes.
+    pub fn orders_by_priority(&self) -> Vec<Order<()>> {
+        self.orders.to_vec()
+    }
+
     /// Matches an incoming order against existing orders at this price level.
     ///
     /// This function attempts to match the incoming order quantity against the orders present in the
@@ -150,93 +909,477 @@ impl PriceLevel {
     /// during the matching process.
     pub fn match_order(
         &mut self,
-        incoming_quantity: u64,
+        incoming_quantity: Quantity,
         taker_order_id: OrderId,
         transaction_id_generator: &UuidGenerator,
     ) -> MatchResult {
-        let mut result = MatchResult::new(taker_order_id, incoming_quantity);
-        let mut remaining = incoming_quantity;
+        self.match_order_inner(
+            incoming_quantity,
+            taker_order_id,
+            transaction_id_generator,
+            |_| false,
+            None,
+        )
+    }
 
-        while remaining > 0 {
-            let Some(order) = self.orders.pop() else {
-                break;
-            };
+    /// Matches an incoming order like [`match_order`](Self::match_order), annotating every
+    /// transaction with the maker and taker fees from `fees`.
+    pub fn match_order_with_fees(
+        &mut self,
+        incoming_quantity: Quantity,
+        taker_order_id: OrderId,
+        transaction_id_generator: &UuidGenerator,
+        fees: &FeeSchedule,
+    ) -> MatchResult {
+        let mut result =
+            self.match_order(incoming_quantity, taker_order_id, transaction_id_generator);
+        for transaction in &mut result.transactions.transactions {
+            fees.apply(transaction);
+        }
+        result
+    }
 
-            let (consumed, updated_order, hidden_reduced, new_remaining) =
-                order.match_against(remaining);
+    /// Matches an incoming order, only trading against makers on the opposite side of
+    /// `taker_side`.
+    ///
+    /// [`match_order`](Self::match_order) trusts the caller to route takers to the opposite
+    /// side of the book. This variant guards against a same-side taker silently filling
+    /// against its own side: resting orders on `taker_side` are skipped and keep their queue
+    /// position.
+    pub fn match_order_sided(
+        &mut self,
+        incoming_quantity: Quantity,
+        taker_order_id: OrderId,
+        transaction_id_generator: &UuidGenerator,
+        taker_side: Side,
+    ) -> MatchResult {
+        self.match_order_inner(
+            incoming_quantity,
+            taker_order_id,
+            transaction_id_generator,
+            |order| order.side() == taker_side,
+            None,
+        )
+    }
 
-            if consumed > 0 {
-                // Update display quantity counter
-                self.display_quantity -= consumed;
+    /// Matches an incoming order, taking no more than `max_per_maker` from any resting order
+    /// in one pass over the queue.
+    ///
+    /// Each maker trades at most the cap and, if it still has quantity, goes to the back of
+    /// the queue as after any partial fill, so the orders are served round-robin until the
+    /// incoming quantity is exhausted or every maker is filled. Some venues allocate this
+    /// way to keep a single large order from absorbing all incoming flow. A cap of 0
+    /// matches nothing. On a level built [`with_comparator`](Self::with_comparator), requeued
+    /// makers take the position the comparator gives them instead.
+    pub fn match_order_capped_per_maker(
+        &mut self,
+        incoming_quantity: Quantity,
+        taker_order_id: OrderId,
+        transaction_id_generator: &UuidGenerator,
+        max_per_maker: Quantity,
+    ) -> MatchResult {
+        if max_per_maker == 0 {
+            self.last_removed.clear();
+            return MatchResult::new(taker_order_id, incoming_quantity);
+        }
+        self.match_order_inner(
+            incoming_quantity,
+            taker_order_id,
+            transaction_id_generator,
+            |_| false,
+            Some(max_per_maker),
+        )
+    }
 
-                // Use UUID generator directly
-                let transaction_id = transaction_id_generator.next();
+    /// Matches an incoming order only against resting orders that have rested for at least
+    /// `min_resting_ms` at `now_ms`.
+    ///
+    /// This implements a minimum resting time ("speed bump") rule: orders whose timestamp is
+    /// later than `now_ms - min_resting_ms` are skipped and keep their queue position, so a
+    /// fresher order ahead in the queue does not block older ones behind it. A refreshed
+    /// iceberg or reserve order keeps the timestamp it rested with.
+    pub fn match_order_min_age(
+        &mut self,
+        incoming_quantity: Quantity,
+        taker_order_id: OrderId,
+        transaction_id_generator: &UuidGenerator,
+        min_resting_ms: u64,
+        now_ms: Timestamp,
+    ) -> MatchResult {
+        let rested_since = now_ms.saturating_sub(min_resting_ms);
+        self.match_order_inner(
+            incoming_quantity,
+            taker_order_id,
+            transaction_id_generator,
+            |order| order.timestamp() > rested_since,
+            None,
+        )
+    }
 
-                let transaction = Transaction::new(
-                    transaction_id,
-                    taker_order_id,
-                    order.id(),
-                    self.price,
-                    consumed,
-                    order.side().opposite(),
-                );
+    /// Matches an incoming order at time `now_ms`, skipping good-till-date orders that have
+    /// expired.
+    ///
+    /// [`match_order`](Self::match_order) does not look at time in force, so a
+    /// [`TimeInForce::Gtd`] order past its expiry still trades until it is purged. Here an
+    /// order whose expiry is at or before `now_ms` never produces a transaction; it keeps its
+    /// place and is left for [`purge_expired`](Self::purge_expired) to remove.
+    pub fn match_order_at(
+        &mut self,
+        incoming_quantity: Quantity,
+        taker_order_id: OrderId,
+        transaction_id_generator: &UuidGenerator,
+        now_ms: Timestamp,
+    ) -> MatchResult {
+        self.match_order_inner(
+            incoming_quantity,
+            taker_order_id,
+            transaction_id_generator,
+            |order| is_gtd_expired(order, now_ms),
+            None,
+        )
+    }
 
-                result.add_transaction(transaction);
+    /// Matches an incoming order object, applying its limit price and time in force.
+    ///
+    /// The taker trades its display quantity against makers on the opposite side (see
+    /// [`match_order_sided`](Self::match_order_sided)), and only if this level is priced at or
+    /// better than its limit. A fill-or-kill taker trades only if those makers can fill it
+    /// entirely, by the same liquidity check as [`try_match_fok`](Self::try_match_fok);
+    /// otherwise nothing trades. The unfilled remainder of immediate-or-cancel and fill-or-kill takers
+    /// is reported as [`cancelled_quantity`](MatchResult::cancelled_quantity); for other
+    /// takers it is left for the caller to rest.
+    pub fn match_taker(
+        &mut self,
+        taker: &Order<()>,
+        transaction_id_generator: &UuidGenerator,
+    ) -> MatchResult {
+        let quantity = taker.display_quantity();
+        let marketable = match taker.side() {
+            Side::Buy => self.price <= taker.price(),
+            Side::Sell => self.price >= taker.price(),
+        };
+        let fillable = marketable
+            && (!taker.is_fill_or_kill()
+                || self.can_fill(quantity, |order| order.side() == taker.side()));
 
-                // If the order was completely executed, add it to filled_order_ids
-                if updated_order.is_none() {
-                    result.add_filled_order_id(order.id());
-                }
-            }
+        let mut result = if !fillable {
+            self.last_removed.clear();
+            MatchResult::new(taker.id(), quantity)
+        } else {
+            self.match_order_sided(quantity, taker.id(), transaction_id_generator, taker.side())
+        };
+
+        if taker.is_immediate() {
+            result.cancelled_quantity = result.remaining_quantity;
+        }
+        result
+    }
+
+    fn match_order_inner(
+        &mut self,
+        incoming_quantity: Quantity,
+        taker_order_id: OrderId,
+        transaction_id_generator: &UuidGenerator,
+        skip: impl Fn(&Order<()>) -> bool,
+        max_per_maker: Option<Quantity>,
+    ) -> MatchResult {
+        let started = metrics::match_started();
+        let mut result = MatchResult::new(taker_order_id, incoming_quantity);
+        self.last_removed.clear();
+
+        // The queue is walked outside `self` so the fills below can update the level
+        let mut orders = std::mem::take(&mut self.orders);
+        let remaining = run_match(
+            &mut orders,
+            incoming_quantity,
+            skip,
+            max_per_maker,
+            |fill| {
+                let MakerFill {
+                    order,
+                    consumed,
+                    updated,
+                    hidden_reduced,
+                } = *fill;
+
+                if consumed > 0 {
+                    // Update display quantity counter
+                    self.display_quantity = self.arithmetic.sub(self.display_quantity, consumed);
+
+                    // Use UUID generator directly
+                    let transaction_id = transaction_id_generator.next();
+
+                    let transaction = Transaction::new(
+                        transaction_id,
+                        taker_order_id,
+                        order.id(),
+                        self.price,
+                        consumed,
+                        order.side().opposite(),
+                    );
+
+                    result.add_transaction(transaction);
+                    result.add_depleted_maker(order.id());
+                    // A refreshed iceberg is stamped with the execution that consumed its slice
+                    if hidden_reduced > 0
+                        && let Some(Order::IcebergOrder { last_reveal_ms, .. }) = &mut fill.updated
+                    {
+                        *last_reveal_ms = Some(transaction.timestamp);
+                    }
+                    self.emit(LevelEvent::OrderFilled {
+                        price: self.price,
+                        transaction,
+                    });
 
-            remaining = new_remaining;
+                    if let Some(group) = order.oco_group() {
+                        result.add_triggered_oco_group(group);
+                    }
 
-            // Calculate waiting time
-            let current_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis() as u64;
-            let waiting_time = current_time.saturating_sub(order.timestamp());
+                    // If the order was completely executed, add it to filled_order_ids
+                    if updated.is_none() {
+                        result.add_filled_order_id(order.id());
+                    }
+                }
 
-            // update statistics
-            self.stats
-                .record_execution(consumed, order.price(), waiting_time);
+                // Calculate waiting time
+                let current_time = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+                let waiting_time = current_time.saturating_sub(order.timestamp());
 
-            if let Some(updated) = updated_order {
-                if hidden_reduced > 0 {
-                    self.reserve_quantity -= hidden_reduced;
-                    self.display_quantity += hidden_reduced;
+                // update statistics
+                self.stats.record_execution(
+                    consumed,
+                    order.price(),
+                    waiting_time,
+                    order.side().opposite(),
+                );
+                let hidden_executed =
+                    take_revealed(&mut self.revealed_unexecuted, &order, consumed);
+                if hidden_executed > 0 {
+                    self.stats.record_hidden_execution(hidden_executed);
                 }
 
-                self.orders.push(updated);
-            } else {
-                self.order_count -= 1;
-                match order {
-                    Order::IcebergOrder {
-                        reserve_quantity, ..
-                    } => {
-                        if reserve_quantity > 0 && hidden_reduced == 0 {
-                            self.reserve_quantity -= reserve_quantity;
-                        }
+                if updated.is_some() {
+                    if hidden_reduced > 0 {
+                        self.reserve_quantity =
+                            self.arithmetic.sub(self.reserve_quantity, hidden_reduced);
+                        self.display_quantity =
+                            self.arithmetic.add(self.display_quantity, hidden_reduced);
+                        track_revealed(&mut self.revealed_unexecuted, order.id(), hidden_reduced);
+                        result.add_replenishment(order.id(), hidden_reduced);
+                        self.emit(LevelEvent::OrderReplenished {
+                            price: self.price,
+                            order_id: order.id(),
+                            quantity: hidden_reduced,
+                        });
                     }
-                    Order::ReserveOrder {
-                        reserve_quantity, ..
-                    } => {
-                        if reserve_quantity > 0 && hidden_reduced == 0 {
-                            self.reserve_quantity -= reserve_quantity;
+                } else {
+                    self.order_count = self.arithmetic.sub_count(self.order_count, 1);
+                    self.revealed_unexecuted.remove(&order.id());
+                    self.last_removed.push(order);
+                    match order {
+                        Order::IcebergOrder {
+                            reserve_quantity, ..
+                        }
+                        | Order::ReserveOrder {
+                            reserve_quantity, ..
+                        } if reserve_quantity > 0 && hidden_reduced == 0 => {
+                            self.reserve_quantity =
+                                self.arithmetic.sub(self.reserve_quantity, reserve_quantity);
                         }
+                        _ => {}
                     }
-                    _ => {}
                 }
-            }
-        }
+            },
+        );
+        self.orders = orders;
 
         result.is_complete = remaining == 0;
         result.remaining_quantity = remaining;
+        self.stats.observe_display_quantity(self.display_quantity);
+        metrics::record_match(self.price, result.transactions.len(), started);
+        result
+    }
+
+    /// Compute how a taker of `quantity` would be allocated across the resting orders,
+    /// without modifying the level.
+    ///
+    /// This dry-runs [`match_order`](Self::match_order) on a copy of the queue, so it follows
+    /// the level's priority, including a comparator and iceberg and reserve refreshes, and
+    /// returns one entry per maker in the order it is first hit. A maker that fills across
+    /// several refreshes appears once with its total.
+    pub fn fill_distribution(&self, quantity: Quantity) -> Vec<(OrderId, Quantity)> {
+        let mut allocation: Vec<(OrderId, Quantity)> = Vec::new();
+        run_match(
+            &mut self.orders.clone(),
+            quantity,
+            |_| false,
+            None,
+            |fill| {
+                if fill.consumed == 0 {
+                    return;
+                }
+                match allocation.iter_mut().find(|(id, _)| *id == fill.order.id()) {
+                    Some((_, filled)) => *filled += fill.consumed,
+                    None => allocation.push((fill.order.id(), fill.consumed)),
+                }
+            },
+        );
+        allocation
+    }
+
+    /// Whether matching `quantity` against the orders for which `skip` is false would fill it
+    /// completely, found by a dry run of the matching loop on a copy of the queue.
+    fn can_fill(&self, quantity: Quantity, skip: impl Fn(&Order<()>) -> bool) -> bool {
+        run_match(&mut self.orders.clone(), quantity, skip, None, |_| {}) == 0
+    }
+
+    /// Match an incoming fill-or-kill order only if this level can fill all of it.
+    ///
+    /// The liquidity check is a dry run of the matching loop, as in
+    /// [`fill_distribution`](Self::fill_distribution), so hidden quantity counts only where
+    /// matching would actually reveal it, and orders that cannot trade are passed over. Returns `None` and leaves the level untouched if
+    /// `incoming_quantity` cannot be filled completely; otherwise matches it with
+    /// [`match_order`](Self::match_order).
+    pub fn try_match_fok(
+        &mut self,
+        incoming_quantity: Quantity,
+        taker_order_id: OrderId,
+        transaction_id_generator: &UuidGenerator,
+    ) -> Option<MatchResult> {
+        if !self.can_fill(incoming_quantity, |_| false) {
+            return None;
+        }
+
+        let result = self.match_order(incoming_quantity, taker_order_id, transaction_id_generator);
+        debug_assert!(result.is_complete, "dry run and match disagree");
+        Some(result)
+    }
+
+    /// Match an incoming order while keeping it under a share of recent traded volume.
+    ///
+    /// The executed quantity is capped at `recent_volume * max_participation_pct / 100`, so a
+    /// POV or VWAP algo never takes more than that share at this level. Quantity held back by
+    /// the cap is reported as remaining, and [`MatchResult::participation_cap`] records the cap
+    /// whenever it bound the request.
+    pub fn match_with_participation(
+        &mut self,
+        incoming_quantity: Quantity,
+        taker_order_id: OrderId,
+        transaction_id_generator: &UuidGenerator,
+        recent_volume: Quantity,
+        max_participation_pct: u8,
+    ) -> MatchResult {
+        let cap = (recent_volume as u128 * max_participation_pct as u128 / 100)
+            .min(Quantity::MAX as u128) as Quantity;
+        if incoming_quantity <= cap {
+            return self.match_order(incoming_quantity, taker_order_id, transaction_id_generator);
+        }
+
+        let mut result = self.match_order(cap, taker_order_id, transaction_id_generator);
+        result.remaining_quantity += incoming_quantity - cap;
+        result.is_complete = false;
+        result.participation_cap = Some(cap);
         result
     }
 
-    /// Create a snapshot of the current price level state
+    /// Take the orders fully consumed and removed by the most recent match.
+    ///
+    /// Each match replaces the buffer, and this call leaves it empty. The orders are returned
+    /// in the state they rested in just before their final fill, in the order they were
+    /// removed, so callers can emit fill events without scanning the queue.
+    pub fn take_last_removed(&mut self) -> Vec<Order<()>> {
+        std::mem::take(&mut self.last_removed)
+    }
+
+    /// Reveal a new display slice for every timed reserve order whose interval has elapsed.
+    ///
+    /// Reserve orders with `auto_replenish_interval_ms` set move `replenish_amount` (or
+    /// [`DEFAULT_RESERVE_REPLENISH_AMOUNT`]) from reserve to display once `now_ms` is at least
+    /// one interval past their last reveal, independently of matching. At most one slice is
+    /// revealed per order per call, and orders keep their queue position, unless the level's
+    /// comparator places them elsewhere after the reveal. Returns the total quantity revealed.
+    pub fn tick_replenish(&mut self, now_ms: Timestamp) -> Quantity {
+        let due: Vec<OrderId> = self
+            .orders
+            .iter()
+            .filter(|order| match order {
+                Order::ReserveOrder {
+                    common,
+                    auto_replenish_interval_ms: Some(interval),
+                    last_reveal_ms,
+                    ..
+                } => {
+                    let since = last_reveal_ms.unwrap_or(common.timestamp);
+                    order.revealable_reserve() > 0 && now_ms >= since.saturating_add(*interval)
+                }
+                _ => false,
+            })
+            .map(|order| order.id())
+            .collect();
+
+        let mut revealed: Quantity = 0;
+        for order_id in due {
+            let Some(mut order) = self.orders.find(&order_id).copied() else {
+                continue;
+            };
+            let revealable = order.revealable_reserve();
+            if let Order::ReserveOrder {
+                common,
+                reserve_quantity,
+                replenish_amount,
+                last_reveal_ms,
+                revealed_quantity,
+                ..
+            } = &mut order
+            {
+                let slice = replenish_amount
+                    .unwrap_or(DEFAULT_RESERVE_REPLENISH_AMOUNT)
+                    .min(revealable);
+                common.display_quantity += slice;
+                *reserve_quantity -= slice;
+                *revealed_quantity += slice;
+                *last_reveal_ms = Some(now_ms);
+                revealed += slice;
+                track_revealed(&mut self.revealed_unexecuted, order_id, slice);
+                self.replace_in_place(order);
+                self.emit(LevelEvent::OrderReplenished {
+                    price: self.price,
+                    order_id,
+                    quantity: slice,
+                });
+            }
+        }
+
+        self.display_quantity = self.arithmetic.add(self.display_quantity, revealed);
+        self.reserve_quantity = self.arithmetic.sub(self.reserve_quantity, revealed);
+        self.stats.observe_display_quantity(self.display_quantity);
+        revealed
+    }
+
+    /// Check whether two levels hold the same price, aggregates and orders in the same queue
+    /// order.
+    ///
+    /// `PartialEq` for `PriceLevel` deliberately compares only the price, so that levels can
+    /// be keyed and ordered by price. Use this when the contents matter, e.g. in tests.
+    pub fn content_eq(&self, other: &Self) -> bool {
+        self.snapshot() == other.snapshot()
+    }
+
+    /// Get the canonical text form of the level, the cross-language contract for hashing.
+    ///
+    /// This is [`PriceLevelSnapshot::canonical_string`] of a [`snapshot`](Self::snapshot):
+    /// every field of the level and its orders, always present and in a fixed order.
+    pub fn canonical_string(&self) -> String {
+        self.snapshot().canonical_string()
+    }
+
+    /// Create a snapshot of the current price level state.
+    ///
+    /// This is the complete state used for persistence and recovery, so it includes orders
+    /// suppressed in the feed; publish [`public_snapshot`](Self::public_snapshot) instead.
     pub fn snapshot(&self) -> PriceLevelSnapshot {
         PriceLevelSnapshot {
             price: self.price,
@@ -247,21 +1390,557 @@ impl PriceLevel {
         }
     }
 
+    /// Overwrite `snapshot` with the current state of the level, like
+    /// [`snapshot`](Self::snapshot), reusing its `orders` buffer.
+    ///
+    /// Publishers that snapshot many times per second can keep one snapshot per level and
+    /// refill it, so the order vector only allocates when the level grows beyond any size
+    /// it had before.
+    pub fn snapshot_into(&self, snapshot: &mut PriceLevelSnapshot) {
+        snapshot.price = self.price;
+        snapshot.display_quantity = self.display_quantity();
+        snapshot.reserve_quantity = self.reserve_quantity();
+        snapshot.order_count = self.order_count();
+        snapshot.orders.clear();
+        snapshot.orders.extend(self.orders.iter().copied());
+    }
+
+    /// Compare two levels by matching priority on the given book side.
+    ///
+    /// Asks keep the ascending price order of [`Ord`], while bids are reversed so the
+    /// highest price comes first. In both cases the better level compares as `Less`, so
+    /// `levels.sort_by(|a, b| a.cmp_for_side(b, side))` yields best-first order.
+    pub fn cmp_for_side(&self, other: &Self, side: Side) -> std::cmp::Ordering {
+        match side {
+            Side::Buy => other.price.cmp(&self.price),
+            Side::Sell => self.price.cmp(&other.price),
+        }
+    }
+
+    /// Create a snapshot of the level as market data sees it, without orders suppressed in
+    /// the feed and with aggregates to match.
+    ///
+    /// Restoring from it drops the suppressed orders, so use [`snapshot`](Self::snapshot) for
+    /// persistence.
+    pub fn public_snapshot(&self) -> PriceLevelSnapshot {
+        let orders = self.iter_public_orders();
+        PriceLevelSnapshot {
+            price: self.price,
+            display_quantity: orders
+                .iter()
+                .map(|order| order.display_quantity())
+                .fold(0, Quantity::saturating_add),
+            reserve_quantity: orders
+                .iter()
+                .map(|order| order.reserve_quantity())
+                .fold(0, Quantity::saturating_add),
+            order_count: orders.len(),
+            orders,
+        }
+    }
+
+    /// Export the level aggregates as a flat depth-of-market row.
+    ///
+    /// A price level does not know which side of the book it belongs to, so the caller
+    /// supplies `side` (the book already tracks bid and ask levels separately). Orders
+    /// suppressed in the feed are left out.
+    pub fn to_dom_row(&self, side: Side) -> DomRow {
+        let (suppressed_display, suppressed_reserve, suppressed_count) = self.suppressed_totals();
+        DomRow {
+            price: self.price,
+            visible_qty: self.display_quantity.saturating_sub(suppressed_display),
+            hidden_qty: self.reserve_quantity.saturating_sub(suppressed_reserve),
+            order_count: self.order_count.saturating_sub(suppressed_count),
+            side,
+        }
+    }
+
+    /// Render the level as a one-line ASCII depth bar, e.g. `10000 | ████████ 150 (2)`.
+    ///
+    /// The bar is scaled so that `max_width` cells correspond to the level's total (display
+    /// plus reserve) quantity, showing how much of its depth is displayed. To compare levels
+    /// in a book, use [`to_depth_bar_scaled`](Self::to_depth_bar_scaled) with a common scale.
+    pub fn to_depth_bar(&self, max_width: usize) -> String {
+        self.to_depth_bar_scaled(max_width, self.total_quantity())
+    }
+
+    /// Render the level as a one-line ASCII depth bar where `max_width` cells correspond to
+    /// `full_scale` display quantity, typically the largest level in the book.
+    ///
+    /// Any non-zero display quantity gets at least one cell, and the bar never exceeds
+    /// `max_width`. An empty bar is omitted entirely, e.g. `10000 | 0 (0)`.
+    pub fn to_depth_bar_scaled(&self, max_width: usize, full_scale: Quantity) -> String {
+        let cells = if self.display_quantity == 0 || full_scale == 0 {
+            0
+        } else {
+            let scaled = self.display_quantity as u128 * max_width as u128 / full_scale as u128;
+            (scaled as usize).clamp(1, max_width)
+        };
+
+        let bar = if cells == 0 {
+            String::new()
+        } else {
+            format!("{} ", "█".repeat(cells))
+        };
+
+        format!(
+            "{} | {}{} ({})",
+            self.price, bar, self.display_quantity, self.order_count
+        )
+    }
+
     /// Serialize the current price level state into a checksum-protected snapshot package.
     pub fn snapshot_package(&self) -> Result<PriceLevelSnapshotPackage, PriceLevelError> {
         PriceLevelSnapshotPackage::new(self.snapshot())
     }
 
+    /// Serialize the current price level state together with its statistics.
+    pub fn snapshot_with_stats(&self) -> Result<PriceLevelSnapshotWithStats, PriceLevelError> {
+        Ok(PriceLevelSnapshotWithStats {
+            package: self.snapshot_package()?,
+            stats: self.stats.clone(),
+        })
+    }
+
     /// Serialize the current price level state to JSON, including checksum metadata.
     pub fn snapshot_to_json(&self) -> Result<String, PriceLevelError> {
         self.snapshot_package()?.to_json()
     }
 
-    /// Apply an update to an existing order at this price level
+    /// Serialize the current price level state, including checksum metadata, to bytes.
+    ///
+    /// The bytes are produced synchronously so the caller decides how and where they are
+    /// written. With an async runtime, hand them to the runtime's file API:
+    ///
+    /// ```
+    /// use pricelevel::PriceLevel;
+    ///
+    /// let level = PriceLevel::new(10000);
+    /// let bytes = level.snapshot_bytes().unwrap();
+    /// let path = std::env::temp_dir().join("pricelevel_snapshot_bytes_doc.json");
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// runtime.block_on(async { tokio::fs::write(&path, &bytes).await }).unwrap();
+    ///
+    /// let restored = PriceLevel::from_snapshot_json(&std::fs::read_to_string(&path).unwrap());
+    /// assert_eq!(restored.unwrap().price(), 10000);
+    /// # std::fs::remove_file(&path).ok();
+    /// ```
+    pub fn snapshot_bytes(&self) -> Result<Vec<u8>, PriceLevelError> {
+        let package = self.snapshot_package()?;
+        serde_json::to_vec(&package).map_err(|error| PriceLevelError::SerializationError {
+            message: error.to_string(),
+        })
+    }
+
+    /// Cancel a single order for the given reason, returning it if it was resting here.
+    ///
+    /// `update_order(OrderUpdate::Cancel { .. })` is equivalent to calling this with
+    /// [`CancelReason::UserRequested`]. The reason is attached to the cancellation metric.
+    pub fn cancel_order(&mut self, order_id: OrderId, reason: CancelReason) -> Option<Order<()>> {
+        let order = self.orders.remove(&order_id)?;
+        self.revealed_unexecuted.remove(&order_id);
+
+        self.display_quantity = self
+            .arithmetic
+            .sub(self.display_quantity, order.display_quantity());
+        self.reserve_quantity = self
+            .arithmetic
+            .sub(self.reserve_quantity, order.reserve_quantity());
+        self.order_count = self.arithmetic.sub_count(self.order_count, 1);
+
+        self.stats.record_order_removed();
+        self.stats.observe_display_quantity(self.display_quantity);
+        metrics::record_cancellation(self.price, reason);
+        self.emit(LevelEvent::OrderCancelled {
+            price: self.price,
+            order_id,
+            reason,
+        });
+
+        Some(order)
+    }
+
+    /// Cancel every order in the given one-cancels-other group, returning them in queue order.
+    ///
+    /// Called by the book for the sibling legs once a match reports the group in
+    /// [`MatchResult::triggered_oco_groups`]. The cancellations are recorded with
+    /// [`CancelReason::OcoTriggered`].
+    pub fn cancel_oco_group(&mut self, group: u64) -> Vec<Order<()>> {
+        let ids: Vec<OrderId> = self
+            .orders
+            .iter()
+            .filter(|order| order.oco_group() == Some(group))
+            .map(|order| order.id())
+            .collect();
+
+        ids.into_iter()
+            .filter_map(|order_id| self.cancel_order(order_id, CancelReason::OcoTriggered))
+            .collect()
+    }
+
+    /// Remove the pegged orders whose peg now points at a different price, returning them
+    /// repriced in queue order.
+    ///
+    /// `reference_price` supplies the current value of each [`PegReferenceType`]; an order's
+    /// target is that price plus its `reference_price_offset`. Orders whose reference is
+    /// unknown, whose target is out of range, or whose target is still this level stay where
+    /// they are. The others are removed as for an
+    /// [`OrderUpdate::UpdatePrice`] and returned with the new price and, depending on
+    /// `policy`, either their original timestamp or `now`.
+    ///
+    /// Each [`RepricedOrder`] also carries the original timestamp, so the book can order
+    /// repriced orders landing on the same level. Adding them in the returned order keeps
+    /// their relative priority under either policy.
+    pub fn reprice_pegged(
+        &mut self,
+        reference_price: impl Fn(PegReferenceType) -> Option<Price>,
+        policy: PegRepricePolicy,
+        now: Timestamp,
+    ) -> Vec<RepricedOrder> {
+        let moves: Vec<(OrderId, Price)> = self
+            .orders
+            .iter()
+            .filter_map(|order| match order {
+                Order::PeggedOrder {
+                    reference_price_offset,
+                    reference_price_type,
+                    ..
+                } => reference_price(*reference_price_type)
+                    .and_then(|reference| reference.checked_add_signed(*reference_price_offset))
+                    .filter(|target| *target != self.price)
+                    .map(|target| (order.id(), target)),
+                _ => None,
+            })
+            .collect();
+
+        let mut repriced = Vec::with_capacity(moves.len());
+        for (order_id, new_price) in moves {
+            let Ok(Some(order)) = self.update_order(OrderUpdate::UpdatePrice {
+                order_id,
+                new_price,
+            }) else {
+                continue;
+            };
+            let original_timestamp = order.timestamp();
+            let timestamp = match policy {
+                PegRepricePolicy::PreserveTimestamp => original_timestamp,
+                PegRepricePolicy::NewTimestamp => now,
+            };
+            repriced.push(RepricedOrder {
+                order: order.with_price(new_price).with_timestamp(timestamp),
+                original_timestamp,
+            });
+        }
+        if !repriced.is_empty() {
+            self.stats.observe_display_quantity(self.display_quantity);
+        }
+        repriced
+    }
+
+    /// Flag orders at this level that look like wash trading: makers of `recent_txns` at this
+    /// price whose taker has the same owner, followed by the resting orders of those owners.
+    ///
+    /// Neither orders nor transactions carry an owner, so the caller supplies `owner_of`,
+    /// typically a lookup into the `OrderMetadata` (`user_id`) it keeps per order ID; orders
+    /// without a known owner are never flagged. This is a surveillance heuristic, not a
+    /// finding: same-owner executions can be legitimate, and wash trades routed through
+    /// different accounts go unnoticed. Each ID appears once, in the order it is first seen.
+    pub fn potential_wash(
+        &self,
+        recent_txns: &[Transaction],
+        owner_of: impl Fn(OrderId) -> Option<u64>,
+    ) -> Vec<OrderId> {
+        let mut flagged: Vec<OrderId> = Vec::new();
+        let mut owners: Vec<u64> = Vec::new();
+        for transaction in recent_txns.iter().filter(|t| t.price == self.price) {
+            let Some(owner) = owner_of(transaction.maker_order_id) else {
+                continue;
+            };
+            if owner_of(transaction.taker_order_id) != Some(owner) {
+                continue;
+            }
+            if !flagged.contains(&transaction.maker_order_id) {
+                flagged.push(transaction.maker_order_id);
+            }
+            if !owners.contains(&owner) {
+                owners.push(owner);
+            }
+        }
+
+        for order in self.orders.iter() {
+            let resting_owner = owner_of(order.id()).is_some_and(|owner| owners.contains(&owner));
+            if resting_owner && !flagged.contains(&order.id()) {
+                flagged.push(order.id());
+            }
+        }
+        flagged
+    }
+
+    /// Merge all standard orders of one owner into a single order, returning the merged
+    /// order, or `None` if the owner has no standard orders here.
+    ///
+    /// Resting orders carry no owner, so the caller supplies `owner_of`, typically a lookup
+    /// into the `OrderMetadata` it keeps per order ID. The
+    /// owner's order with the earliest timestamp keeps its ID and queue position (or is
+    /// re-sorted by the level's comparator) and takes the summed display quantity; the others
+    /// are canceled with [`CancelReason::Replaced`]. The level's display quantity is
+    /// unchanged.
+    pub fn coalesce_owner(
+        &mut self,
+        owner_id: u64,
+        owner_of: impl Fn(&Order<()>) -> Option<u64>,
+    ) -> Option<Order<()>> {
+        let owned: Vec<Order<()>> = self
+            .orders
+            .iter()
+            .filter(|order| matches!(order, Order::Standard { .. }))
+            .filter(|order| owner_of(order) == Some(owner_id))
+            .copied()
+            .collect();
+        let survivor = owned.iter().min_by_key(|order| order.timestamp())?.id();
+
+        let mut merged: Quantity = 0;
+        for order in &owned {
+            if order.id() != survivor {
+                self.cancel_order(order.id(), CancelReason::Replaced);
+                merged += order.display_quantity();
+            }
+        }
+
+        let order = self.orders.find(&survivor)?;
+        let order = order.with_reduced_quantity(order.display_quantity() + merged);
+        self.replace_in_place(order);
+        self.display_quantity = self.arithmetic.add(self.display_quantity, merged);
+        self.stats.observe_display_quantity(self.display_quantity);
+        self.emit(LevelEvent::OrderUpdated {
+            price: self.price,
+            order,
+        });
+        Some(order)
+    }
+
+    /// Cancel every order resting at this level for the given reason, returning how many were
+    /// removed.
+    pub fn cancel_all(&mut self, reason: CancelReason) -> usize {
+        let cancelled = self.order_count;
+        for _ in 0..cancelled {
+            self.stats.record_order_removed();
+            metrics::record_cancellation(self.price, reason);
+        }
+        if self.event_sender.is_some() {
+            for order in self.orders.iter() {
+                self.emit(LevelEvent::OrderCancelled {
+                    price: self.price,
+                    order_id: order.id(),
+                    reason,
+                });
+            }
+        }
+
+        self.orders.clear(true);
+        self.revealed_unexecuted.clear();
+        self.display_quantity = 0;
+        self.reserve_quantity = 0;
+        self.order_count = 0;
+        self.stats.observe_display_quantity(0);
+        cancelled
+    }
+
+    /// Remove the day orders entered before `session_end_ts`, returning them in queue order.
+    ///
+    /// A [`TimeInForce::Day`] order with an earlier timestamp belongs to a session that has
+    /// ended. Each one is canceled with [`CancelReason::Expired`], so its display and
+    /// reserve quantities leave the level aggregates, the removal is recorded in the
+    /// statistics and an event is published. Matching never checks this itself; call it at
+    /// the session boundary.
+    pub fn expire_day_orders(&mut self, session_end_ts: Timestamp) -> Vec<Order<()>> {
+        let expired: Vec<OrderId> = self
+            .orders
+            .iter()
+            .filter(|order| {
+                order.time_in_force() == TimeInForce::Day && order.timestamp() < session_end_ts
+            })
+            .map(|order| order.id())
+            .collect();
+
+        expired
+            .into_iter()
+            .filter_map(|order_id| self.cancel_order(order_id, CancelReason::Expired))
+            .collect()
+    }
+
+    /// Remove the good-till-date orders whose expiry is at or before `now_ms`, returning them
+    /// in queue order.
+    ///
+    /// Like [`expire_day_orders`](Self::expire_day_orders), each order is canceled with
+    /// [`CancelReason::Expired`], updating the aggregates and statistics.
+    pub fn purge_expired(&mut self, now_ms: Timestamp) -> Vec<Order<()>> {
+        let expired: Vec<OrderId> = self
+            .orders
+            .iter()
+            .filter(|order| is_gtd_expired(order, now_ms))
+            .map(|order| order.id())
+            .collect();
+
+        expired
+            .into_iter()
+            .filter_map(|order_id| self.cancel_order(order_id, CancelReason::Expired))
+            .collect()
+    }
+
+    /// Apply an update to an existing order at this price level.
+    ///
+    /// Queue priority is handled per variant:
+    ///
+    /// - [`OrderUpdate::UpdateQuantity`] keeps the order's position when the quantity
+    ///   decreases or stays the same and moves it to the back when it increases. On a level
+    ///   built [`with_comparator`](Self::with_comparator) the order is re-sorted instead.
+    /// - [`OrderUpdate::UpdatePriceAndQuantity`] and [`OrderUpdate::Replace`] at this
+    ///   level's price follow the `UpdateQuantity` rules.
+    /// - A price change, through any of these or [`OrderUpdate::UpdatePrice`], removes the
+    ///   order and returns it for the caller to route to its new level.
+    /// - For [`OrderUpdate::ReplaceFull`], a replacement priced at this level takes the old
+    ///   order's place, keeping its queue position if `preserve_priority` is set and going to
+    ///   the back otherwise, and the replacement is returned as it now rests, normalized like
+    ///   an [added](Self::add_order) order. On a level with a comparator it is re-sorted
+    ///   either way. A replacement priced elsewhere removes the old order and is returned for
+    ///   the caller to route to its level. A replacement on the other side is rejected with
+    ///   [`PriceLevelError::InvalidOperation`].
     pub fn update_order(
         &mut self,
         update: OrderUpdate,
     ) -> Result<Option<Order<()>>, PriceLevelError> {
+        let result = self.apply_update(update);
+        self.stats.observe_display_quantity(self.display_quantity);
+        result
+    }
+
+    /// Compute the aggregate totals the level would have after
+    /// [`update_order`](Self::update_order) applied `update`, without modifying it.
+    ///
+    /// An update that moves the order to another price counts as its removal, and an update
+    /// of an order that is not resting here leaves the totals unchanged, as it would in
+    /// `update_order`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`PriceLevelError::InvalidOperation`] that `update_order` would return.
+    pub fn preview_update(
+        &self,
+        update: &OrderUpdate,
+    ) -> Result<PriceLevelAggregate, PriceLevelError> {
+        let order_id = match update {
+            OrderUpdate::UpdatePrice {
+                order_id,
+                new_price,
+            } => {
+                if *new_price == self.price {
+                    return Err(PriceLevelError::InvalidOperation {
+                        message: "Cannot update price to the same value".to_string(),
+                    });
+                }
+                *order_id
+            }
+            OrderUpdate::ReplaceFull {
+                order_id,
+                new_order,
+                ..
+            } => {
+                if new_order.id() != *order_id {
+                    return Err(PriceLevelError::InvalidOperation {
+                        message: format!(
+                            "Replacement order {} does not match order {order_id}",
+                            new_order.id()
+                        ),
+                    });
+                }
+                if let Some(resting) = self.orders.find(order_id)
+                    && new_order.side() != resting.side()
+                {
+                    return Err(PriceLevelError::InvalidOperation {
+                        message: format!(
+                            "Replacement order {order_id} cannot change side from {} to {}",
+                            resting.side(),
+                            new_order.side()
+                        ),
+                    });
+                }
+                *order_id
+            }
+            OrderUpdate::UpdateQuantity { order_id, .. }
+            | OrderUpdate::UpdatePriceAndQuantity { order_id, .. }
+            | OrderUpdate::Cancel { order_id }
+            | OrderUpdate::Replace { order_id, .. } => *order_id,
+        };
+
+        let mut aggregate = self.aggregate();
+        let Some(old_order) = self.orders.find(&order_id) else {
+            return Ok(aggregate);
+        };
+
+        // The order as it would rest here afterwards, or `None` if it leaves the level
+        let new_order = match update {
+            OrderUpdate::UpdateQuantity { new_quantity, .. } => {
+                Some(old_order.with_reduced_quantity(*new_quantity))
+            }
+            OrderUpdate::UpdatePriceAndQuantity {
+                new_price: price,
+                new_quantity: quantity,
+                ..
+            }
+            | OrderUpdate::Replace {
+                price, quantity, ..
+            } if *price == self.price => Some(old_order.with_reduced_quantity(*quantity)),
+            OrderUpdate::ReplaceFull { new_order, .. } if new_order.price() == self.price => {
+                Some(reveal_initial_slice(**new_order))
+            }
+            _ => None,
+        };
+
+        aggregate.display_quantity = self
+            .arithmetic
+            .sub(aggregate.display_quantity, old_order.display_quantity());
+        aggregate.reserve_quantity = self
+            .arithmetic
+            .sub(aggregate.reserve_quantity, old_order.reserve_quantity());
+        match new_order {
+            Some(new_order) => {
+                aggregate.display_quantity = self
+                    .arithmetic
+                    .add(aggregate.display_quantity, new_order.display_quantity());
+                aggregate.reserve_quantity = self
+                    .arithmetic
+                    .add(aggregate.reserve_quantity, new_order.reserve_quantity());
+            }
+            None => aggregate.order_count = self.arithmetic.sub_count(aggregate.order_count, 1),
+        }
+        Ok(aggregate)
+    }
+
+    /// Remove an order that is repriced to `new_price`, returning it as it rested here.
+    fn remove_moved(&mut self, order_id: OrderId, new_price: Price) -> Option<Order<()>> {
+        let order = self.orders.remove(&order_id)?;
+        self.revealed_unexecuted.remove(&order_id);
+
+        self.display_quantity = self
+            .arithmetic
+            .sub(self.display_quantity, order.display_quantity());
+        self.reserve_quantity = self
+            .arithmetic
+            .sub(self.reserve_quantity, order.reserve_quantity());
+        self.order_count = self.arithmetic.sub_count(self.order_count, 1);
+
+        self.stats.record_order_removed();
+        self.emit(LevelEvent::OrderMoved {
+            price: self.price,
+            order_id,
+            new_price,
+        });
+        Some(order)
+    }
+
+    fn apply_update(&mut self, update: OrderUpdate) -> Result<Option<Order<()>>, PriceLevelError> {
         match update {
             OrderUpdate::UpdatePrice {
                 order_id,
@@ -270,19 +1949,7 @@ impl PriceLevel {
                 // If price changes, this order needs to be moved to a different price level
                 // So we remove it from this level and return it for re-insertion elsewhere
                 if new_price != self.price {
-                    let Some(order) = self.orders.remove(&order_id) else {
-                        return Ok(None);
-                    };
-
-                    let old_visible = order.display_quantity();
-                    let old_hidden = order.reserve_quantity();
-                    self.display_quantity -= old_visible;
-                    self.reserve_quantity -= old_hidden;
-                    self.order_count -= 1;
-
-                    self.stats.record_order_removed();
-
-                    Ok(Some(order))
+                    Ok(self.remove_moved(order_id, new_price))
                 } else {
                     // If price is the same, this is a no-op at the price level
                     // (Should be handled at the order book level)
@@ -296,8 +1963,7 @@ impl PriceLevel {
                 order_id,
                 new_quantity,
             } => {
-                // Remove the old order
-                let Some(old_order) = self.orders.remove(&order_id) else {
+                let Some(old_order) = self.orders.find(&order_id).copied() else {
                     return Ok(None); // Order not found, remove by other thread
                 };
 
@@ -308,31 +1974,51 @@ impl PriceLevel {
                 // Create updated order with new quantity
                 let new_order = old_order.with_reduced_quantity(new_quantity);
 
+                // A decrease keeps the order's place and an increase loses it
+                if new_quantity <= old_visible {
+                    self.replace_in_place(new_order);
+                } else {
+                    self.orders.remove(&order_id);
+                    self.orders.push(new_order);
+                }
+
                 // Calculate the new quantities
                 let new_visible = new_order.display_quantity();
                 let new_hidden = new_order.reserve_quantity();
+                if let Some(revealed) = self.revealed_unexecuted.get_mut(&order_id) {
+                    *revealed = (*revealed).min(new_visible);
+                }
 
                 // Update atomic counters
                 if old_visible != new_visible {
                     if new_visible > old_visible {
-                        self.display_quantity += new_visible - old_visible;
+                        self.display_quantity = self
+                            .arithmetic
+                            .add(self.display_quantity, new_visible - old_visible);
                     } else {
-                        self.display_quantity -= old_visible - new_visible;
+                        self.display_quantity = self
+                            .arithmetic
+                            .sub(self.display_quantity, old_visible - new_visible);
                     }
                 }
 
                 if old_hidden != new_hidden {
                     if new_hidden > old_hidden {
-                        self.reserve_quantity += new_hidden - old_hidden;
+                        self.reserve_quantity = self
+                            .arithmetic
+                            .add(self.reserve_quantity, new_hidden - old_hidden);
                     } else {
-                        self.reserve_quantity -= old_hidden - new_hidden;
+                        self.reserve_quantity = self
+                            .arithmetic
+                            .sub(self.reserve_quantity, old_hidden - new_hidden);
                     }
                 }
+                self.emit(LevelEvent::OrderUpdated {
+                    price: self.price,
+                    order: new_order,
+                });
 
-                // Add the updated order back to the queue
-                let new_order_ref = self.orders.push(new_order);
-
-                return Ok(Some(*new_order_ref));
+                Ok(Some(new_order))
             }
 
             OrderUpdate::UpdatePriceAndQuantity {
@@ -342,44 +2028,19 @@ impl PriceLevel {
             } => {
                 // If price changes, remove the order and let the order book handle re-insertion
                 if new_price == self.price {
-                    // If price is the same, just update the quantity (reuse logic)
+                    // If price is the same, just update the quantity, with the same priority
+                    // rules
                     return self.update_order(OrderUpdate::UpdateQuantity {
                         order_id,
                         new_quantity,
                     });
                 };
 
-                let Some(order) = self.orders.remove(&order_id) else {
-                    return Ok(None);
-                };
-
-                let visible_qty = order.display_quantity();
-                let hidden_qty = order.reserve_quantity();
-
-                self.display_quantity -= visible_qty;
-                self.reserve_quantity -= hidden_qty;
-                self.order_count -= 1;
-
-                self.stats.record_order_removed();
-
-                Ok(Some(order))
-            }
+                Ok(self.remove_moved(order_id, new_price))
+            }
 
             OrderUpdate::Cancel { order_id } => {
-                // Remove the order
-                let Some(order) = self.orders.remove(&order_id) else {
-                    return Ok(None);
-                };
-
-                let old_visible = order.display_quantity();
-                let old_hidden = order.reserve_quantity();
-                self.display_quantity -= old_visible;
-                self.reserve_quantity -= old_hidden;
-                self.order_count -= 1;
-
-                self.stats.record_order_removed();
-
-                Ok(Some(order))
+                Ok(self.cancel_order(order_id, CancelReason::UserRequested))
             }
 
             OrderUpdate::Replace {
@@ -397,33 +2058,473 @@ impl PriceLevel {
                     });
                 };
 
-                let Some(order) = self.orders.remove(&order_id) else {
+                Ok(self.remove_moved(order_id, price))
+            }
+
+            OrderUpdate::ReplaceFull {
+                order_id,
+                new_order,
+                preserve_priority,
+            } => {
+                if new_order.id() != order_id {
+                    return Err(PriceLevelError::InvalidOperation {
+                        message: format!(
+                            "Replacement order {} does not match order {order_id}",
+                            new_order.id()
+                        ),
+                    });
+                }
+                let Some(resting) = self.orders.find(&order_id) else {
                     return Ok(None);
                 };
+                if new_order.side() != resting.side() {
+                    return Err(PriceLevelError::InvalidOperation {
+                        message: format!(
+                            "Replacement order {order_id} cannot change side from {} to {}",
+                            resting.side(),
+                            new_order.side()
+                        ),
+                    });
+                }
+
+                // A different price moves the order to another level, like UpdatePrice
+                if new_order.price() != self.price {
+                    return Ok(self
+                        .remove_moved(order_id, new_order.price())
+                        .map(|_| *new_order));
+                }
+
+                // The replacement rests as add_order would have queued it
+                let new_order = reveal_initial_slice(*new_order);
+                let old_order = if preserve_priority {
+                    let Some(old_order) = self.replace_in_place(new_order) else {
+                        return Ok(None);
+                    };
+                    old_order
+                } else {
+                    let Some(old_order) = self.orders.remove(&order_id) else {
+                        return Ok(None);
+                    };
+                    self.orders.push(new_order);
+                    old_order
+                };
+                self.revealed_unexecuted.remove(&order_id);
+
+                self.display_quantity = self.arithmetic.add(
+                    self.arithmetic
+                        .sub(self.display_quantity, old_order.display_quantity()),
+                    new_order.display_quantity(),
+                );
+                self.reserve_quantity = self.arithmetic.add(
+                    self.arithmetic
+                        .sub(self.reserve_quantity, old_order.reserve_quantity()),
+                    new_order.reserve_quantity(),
+                );
+                self.emit(LevelEvent::OrderUpdated {
+                    price: self.price,
+                    order: new_order,
+                });
+
+                Ok(Some(new_order))
+            }
+        }
+    }
+}
+
+/// Move a resting order from one price level to another.
+///
+/// The order is removed from `from`, repriced to `to`'s price, optionally resized to
+/// `new_quantity`, and appended to the back of `to`'s queue. Both levels' aggregates and
+/// statistics are updated as for a removal and an addition. Returns the order as it now rests
+/// in `to`.
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::InvalidOperation`] if both levels have the same price, if
+/// `new_quantity` is zero or if the order is not found in `from`, and the error of
+/// [`PriceLevel::add_order`] if the moved order breaks one of `to`'s limits; neither level is
+/// modified in those cases.
+pub fn move_order(
+    from: &mut PriceLevel,
+    to: &mut PriceLevel,
+    order_id: OrderId,
+    new_quantity: Option<Quantity>,
+) -> Result<Order<()>, PriceLevelError> {
+    if from.price == to.price {
+        return Err(PriceLevelError::InvalidOperation {
+            message: format!("Cannot move order {order_id} to a level with the same price"),
+        });
+    }
+    if new_quantity == Some(0) {
+        return Err(PriceLevelError::InvalidOperation {
+            message: format!("Cannot move order {order_id} with a zero quantity"),
+        });
+    }
+
+    let Some(order) = from.orders.find(&order_id) else {
+        return Err(PriceLevelError::InvalidOperation {
+            message: format!("Order {order_id} not found at price {}", from.price),
+        });
+    };
+
+    let mut moved = order.with_price(to.price);
+    if let Some(quantity) = new_quantity {
+        moved = moved.with_reduced_quantity(quantity);
+    }
+    to.check_limits(&moved)?;
+
+    from.update_order(OrderUpdate::UpdatePrice {
+        order_id,
+        new_price: to.price,
+    })?;
+    Ok(*to.insert_order(moved))
+}
+
+/// Take the reserve revealed into `order`'s display that a fill of `consumed` executes, as
+/// tracked in `revealed`. `order` is the maker as it rested before the fill; quantity it
+/// displayed before the reveal executes first.
+fn take_revealed(
+    revealed: &mut HashMap<OrderId, Quantity>,
+    order: &Order<()>,
+    consumed: Quantity,
+) -> Quantity {
+    let Some(pending) = revealed.get_mut(&order.id()) else {
+        return 0;
+    };
+    let pending_now = (*pending).min(order.display_quantity());
+    let executed = consumed.saturating_sub(order.display_quantity() - pending_now);
+    *pending = pending_now - executed;
+    if *pending == 0 {
+        revealed.remove(&order.id());
+    }
+    executed
+}
+
+/// Record `quantity` revealed from `order_id`'s reserve into its display in `revealed`
+fn track_revealed(
+    revealed: &mut HashMap<OrderId, Quantity>,
+    order_id: OrderId,
+    quantity: Quantity,
+) {
+    let pending = revealed.entry(order_id).or_default();
+    *pending = pending.saturating_add(quantity);
+}
+
+/// One maker's part in a match, as reported by [`run_match`]
+#[derive(Clone, Copy)]
+struct MakerFill {
+    /// The maker as it rested before this fill
+    order: Order<()>,
+    /// The quantity executed against it
+    consumed: Quantity,
+    /// The maker as it goes back into the queue, or `None` if it left the level
+    updated: Option<Order<()>>,
+    /// The quantity revealed from its reserve
+    hidden_reduced: Quantity,
+}
+
+/// Match up to `incoming_quantity` against `orders` in priority order, reporting every maker
+/// touched to `on_fill` and returning the unfilled quantity.
+///
+/// This is the matching loop shared by the live match and every dry run. Makers are popped
+/// from the head; a partially filled or refreshed maker is pushed back, as `on_fill` left
+/// it, so it takes the position the queue gives it. Orders for which `skip` is true, and orders with nothing
+/// displayed that would be requeued forever, keep their place at the head. `max_per_maker`
+/// caps what one maker is offered per pass.
+fn run_match(
+    orders: &mut OrderQueue,
+    incoming_quantity: Quantity,
+    skip: impl Fn(&Order<()>) -> bool,
+    max_per_maker: Option<Quantity>,
+    mut on_fill: impl FnMut(&mut MakerFill),
+) -> Quantity {
+    let mut remaining = incoming_quantity;
+    let mut skipped = Vec::new();
+
+    while remaining > 0 {
+        let Some(order) = orders.pop() else {
+            break;
+        };
+
+        // Skipped orders keep their queue position
+        if skip(&order) {
+            skipped.push(order);
+            continue;
+        }
+
+        // Offer the maker no more than the cap; what it leaves of the offer is added back
+        let offered = max_per_maker.map_or(remaining, |cap| remaining.min(cap));
+        let (consumed, updated, hidden_reduced, unused) = order.match_against(offered);
+
+        // An order with nothing displayed cannot trade and would be requeued forever; leave
+        // it in place.
+        if consumed == 0 && updated.is_some_and(|o| o.display_quantity() == 0) {
+            skipped.push(order);
+            continue;
+        }
+
+        remaining = remaining - offered + unused;
+        let mut fill = MakerFill {
+            order,
+            consumed,
+            updated,
+            hidden_reduced,
+        };
+        on_fill(&mut fill);
+        if let Some(updated) = fill.updated {
+            orders.push(updated);
+        }
+    }
+
+    // Skipped orders were ahead of everything still queued, so they go back to the front
+    for order in skipped.into_iter().rev() {
+        orders.push_front(order);
+    }
+    remaining
+}
+
+/// Reveal a first display slice for an iceberg or reserve order entered with nothing
+/// displayed, and record an iceberg's entered display, as described on
+/// [`PriceLevel::add_order`]. Other orders are returned unchanged.
+fn reveal_initial_slice(order: Order<()>) -> Order<()> {
+    match order {
+        Order::IcebergOrder {
+            common,
+            reserve_quantity,
+            ..
+        } => {
+            let mut order = if common.display_quantity == 0 && reserve_quantity > 0 {
+                order
+                    .refresh_iceberg(DEFAULT_RESERVE_REPLENISH_AMOUNT.min(reserve_quantity))
+                    .0
+            } else {
+                order
+            };
+            if let Order::IcebergOrder {
+                common,
+                original_display: original_display @ 0,
+                ..
+            } = &mut order
+            {
+                *original_display = common.display_quantity;
+            }
+            order
+        }
+        Order::ReserveOrder {
+            common,
+            replenish_amount,
+            ..
+        } if common.display_quantity == 0 && order.revealable_reserve() > 0 => {
+            let slice = replenish_amount
+                .unwrap_or(DEFAULT_RESERVE_REPLENISH_AMOUNT)
+                .clamp(1, order.revealable_reserve());
+            order.refresh_iceberg(slice).0
+        }
+        _ => order,
+    }
+}
 
-                let old_visible = order.display_quantity();
-                let old_hidden = order.reserve_quantity();
-                self.display_quantity -= old_visible;
-                self.reserve_quantity -= old_hidden;
-                self.order_count -= 1;
+/// Whether `order` is good-till-date with an expiry at or before `now_ms`
+fn is_gtd_expired(order: &Order<()>, now_ms: Timestamp) -> bool {
+    matches!(order.time_in_force(), TimeInForce::Gtd(expiry) if expiry <= now_ms)
+}
+
+/// Get the volume-weighted average price of taking `quantity` from `tranches` of
+/// `(price, available quantity)` in priority order, truncated to a whole price. Returns
+/// `None` if the tranches hold less than `quantity`; a zero quantity prices at the first
+/// tranche.
+fn volume_weighted_price(tranches: &[(Price, Quantity)], quantity: Quantity) -> Option<Price> {
+    if quantity == 0 {
+        return tranches.first().map(|&(price, _)| price);
+    }
+    let mut remaining = quantity;
+    let mut value: u128 = 0;
+    for &(price, available) in tranches {
+        let taken = remaining.min(available);
+        value += taken as u128 * price as u128;
+        remaining -= taken;
+        if remaining == 0 {
+            return Some((value / quantity as u128) as Price);
+        }
+    }
+    None
+}
+
+/// Sum the [index contributions](PriceLevel::index_contribution) of `levels`, each scaled by
+/// the same `weight`.
+pub fn sum_contributions(levels: &[PriceLevel], weight: f64) -> f64 {
+    levels
+        .iter()
+        .map(|level| level.index_contribution(weight))
+        .sum()
+}
 
-                self.stats.record_order_removed();
+/// Get the quantity that would trade between a bid level and an ask level if they cross.
+///
+/// Levels cross when `bid.price() >= ask.price()`, including when they are locked at the same
+/// price; the result is then the smaller of their [fillable
+/// quantities](PriceLevel::fillable_quantity). Otherwise it is 0. Neither level is modified.
+pub fn crossed_quantity(bid: &PriceLevel, ask: &PriceLevel) -> Quantity {
+    if bid.price < ask.price {
+        return 0;
+    }
+    bid.fillable_quantity().min(ask.fillable_quantity())
+}
+
+/// Apply a captured [`MatchResult`] to a level without re-running the matching logic.
+///
+/// `level` must be in the state the match started from, e.g. restored from a `before`
+/// snapshot. Each transaction is replayed against its maker in order: the executed quantity
+/// leaves the display, any recorded [`replenishments`](MatchResult::replenishments) entry for
+/// that maker is revealed from its reserve, and the maker is then requeued where the level's
+/// priority puts it, at the back unless a comparator orders the queue, or, once its display
+/// is exhausted, leaves the level together with any remaining reserve.
+/// This reproduces the aggregates, queue order and removed orders of the original match.
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::InvalidOperation`] if the result does not fit the level: an
+/// unknown maker, an execution larger than the maker's display, a replenishment that is
+/// out of sequence or exceeds the reserve, or removed orders that disagree with
+/// `filled_order_ids`. The level is not modified in that case.
+pub fn apply_match_result(
+    level: &mut PriceLevel,
+    result: &MatchResult,
+) -> Result<(), PriceLevelError> {
+    let invalid = |message: String| PriceLevelError::InvalidOperation { message };
+
+    let arithmetic = level.arithmetic;
+    let mut orders = level.orders.to_vec();
+    let mut replenishments = result.replenishments.iter().peekable();
+    let mut removed = Vec::new();
+    let mut display_quantity = level.display_quantity;
+    let mut reserve_quantity = level.reserve_quantity;
+    let mut revealed_unexecuted = level.revealed_unexecuted.clone();
+    let mut hidden_executed: Quantity = 0;
+
+    for transaction in result.transactions.as_vec() {
+        let maker_id = transaction.maker_order_id;
+        let position = orders
+            .iter()
+            .position(|order| order.id() == maker_id)
+            .ok_or_else(|| invalid(format!("Maker {maker_id} is not resting at the level")))?;
+        let original = orders.remove(position);
+
+        let Some(display) = original
+            .display_quantity()
+            .checked_sub(transaction.quantity)
+        else {
+            return Err(invalid(format!(
+                "Maker {maker_id} cannot execute {} with {} displayed",
+                transaction.quantity,
+                original.display_quantity()
+            )));
+        };
+        let mut order = original.with_reduced_quantity(display);
+        display_quantity = arithmetic.sub(display_quantity, transaction.quantity);
+        hidden_executed = hidden_executed.saturating_add(take_revealed(
+            &mut revealed_unexecuted,
+            &original,
+            transaction.quantity,
+        ));
 
-                Ok(Some(order))
+        if let Some((_, revealed)) = replenishments.next_if(|(id, _)| *id == maker_id) {
+            match &mut order {
+                Order::IcebergOrder {
+                    common,
+                    reserve_quantity: reserve,
+                    ..
+                }
+                | Order::ReserveOrder {
+                    common,
+                    reserve_quantity: reserve,
+                    ..
+                } if *reserve >= *revealed => {
+                    common.display_quantity += revealed;
+                    *reserve -= revealed;
+                    match &mut order {
+                        Order::ReserveOrder {
+                            revealed_quantity, ..
+                        } => *revealed_quantity += revealed,
+                        Order::IcebergOrder { last_reveal_ms, .. } => {
+                            *last_reveal_ms = Some(transaction.timestamp);
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "Maker {maker_id} cannot reveal {revealed} from its reserve"
+                    )));
+                }
             }
+            display_quantity = arithmetic.add(display_quantity, *revealed);
+            reserve_quantity = arithmetic.sub(reserve_quantity, *revealed);
+            track_revealed(&mut revealed_unexecuted, maker_id, *revealed);
+        }
+
+        if order.display_quantity() == 0 {
+            reserve_quantity = arithmetic.sub(reserve_quantity, order.reserve_quantity());
+            revealed_unexecuted.remove(&maker_id);
+            removed.push(original);
+        } else {
+            orders.push(order);
         }
     }
+
+    if let Some((order_id, _)) = replenishments.next() {
+        return Err(invalid(format!(
+            "Replenishment of {order_id} does not follow one of its executions"
+        )));
+    }
+    let removed_ids: Vec<OrderId> = removed.iter().map(|order| order.id()).collect();
+    if removed_ids != result.filled_order_ids {
+        return Err(invalid(format!(
+            "Replay removed {} orders but the result lists {} as filled",
+            removed_ids.len(),
+            result.filled_order_ids.len()
+        )));
+    }
+
+    for transaction in result.transactions.as_vec() {
+        let arrival = orders
+            .iter()
+            .chain(&removed)
+            .find(|order| order.id() == transaction.maker_order_id)
+            .map_or(transaction.timestamp, |order| order.timestamp());
+        level.stats.record_execution(
+            transaction.quantity,
+            transaction.price,
+            transaction.timestamp.saturating_sub(arrival),
+            transaction.taker_side,
+        );
+    }
+    if hidden_executed > 0 {
+        level.stats.record_hidden_execution(hidden_executed);
+    }
+    level.revealed_unexecuted = revealed_unexecuted;
+
+    level.orders.clear(true);
+    for order in orders {
+        level.orders.push(order);
+    }
+    level.display_quantity = display_quantity;
+    level.reserve_quantity = reserve_quantity;
+    level.order_count = arithmetic.sub_count(level.order_count, removed.len());
+    level.last_removed = removed;
+    level.stats.observe_display_quantity(display_quantity);
+    Ok(())
 }
 
 /// Serializable representation of a price level for easier data transfer and storage
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PriceLevelData {
     /// The price of this level
-    pub price: u64,
+    pub price: Price,
     /// Total display quantity at this price level
-    pub display_quantity: u64,
+    pub display_quantity: Quantity,
     /// Total reserve quantity at this price level
-    pub reserve_quantity: u64,
+    pub reserve_quantity: Quantity,
     /// Number of orders at this price level
     pub order_count: usize,
     /// Orders at this price level
@@ -445,6 +2546,9 @@ impl From<&PriceLevel> for PriceLevelData {
 impl From<&PriceLevelSnapshot> for PriceLevel {
     fn from(snapshot: &PriceLevelSnapshot) -> Self {
         let mut snapshot = snapshot.clone();
+        for order in &mut snapshot.orders {
+            *order = reveal_initial_slice(*order);
+        }
         snapshot.refresh_aggregates();
 
         let orders = OrderQueue::from(snapshot.orders);
@@ -457,6 +2561,14 @@ impl From<&PriceLevelSnapshot> for PriceLevel {
             order_count,
             orders,
             stats: PriceLevelStatistics::new(),
+            last_removed: Vec::new(),
+            revealed_unexecuted: HashMap::new(),
+            lot_size: 1,
+            price_band: None,
+            max_order_notional: None,
+            event_sender: None,
+            check_on_drop: true,
+            arithmetic: ArithmeticPolicy::default(),
         }
     }
 }
@@ -469,7 +2581,7 @@ impl TryFrom<PriceLevelData> for PriceLevel {
 
         // Add orders to the price level
         for order in data.orders {
-            price_level.add_order(order);
+            price_level.add_order(order)?;
         }
 
         Ok(price_level)
@@ -509,7 +2621,10 @@ impl FromStr for PriceLevel {
                 content[orders_start..]
                     .find(']')
                     .ok_or_else(|| PriceLevelError::ParseError {
-                        message: "Invalid format: unclosed orders bracket".to_string(),
+                        message: format!(
+                            "Invalid format: unclosed orders bracket in '{}'",
+                            &content[orders_start..]
+                        ),
                     })?
                     + orders_start;
 
@@ -524,17 +2639,22 @@ impl FromStr for PriceLevel {
         }
 
         for part in remaining_content.split(';').filter(|s| !s.is_empty()) {
-            let mut iter = part.splitn(2, '=');
-            if let (Some(key), Some(value)) = (iter.next(), iter.next()) {
-                parts.insert(key, value);
-            }
+            let Some((key, value)) = part.split_once('=') else {
+                return Err(PriceLevelError::ParseError {
+                    message: format!("Invalid format: expected key=value, got '{part}'"),
+                });
+            };
+            parts.insert(key, value);
         }
 
         let price = parts
             .get("price")
-            .and_then(|v| v.parse::<u64>().ok())
-            .ok_or_else(|| PriceLevelError::ParseError {
-                message: "Missing or invalid price".to_string(),
+            .ok_or_else(|| PriceLevelError::MissingField("price".to_string()))?;
+        let price = price
+            .parse::<u64>()
+            .map_err(|_| PriceLevelError::InvalidFieldValue {
+                field: "price".to_string(),
+                value: price.to_string(),
             })?;
 
         let mut price_level = PriceLevel::new(price);
@@ -542,22 +2662,33 @@ impl FromStr for PriceLevel {
         if let Some(orders_part) = parts.get("orders")
             && !orders_part.is_empty()
         {
+            let parse_order = |order_index: usize, input: &str| {
+                Order::<()>::from_str(input).map_err(|e| PriceLevelError::OrderParseError {
+                    order_index,
+                    input: input.to_string(),
+                    message: e.to_string(),
+                })
+            };
             let mut bracket_level = 0;
             let mut last_split = 0;
+            let mut order_index = 0;
 
             for (i, c) in orders_part.char_indices() {
                 match c {
                     '(' | '[' => bracket_level += 1,
+                    ')' | ']' if bracket_level == 0 => {
+                        return Err(PriceLevelError::OrderParseError {
+                            order_index,
+                            input: orders_part[last_split..].to_string(),
+                            message: format!("unbalanced '{c}'"),
+                        });
+                    }
                     ')' | ']' => bracket_level -= 1,
                     ',' if bracket_level == 0 => {
-                        let order_str = &orders_part[last_split..i];
-                        let order = Order::<()>::from_str(order_str).map_err(|e| {
-                            PriceLevelError::ParseError {
-                                message: format!("Order parse error: {e}"),
-                            }
-                        })?;
-                        price_level.add_order(order);
+                        let order = parse_order(order_index, &orders_part[last_split..i])?;
+                        price_level.add_order(order)?;
                         last_split = i + 1;
+                        order_index += 1;
                     }
                     _ => {}
                 }
@@ -565,11 +2696,7 @@ impl FromStr for PriceLevel {
 
             let order_str = &orders_part[last_split..];
             if !order_str.is_empty() {
-                let order =
-                    Order::<()>::from_str(order_str).map_err(|e| PriceLevelError::ParseError {
-                        message: format!("Order parse error: {e}"),
-                    })?;
-                price_level.add_order(order);
+                price_level.add_order(parse_order(order_index, order_str)?)?;
             }
         }
 
@@ -590,6 +2717,23 @@ impl<'de> Deserialize<'de> for PriceLevel {
     }
 }
 
+impl Drop for PriceLevel {
+    fn drop(&mut self) {
+        // Skipped while unwinding so a failing test reports its own panic, not an abort
+        if cfg!(debug_assertions) && self.check_on_drop && !std::thread::panicking() {
+            assert!(
+                self.aggregates_match_queue(),
+                "price level {} dropped with aggregates display={} reserve={} count={} that do not match its {} orders",
+                self.price,
+                self.display_quantity,
+                self.reserve_quantity,
+                self.order_count,
+                self.orders.len()
+            );
+        }
+    }
+}
+
 impl PartialEq for PriceLevel {
     fn eq(&self, other: &Self) -> bool {
         self.price == other.price
@@ -604,6 +2748,10 @@ impl PartialOrd for PriceLevel {
     }
 }
 
+/// Orders levels by ascending price regardless of book side.
+///
+/// This is only the natural ordering for the ask side; for bids, where a higher price has
+/// higher priority, use [`PriceLevel::cmp_for_side`] instead.
 impl Ord for PriceLevel {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.price.cmp(&other.price)
@@ -628,11 +2776,20 @@ impl Display for PriceLevel {
 #[cfg(test)]
 mod tests {
     use crate::errors::PriceLevelError;
+    use crate::execution::{FeeSchedule, MatchResult};
     use crate::order::{
-        Order, OrderCommon, OrderId, OrderUpdate, PegReferenceType, Side, TimeInForce,
+        CancelReason, IcebergRefresh, Order, OrderCommon, OrderId, OrderUpdate, PegReferenceType,
+        PegRepricePolicy, Price, Quantity, Side, TimeInForce,
+    };
+    use crate::price_level::level::{
+        PriceLevel, PriceLevelData, apply_match_result, crossed_quantity, move_order,
+        sum_contributions, volume_weighted_price,
     };
-    use crate::price_level::level::{PriceLevel, PriceLevelData};
     use crate::price_level::snapshot::SNAPSHOT_FORMAT_VERSION;
+    use crate::price_level::{
+        ArithmeticPolicy, LevelEvent, LifecycleValidator, PressureFormula, PriceLevelSnapshot,
+        PriceLevelSnapshotPackage, PriceLevelSnapshotWithStats, PriceLevelStatistics,
+    };
     use crate::{DEFAULT_RESERVE_REPLENISH_AMOUNT, UuidGenerator};
     use std::str::FromStr;
     use tracing::error;
@@ -642,6 +2799,12 @@ mod tests {
     static TIMESTAMP_COUNTER: std::sync::atomic::AtomicU64 =
         std::sync::atomic::AtomicU64::new(1616823000000);
 
+    // Transaction ID generator with a fixed namespace, shared by the matching tests
+    fn create_transaction_id_generator() -> UuidGenerator {
+        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
+        UuidGenerator::new(namespace)
+    }
+
     // Helper functions to create different order types for testing
     pub fn create_standard_order(id: u64, price: u64, quantity: u64) -> Order<()> {
         let order_id = OrderId::from_u64(id);
@@ -655,6 +2818,9 @@ mod tests {
                 timestamp,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         }
     }
@@ -662,8 +2828,12 @@ mod tests {
     #[test]
     fn test_price_level_snapshot_roundtrip() {
         let mut price_level = PriceLevel::new(10000);
-        price_level.add_order(create_standard_order(1, 10000, 100));
-        price_level.add_order(create_iceberg_order(2, 10000, 50, 200));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 200))
+            .unwrap();
 
         let package = price_level
             .snapshot_package()
@@ -699,7 +2869,9 @@ mod tests {
     #[test]
     fn test_price_level_snapshot_checksum_failure() {
         let mut price_level = PriceLevel::new(20000);
-        price_level.add_order(create_standard_order(1, 20000, 100));
+        price_level
+            .add_order(create_standard_order(1, 20000, 100))
+            .unwrap();
 
         let mut package = price_level
             .snapshot_package()
@@ -718,10 +2890,18 @@ mod tests {
     #[test]
     fn test_price_level_from_snapshot_preserves_order_positions() {
         let mut price_level = PriceLevel::new(15000);
-        price_level.add_order(create_standard_order(1, 15000, 100));
-        price_level.add_order(create_iceberg_order(2, 15000, 40, 120));
-        price_level.add_order(create_post_only_order(3, 15000, 60));
-        price_level.add_order(create_reserve_order(4, 15000, 30, 90, 15, true, Some(20)));
+        price_level
+            .add_order(create_standard_order(1, 15000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 15000, 40, 120))
+            .unwrap();
+        price_level
+            .add_order(create_post_only_order(3, 15000, 60))
+            .unwrap();
+        price_level
+            .add_order(create_reserve_order(4, 15000, 30, 90, 15, true, Some(20)))
+            .unwrap();
 
         let snapshot = price_level.snapshot();
         let restored = PriceLevel::from(&snapshot);
@@ -751,10 +2931,18 @@ mod tests {
     #[test]
     fn test_price_level_from_snapshot_package_preserves_order_positions() {
         let mut price_level = PriceLevel::new(17500);
-        price_level.add_order(create_standard_order(10, 17500, 80));
-        price_level.add_order(create_trailing_stop_order(11, 17500, 50));
-        price_level.add_order(create_pegged_order(12, 17500, 40));
-        price_level.add_order(create_market_to_limit_order(13, 17500, 70));
+        price_level
+            .add_order(create_standard_order(10, 17500, 80))
+            .unwrap();
+        price_level
+            .add_order(create_trailing_stop_order(11, 17500, 50))
+            .unwrap();
+        price_level
+            .add_order(create_pegged_order(12, 17500, 40))
+            .unwrap();
+        price_level
+            .add_order(create_market_to_limit_order(13, 17500, 70))
+            .unwrap();
 
         let package = price_level
             .snapshot_package()
@@ -793,8 +2981,14 @@ mod tests {
                 timestamp,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: hidden,
+            original_display: visible,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         }
     }
 
@@ -809,6 +3003,9 @@ mod tests {
                 timestamp,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         }
     }
@@ -824,6 +3021,9 @@ mod tests {
                 timestamp,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             trail_amount: 100,
             last_reference_price: price + 100,
@@ -841,6 +3041,9 @@ mod tests {
                 timestamp,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reference_price_offset: -50,
             reference_price_type: PegReferenceType::BestAsk,
@@ -858,6 +3061,9 @@ mod tests {
                 timestamp,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         }
     }
@@ -881,11 +3087,18 @@ mod tests {
                 timestamp,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: hidden,
             replenish_threshold: threshold,
             replenish_amount,
             auto_replenish,
+            auto_replenish_interval_ms: None,
+            last_reveal_ms: None,
+            max_total_display: None,
+            revealed_quantity: 0,
         }
     }
 
@@ -900,6 +3113,9 @@ mod tests {
                 timestamp,
                 time_in_force: TimeInForce::Fok,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         }
     }
@@ -915,10 +3131,39 @@ mod tests {
                 timestamp,
                 time_in_force: TimeInForce::Ioc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         }
     }
 
+    fn create_day_order(id: u64, visible: u64, hidden: u64, timestamp: u64) -> Order<()> {
+        let common = OrderCommon {
+            id: OrderId::from_u64(id),
+            price: 10000,
+            display_quantity: visible,
+            side: Side::Sell,
+            timestamp,
+            time_in_force: TimeInForce::Day,
+            extra_fields: (),
+            parent_id: None,
+            oco_group: None,
+            suppress_in_feed: false,
+        };
+        if hidden == 0 {
+            Order::Standard { common }
+        } else {
+            Order::IcebergOrder {
+                original_display: common.display_quantity,
+                common,
+                reserve_quantity: hidden,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
+            }
+        }
+    }
+
     fn create_good_till_date_order(id: u64, price: u64, quantity: u64, expiry: u64) -> Order<()> {
         let timestamp = TIMESTAMP_COUNTER.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         Order::Standard {
@@ -930,6 +3175,9 @@ mod tests {
                 timestamp,
                 time_in_force: TimeInForce::Gtd(expiry),
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         }
     }
@@ -958,7 +3206,7 @@ mod tests {
 
         {
             // Verify the returned reference points to the expected order
-            let order_ref = price_level.add_order(order);
+            let order_ref = price_level.add_order(order).unwrap();
             assert_eq!(order_ref.id(), OrderId::from_u64(1));
             assert_eq!(order_ref.price(), 10000);
             assert_eq!(order_ref.display_quantity(), 100);
@@ -978,7 +3226,7 @@ mod tests {
         let mut price_level = PriceLevel::new(10000);
         let order = create_iceberg_order(2, 10000, 50, 200);
 
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         assert_eq!(price_level.display_quantity(), 50);
         assert_eq!(price_level.reserve_quantity(), 200);
@@ -991,10 +3239,18 @@ mod tests {
         let mut price_level = PriceLevel::new(10000);
 
         // Add different order types
-        price_level.add_order(create_standard_order(1, 10000, 100));
-        price_level.add_order(create_iceberg_order(2, 10000, 50, 200));
-        price_level.add_order(create_post_only_order(3, 10000, 75));
-        price_level.add_order(create_reserve_order(4, 10000, 25, 100, 100, true, None));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 200))
+            .unwrap();
+        price_level
+            .add_order(create_post_only_order(3, 10000, 75))
+            .unwrap();
+        price_level
+            .add_order(create_reserve_order(4, 10000, 25, 100, 100, true, None))
+            .unwrap();
 
         assert_eq!(price_level.display_quantity(), 250); // 100 + 50 + 75 + 25
         assert_eq!(price_level.reserve_quantity(), 300); // 0 + 200 + 0 + 100
@@ -1009,8 +3265,12 @@ mod tests {
     fn test_update_order_cancel() {
         let mut price_level = PriceLevel::new(10000);
 
-        price_level.add_order(create_standard_order(1, 10000, 100));
-        price_level.add_order(create_iceberg_order(2, 10000, 50, 200));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 200))
+            .unwrap();
 
         // Cancel the standard order using OrderUpdate
         let result = price_level.update_order(OrderUpdate::Cancel {
@@ -1054,8 +3314,12 @@ mod tests {
     fn test_iter_orders() {
         let mut price_level = PriceLevel::new(10000);
 
-        price_level.add_order(create_standard_order(1, 10000, 100));
-        price_level.add_order(create_iceberg_order(2, 10000, 50, 200));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 200))
+            .unwrap();
 
         let orders = price_level.iter_orders();
 
@@ -1070,10 +3334,11 @@ mod tests {
     #[test]
     fn test_match_standard_order_full() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
-        price_level.add_order(create_standard_order(1, 10000, 100));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
 
         // Match the entire order
         let taker_id = OrderId::from_u64(999); // market order ID
@@ -1105,8 +3370,7 @@ mod tests {
     #[test]
     fn test_match_zero_quantity_is_trivially_complete() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         let taker_id = OrderId::from_u64(4242);
         let match_result = price_level.match_order(0, taker_id, &transaction_id_generator);
@@ -1121,10 +3385,11 @@ mod tests {
     #[test]
     fn test_match_standard_order_partial() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
-        price_level.add_order(create_standard_order(1, 10000, 100));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
 
         // Match part of the order
         let taker_id = OrderId::from_u64(999);
@@ -1157,10 +3422,11 @@ mod tests {
     #[test]
     fn test_match_standard_order_excess() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
-        price_level.add_order(create_standard_order(1, 10000, 100));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
 
         // Match with quantity exceeding available
         let taker_id = OrderId::from_u64(999);
@@ -1192,11 +3458,12 @@ mod tests {
     /// after each match, including visible/hidden quantities and the number of orders.
     fn test_match_iceberg_order() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Add a new iceberg order with a visible quantity of 50 and a hidden quantity of 100.
-        price_level.add_order(create_iceberg_order(1, 10000, 50, 100));
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 50, 100))
+            .unwrap();
 
         // Match the visible portion of the iceberg order.
         let taker_id = OrderId::from_u64(999);
@@ -1252,11 +3519,12 @@ mod tests {
     #[test]
     fn test_match_iceberg_order_overlapping() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Add a new iceberg order with a visible quantity of 50 and a hidden quantity of 100.
-        price_level.add_order(create_iceberg_order(1, 10000, 100, 100));
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 100, 100))
+            .unwrap();
 
         // Match the visible portion of the iceberg order.
         let taker_id = OrderId::from_u64(999);
@@ -1285,8 +3553,8 @@ mod tests {
         let match_result = price_level.match_order(50, taker_id, &transaction_id_generator);
         assert_eq!(match_result.remaining_quantity, 0);
         assert!(match_result.is_complete);
-        assert_eq!(price_level.display_quantity(), 50); // Visible quantity replenished
-        assert_eq!(price_level.reserve_quantity(), 50); // Hidden quantity reduced
+        assert_eq!(price_level.display_quantity(), 100); // Refreshed to the entered display size
+        assert_eq!(price_level.reserve_quantity(), 0); // Hidden quantity reduced
         assert_eq!(price_level.order_count(), 1);
         let transaction = &match_result.transactions.as_vec()[0];
 
@@ -1297,7 +3565,7 @@ mod tests {
         assert_eq!(transaction.taker_side, Side::Buy);
         assert_eq!(match_result.filled_order_ids.len(), 0);
 
-        // Match the remaining 50 units (50 visible + 0 hidden).
+        // Match the remaining 100 units (100 visible + 0 hidden).
         let taker_id = OrderId::from_u64(1001);
 
         // This should match the remaining visible quantity and deplete the hidden quantity.
@@ -1314,10 +3582,11 @@ mod tests {
     #[test]
     fn test_match_iceberg_order_partial_visible() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
-        price_level.add_order(create_iceberg_order(1, 10000, 50, 150));
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 50, 150))
+            .unwrap();
 
         // Match part of the visible portion
         let taker_id = OrderId::from_u64(999);
@@ -1330,6 +3599,50 @@ mod tests {
         assert_eq!(price_level.order_count(), 1);
     }
 
+    #[test]
+    fn test_match_iceberg_refreshes_by_strategy() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        // A partial fill does not shrink the next slice
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 10, 30))
+            .unwrap();
+        price_level.match_order(4, OrderId::from_u64(900), &transaction_id_generator);
+        let result = price_level.match_order(6, OrderId::from_u64(901), &transaction_id_generator);
+        assert_eq!(price_level.display_quantity(), 10);
+        assert_eq!(price_level.reserve_quantity(), 20);
+        let reveal_time = result.transactions.as_vec()[0].timestamp;
+        match price_level.iter_orders()[0] {
+            Order::IcebergOrder {
+                original_display,
+                last_reveal_ms,
+                ..
+            } => {
+                assert_eq!(original_display, 10);
+                assert_eq!(last_reveal_ms, Some(reveal_time));
+            }
+            other => panic!("Expected an iceberg order, got {other:?}"),
+        }
+
+        // A fixed strategy reveals its own size, capped at the reserve
+        let mut order = create_iceberg_order(2, 10000, 10, 8);
+        if let Order::IcebergOrder {
+            refresh_strategy, ..
+        } = &mut order
+        {
+            *refresh_strategy = IcebergRefresh::Fixed(5);
+        }
+        let mut price_level = PriceLevel::new(10000);
+        price_level.add_order(order).unwrap();
+        price_level.match_order(10, OrderId::from_u64(902), &transaction_id_generator);
+        assert_eq!(price_level.display_quantity(), 5);
+        assert_eq!(price_level.reserve_quantity(), 3);
+        price_level.match_order(5, OrderId::from_u64(903), &transaction_id_generator);
+        assert_eq!(price_level.display_quantity(), 3);
+        assert_eq!(price_level.reserve_quantity(), 0);
+    }
+
     // ------------------------------------------- RESERVE ORDERS -------------------------------------------
 
     #[test]
@@ -1338,11 +3651,12 @@ mod tests {
     /// from the price level even if there is remaining hidden quantity.
     fn test_match_reserve_order_no_auto_replenish() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Create a reserve order with auto-replenish disabled
-        price_level.add_order(create_reserve_order(1, 10000, 50, 150, 20, false, None));
+        price_level
+            .add_order(create_reserve_order(1, 10000, 50, 150, 20, false, None))
+            .unwrap();
 
         // Match the entire visible portion
         let taker_id = OrderId::from_u64(999);
@@ -1362,11 +3676,12 @@ mod tests {
     /// replenish from the hidden quantity.
     fn test_match_reserve_order_with_auto_replenish() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Create a reserve order with auto-replenish enabled
-        price_level.add_order(create_reserve_order(1, 10000, 50, 150, 20, true, None));
+        price_level
+            .add_order(create_reserve_order(1, 10000, 50, 150, 20, true, None))
+            .unwrap();
 
         // Match the entire visible portion
         let taker_id = OrderId::from_u64(999);
@@ -1392,11 +3707,12 @@ mod tests {
     /// replenishment even when falling below the threshold.
     fn test_match_reserve_order_partial_no_replenish() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Create a reserve order with auto-replenish disabled
-        price_level.add_order(create_reserve_order(1, 10000, 50, 150, 20, false, None));
+        price_level
+            .add_order(create_reserve_order(1, 10000, 50, 150, 20, false, None))
+            .unwrap();
 
         // Match partially, but still above threshold
         let taker_id = OrderId::from_u64(999);
@@ -1424,20 +3740,21 @@ mod tests {
     /// using the specified custom amount rather than the default.
     fn test_match_reserve_order_with_custom_replenish_amount() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Create a reserve order with auto-replenish enabled and a custom replenishment amount
         let custom_amount = 50;
-        price_level.add_order(create_reserve_order(
-            1,
-            10000,
-            50,
-            150,
-            20,
-            true,
-            Some(custom_amount),
-        ));
+        price_level
+            .add_order(create_reserve_order(
+                1,
+                10000,
+                50,
+                150,
+                20,
+                true,
+                Some(custom_amount),
+            ))
+            .unwrap();
 
         // Match the entire visible portion
         let taker_id = OrderId::from_u64(999);
@@ -1457,11 +3774,12 @@ mod tests {
     /// when visible quantity equals the threshold.
     fn test_match_reserve_order_with_zero_threshold() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Create a reserve order with threshold 0 and auto-replenish enabled
-        price_level.add_order(create_reserve_order(1, 10000, 50, 150, 0, true, None));
+        price_level
+            .add_order(create_reserve_order(1, 10000, 50, 150, 0, true, None))
+            .unwrap();
 
         // Match partially
         let taker_id = OrderId::from_u64(999);
@@ -1480,11 +3798,12 @@ mod tests {
     /// The order should be removed from the book when visible quantity reaches 0.
     fn test_match_reserve_order_threshold_zero() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Create a reserve order with threshold 0 and auto-replenish disabled
-        price_level.add_order(create_reserve_order(1, 10000, 50, 150, 0, false, None));
+        price_level
+            .add_order(create_reserve_order(1, 10000, 50, 150, 0, false, None))
+            .unwrap();
 
         // Match the entire visible portion
         let taker_id = OrderId::from_u64(999);
@@ -1503,11 +3822,12 @@ mod tests {
     /// The order should be removed from the book when visible quantity reaches 0.
     fn test_match_reserve_order_threshold_one() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Create a reserve order with threshold 1 and auto-replenish disabled
-        price_level.add_order(create_reserve_order(1, 10000, 50, 150, 1, false, None));
+        price_level
+            .add_order(create_reserve_order(1, 10000, 50, 150, 1, false, None))
+            .unwrap();
 
         // Match the entire visible portion
         let taker_id = OrderId::from_u64(999);
@@ -1526,11 +3846,12 @@ mod tests {
     /// Verifies behavior when matching above and below the threshold.
     fn test_match_reserve_order_with_threshold() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Create a reserve order with threshold 20 and auto-replenish disabled
-        price_level.add_order(create_reserve_order(1, 10000, 50, 150, 20, false, None));
+        price_level
+            .add_order(create_reserve_order(1, 10000, 50, 150, 20, false, None))
+            .unwrap();
 
         // Match part of the visible portion, but still above threshold
         let taker_id = OrderId::from_u64(999);
@@ -1560,12 +3881,13 @@ mod tests {
     ///    This test verifies correct transaction generation and order state throughout.
     fn test_match_reserve_order_overlapping() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Create a reserve order with threshold 20, auto-replenish enabled
         // and default replenish amount (80)
-        price_level.add_order(create_reserve_order(1, 10000, 100, 100, 20, true, None));
+        price_level
+            .add_order(create_reserve_order(1, 10000, 100, 100, 20, true, None))
+            .unwrap();
 
         // Match 80 units, which is above the replenish threshold
         let taker_id = OrderId::from_u64(999);
@@ -1642,10 +3964,11 @@ mod tests {
     #[test]
     fn test_match_post_only_order() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
-        price_level.add_order(create_post_only_order(1, 10000, 100));
+        price_level
+            .add_order(create_post_only_order(1, 10000, 100))
+            .unwrap();
 
         // Post-only orders behave like standard orders for matching
         let taker_id = OrderId::from_u64(999);
@@ -1660,10 +3983,11 @@ mod tests {
     #[test]
     fn test_match_trailing_stop_order() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
-        price_level.add_order(create_trailing_stop_order(1, 10000, 100));
+        price_level
+            .add_order(create_trailing_stop_order(1, 10000, 100))
+            .unwrap();
 
         // Trailing stop orders behave like standard orders for matching
         let taker_id = OrderId::from_u64(999);
@@ -1678,10 +4002,11 @@ mod tests {
     #[test]
     fn test_match_pegged_order() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
-        price_level.add_order(create_pegged_order(1, 10000, 100));
+        price_level
+            .add_order(create_pegged_order(1, 10000, 100))
+            .unwrap();
 
         // Pegged orders behave like standard orders for matching
         let taker_id = OrderId::from_u64(999);
@@ -1696,10 +4021,11 @@ mod tests {
     #[test]
     fn test_match_market_to_limit_order() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
-        price_level.add_order(create_market_to_limit_order(1, 10000, 100));
+        price_level
+            .add_order(create_market_to_limit_order(1, 10000, 100))
+            .unwrap();
 
         // Market-to-limit orders behave like standard orders for matching
         let taker_id = OrderId::from_u64(999);
@@ -1714,10 +4040,11 @@ mod tests {
     #[test]
     fn test_match_fill_or_kill_order() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
-        price_level.add_order(create_fill_or_kill_order(1, 10000, 100));
+        price_level
+            .add_order(create_fill_or_kill_order(1, 10000, 100))
+            .unwrap();
 
         // For the price level, FOK behaves like standard orders
         let taker_id = OrderId::from_u64(999);
@@ -1732,10 +4059,11 @@ mod tests {
     #[test]
     fn test_match_immediate_or_cancel_order() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
-        price_level.add_order(create_immediate_or_cancel_order(1, 10000, 100));
+        price_level
+            .add_order(create_immediate_or_cancel_order(1, 10000, 100))
+            .unwrap();
 
         // For the price level, IOC behaves like standard orders
         let taker_id = OrderId::from_u64(999);
@@ -1750,10 +4078,11 @@ mod tests {
     #[test]
     fn test_match_good_till_date_order() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
-        price_level.add_order(create_good_till_date_order(1, 10000, 100, 1617000000000));
+        price_level
+            .add_order(create_good_till_date_order(1, 10000, 100, 1617000000000))
+            .unwrap();
 
         // GTD orders behave like standard orders for matching
         let taker_id = OrderId::from_u64(999);
@@ -1768,12 +4097,17 @@ mod tests {
     #[test]
     fn test_match_multiple_orders() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
-
-        price_level.add_order(create_standard_order(1, 10000, 50));
-        price_level.add_order(create_standard_order(2, 10000, 75));
-        price_level.add_order(create_standard_order(3, 10000, 25));
+        let transaction_id_generator = create_transaction_id_generator();
+
+        price_level
+            .add_order(create_standard_order(1, 10000, 50))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 75))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 25))
+            .unwrap();
 
         // Match first two orders completely and third partially
         let taker_id = OrderId::from_u64(999);
@@ -1827,8 +4161,12 @@ mod tests {
         let mut price_level = PriceLevel::new(10000);
 
         // Add some orders
-        price_level.add_order(create_standard_order(1, 10000, 100));
-        price_level.add_order(create_standard_order(2, 10000, 50));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 50))
+            .unwrap();
 
         // Create a snapshot
         let snapshot = price_level.snapshot();
@@ -1857,7 +4195,7 @@ mod tests {
 
         // Add an order
         let order = create_standard_order(1, 10000, 100);
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         // Update the price to a different value
         let update = OrderUpdate::UpdatePrice {
@@ -1879,7 +4217,7 @@ mod tests {
 
         // Test updating price to same value (should return error)
         let order = create_standard_order(2, 10000, 100);
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         let same_price_update = OrderUpdate::UpdatePrice {
             order_id: OrderId::from_u64(2),
@@ -1900,7 +4238,7 @@ mod tests {
 
         // Add an order
         let order = create_standard_order(1, 10000, 100);
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         // Update to increase quantity
         let update = OrderUpdate::UpdateQuantity {
@@ -1949,13 +4287,157 @@ mod tests {
         assert!(result.unwrap().is_none());
     }
 
+    #[test]
+    fn test_preview_update() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 200))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 30))
+            .unwrap();
+        let before = price_level.snapshot();
+
+        let cancel = OrderUpdate::Cancel {
+            order_id: OrderId::from_u64(2),
+        };
+        let preview = price_level.preview_update(&cancel).unwrap();
+        assert_eq!(preview.display_quantity, 130);
+        assert_eq!(preview.reserve_quantity, 0);
+        assert_eq!(preview.order_count, 2);
+
+        let increase = OrderUpdate::UpdateQuantity {
+            order_id: OrderId::from_u64(1),
+            new_quantity: 150,
+        };
+        let preview = price_level.preview_update(&increase).unwrap();
+        assert_eq!(preview.display_quantity, 230);
+        assert_eq!(preview.total_quantity(), 430);
+        assert_eq!(preview.order_count, 3);
+
+        let decrease = OrderUpdate::UpdateQuantity {
+            order_id: OrderId::from_u64(2),
+            new_quantity: 20,
+        };
+        let preview = price_level.preview_update(&decrease).unwrap();
+        assert_eq!(preview.display_quantity, 150);
+        assert_eq!(preview.reserve_quantity, 200);
+
+        // Unknown orders leave the totals unchanged, and invalid updates fail as they would
+        let missing = OrderUpdate::Cancel {
+            order_id: OrderId::from_u64(99),
+        };
+        assert_eq!(
+            price_level.preview_update(&missing).unwrap(),
+            price_level.aggregate()
+        );
+        let same_price = OrderUpdate::UpdatePrice {
+            order_id: OrderId::from_u64(1),
+            new_price: 10000,
+        };
+        assert!(matches!(
+            price_level.preview_update(&same_price),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert_eq!(price_level.snapshot(), before);
+
+        // Each preview matches the level after the update is applied
+        for update in [decrease, increase, cancel] {
+            let preview = price_level.preview_update(&update).unwrap();
+            price_level.update_order(update).unwrap();
+            assert_eq!(preview, price_level.aggregate());
+        }
+    }
+
+    #[test]
+    fn test_update_quantity_priority() {
+        let ids = |price_level: &PriceLevel| -> Vec<OrderId> {
+            price_level.iter_orders().iter().map(|o| o.id()).collect()
+        };
+        let mut price_level = PriceLevel::new(10000);
+        for id in 1..=3 {
+            price_level
+                .add_order(create_standard_order(id, 10000, 100))
+                .unwrap();
+        }
+
+        // Decreasing through any same-price update keeps the order's place
+        price_level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: OrderId::from_u64(1),
+                new_quantity: 80,
+            })
+            .unwrap();
+        price_level
+            .update_order(OrderUpdate::UpdatePriceAndQuantity {
+                order_id: OrderId::from_u64(1),
+                new_price: 10000,
+                new_quantity: 60,
+            })
+            .unwrap();
+        price_level
+            .update_order(OrderUpdate::Replace {
+                order_id: OrderId::from_u64(2),
+                price: 10000,
+                quantity: 100,
+                side: Side::Buy,
+            })
+            .unwrap();
+        assert_eq!(ids(&price_level), [1, 2, 3].map(OrderId::from_u64).to_vec());
+        assert_eq!(price_level.display_quantity(), 260);
+
+        // Increasing loses priority
+        price_level
+            .update_order(OrderUpdate::UpdatePriceAndQuantity {
+                order_id: OrderId::from_u64(1),
+                new_price: 10000,
+                new_quantity: 70,
+            })
+            .unwrap();
+        assert_eq!(ids(&price_level), [2, 3, 1].map(OrderId::from_u64).to_vec());
+        assert_eq!(price_level.display_quantity(), 270);
+
+        // A price change still removes the order for re-routing
+        let moved = price_level
+            .update_order(OrderUpdate::UpdatePriceAndQuantity {
+                order_id: OrderId::from_u64(3),
+                new_price: 10010,
+                new_quantity: 10,
+            })
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.id(), OrderId::from_u64(3));
+        assert_eq!(ids(&price_level), [2, 1].map(OrderId::from_u64).to_vec());
+        assert!(price_level.aggregates_match_queue());
+
+        // A sorted queue re-sorts a decreased order
+        let mut sorted = PriceLevel::new(10000)
+            .with_comparator(|a, b| b.display_quantity().cmp(&a.display_quantity()));
+        sorted
+            .add_order(create_standard_order(1, 10000, 50))
+            .unwrap();
+        sorted
+            .add_order(create_standard_order(2, 10000, 40))
+            .unwrap();
+        sorted
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: OrderId::from_u64(1),
+                new_quantity: 30,
+            })
+            .unwrap();
+        assert_eq!(ids(&sorted), [2, 1].map(OrderId::from_u64).to_vec());
+    }
+
     #[test]
     fn test_update_order_update_price_and_quantity() {
         let mut price_level = PriceLevel::new(10000);
 
         // Add an order
         let order = create_standard_order(1, 10000, 100);
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         // Update both price and quantity with different price
         let update = OrderUpdate::UpdatePriceAndQuantity {
@@ -1978,7 +4460,7 @@ mod tests {
 
         // Test with same price but different quantity
         let order = create_standard_order(2, 10000, 100);
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         let update = OrderUpdate::UpdatePriceAndQuantity {
             order_id: OrderId::from_u64(2),
@@ -2005,7 +4487,7 @@ mod tests {
 
         // Add an order
         let order = create_standard_order(1, 10000, 100);
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         // Replace with different price
         let update = OrderUpdate::Replace {
@@ -2029,7 +4511,7 @@ mod tests {
 
         // Test with same price but different quantity
         let order = create_standard_order(2, 10000, 100);
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         let update = OrderUpdate::Replace {
             order_id: OrderId::from_u64(2),
@@ -2057,8 +4539,12 @@ mod tests {
         let mut price_level = PriceLevel::new(10000);
 
         // Add some orders
-        price_level.add_order(create_standard_order(1, 10000, 100));
-        price_level.add_order(create_standard_order(2, 10000, 50));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 50))
+            .unwrap();
 
         // Convert to PriceLevelData
         let data: PriceLevelData = (&price_level).into();
@@ -2116,7 +4602,9 @@ mod tests {
     #[test]
     fn test_price_level_display() {
         let mut price_level = PriceLevel::new(10000);
-        price_level.add_order(create_standard_order(1, 10000, 100));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
 
         let display_str = format!("{price_level}");
 
@@ -2133,11 +4621,21 @@ mod tests {
     #[test]
     fn test_price_level_from_str() {
         let mut price_level = PriceLevel::new(10000);
-        price_level.add_order(create_standard_order(1, 10000, 50));
-        price_level.add_order(create_standard_order(2, 10000, 75));
-        price_level.add_order(create_good_till_date_order(3, 10000, 100, 1617000000000));
-        price_level.add_order(create_reserve_order(4, 10000, 100, 100, 20, true, None));
-        price_level.add_order(create_iceberg_order(5, 10000, 50, 100));
+        price_level
+            .add_order(create_standard_order(1, 10000, 50))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 75))
+            .unwrap();
+        price_level
+            .add_order(create_good_till_date_order(3, 10000, 100, 1617000000000))
+            .unwrap();
+        price_level
+            .add_order(create_reserve_order(4, 10000, 100, 100, 20, true, None))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(5, 10000, 50, 100))
+            .unwrap();
 
         let input = "PriceLevel:price=10000;display_quantity=375;reserve_quantity=200;order_count=5;orders=[Standard:id=00000000-0000-0001-0000-000000000000;price=10000;display_quantity=50;side=BUY;timestamp=1616823000000;time_in_force=GTC,Standard:id=00000000-0000-0002-0000-000000000000;price=10000;display_quantity=75;side=BUY;timestamp=1616823000001;time_in_force=GTC,Standard:id=00000000-0000-0003-0000-000000000000;price=10000;display_quantity=100;side=BUY;timestamp=1616823000002;time_in_force=GTD-1617000000000,ReserveOrder:id=00000000-0000-0004-0000-000000000000;price=10000;display_quantity=100;reserve_quantity=100;side=SELL;timestamp=1616823000003;time_in_force=GTC;replenish_threshold=20;replenish_amount=None;auto_replenish=true,IcebergOrder:id=00000000-0000-0005-0000-000000000000;price=10000;display_quantity=50;reserve_quantity=100;side=SELL;timestamp=1616823000004;time_in_force=GTC]";
         let result = PriceLevel::from_str(input);
@@ -2164,11 +4662,68 @@ mod tests {
         assert_eq!(orders[0].display_quantity(), 50);
     }
 
+    #[test]
+    fn test_price_level_from_str_reports_malformed_input() {
+        let order = |id: u64, quantity: &str| {
+            format!(
+                "Standard:id=00000000-0000-000{id}-0000-000000000000;price=10000;display_quantity={quantity};side=BUY;timestamp=1616823000000;time_in_force=GTC"
+            )
+        };
+        let level =
+            |orders: &[String]| format!("PriceLevel:price=10000;orders=[{}]", orders.join(","));
+
+        // The failing order is pinpointed by its position and text
+        let input = level(&[
+            order(1, "10"),
+            order(2, "20"),
+            order(3, "abc"),
+            order(4, "40"),
+        ]);
+        match PriceLevel::from_str(&input) {
+            Err(PriceLevelError::OrderParseError {
+                order_index, input, ..
+            }) => {
+                assert_eq!(order_index, 2);
+                assert_eq!(input, order(3, "abc"));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+
+        let input = level(&[order(1, "10"), String::new(), order(3, "30")]);
+        assert!(matches!(
+            PriceLevel::from_str(&input),
+            Err(PriceLevelError::OrderParseError { order_index: 1, ref input, .. }) if input.is_empty()
+        ));
+
+        let input = format!("PriceLevel:price=10000;orders=[{})]", order(1, "10"));
+        assert!(matches!(
+            PriceLevel::from_str(&input),
+            Err(PriceLevelError::OrderParseError { order_index: 0, .. })
+        ));
+
+        // Level fields get errors naming the field or the offending text
+        assert!(matches!(
+            PriceLevel::from_str("PriceLevel:display_quantity=10"),
+            Err(PriceLevelError::MissingField(ref field)) if field == "price"
+        ));
+        assert!(matches!(
+            PriceLevel::from_str("PriceLevel:price=ten"),
+            Err(PriceLevelError::InvalidFieldValue { ref value, .. }) if value == "ten"
+        ));
+        let error =
+            PriceLevel::from_str("PriceLevel:price=10000;orders=[Standard:id=1").unwrap_err();
+        assert!(error.to_string().contains("orders=[Standard:id=1"));
+        let error = PriceLevel::from_str("PriceLevel:price=10000;garbage").unwrap_err();
+        assert!(error.to_string().contains("'garbage'"));
+    }
+
     // Test serialization and deserialization for PriceLevel
     #[test]
     fn test_price_level_serde() {
         let mut price_level = PriceLevel::new(10000);
-        price_level.add_order(create_standard_order(1, 10000, 100));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
 
         // Serialize to JSON
         let serialized = serde_json::to_string(&price_level).unwrap();
@@ -2202,11 +4757,12 @@ mod tests {
     #[test]
     fn test_level_partial_match_remaining() {
         let mut price_level = PriceLevel::new(10000);
-        let namespace = Uuid::parse_str("6ba7b810-9dad-11d1-80b4-00c04fd430c8").unwrap();
-        let transaction_id_generator = UuidGenerator::new(namespace);
+        let transaction_id_generator = create_transaction_id_generator();
 
         // Add orders with more quantity than we'll match
-        price_level.add_order(create_standard_order(1, 10000, 200));
+        price_level
+            .add_order(create_standard_order(1, 10000, 200))
+            .unwrap();
 
         // Match only part of what's available
         let match_result =
@@ -2223,7 +4779,9 @@ mod tests {
         let mut price_level = PriceLevel::new(10000);
 
         // Add an order
-        price_level.add_order(create_standard_order(1, 10000, 100));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
 
         // Update to a different price (should remove from this level)
         let result = price_level.update_order(OrderUpdate::UpdatePrice {
@@ -2242,7 +4800,9 @@ mod tests {
         let mut price_level = PriceLevel::new(10000);
 
         // Add an order
-        price_level.add_order(create_standard_order(1, 10000, 100));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
 
         // Update the quantity but keep the same price
         let result = price_level.update_order(OrderUpdate::UpdatePriceAndQuantity {
@@ -2263,8 +4823,12 @@ mod tests {
         let mut price_level = PriceLevel::new(10000);
 
         // Add some orders
-        price_level.add_order(create_standard_order(1, 10000, 100));
-        price_level.add_order(create_iceberg_order(2, 10000, 50, 150));
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 150))
+            .unwrap();
 
         // Serialize to JSON
         let serialized = serde_json::to_string(&price_level).unwrap();
@@ -2298,9 +4862,12 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         // Try to update price to the same value
         let update = OrderUpdate::UpdatePrice {
@@ -2352,9 +4919,12 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         // Set up a test that simulates order removal by another thread
         // This can be done by modifying the OrderQueue's internal state directly
@@ -2399,9 +4969,12 @@ mod tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         // Update to increase quantity (old visible < new visible)
         let update = OrderUpdate::UpdateQuantity {
@@ -2413,177 +4986,3507 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().is_some());
 
-        // Verify quantity increased
-        assert_eq!(price_level.display_quantity(), 100);
+        // Verify quantity increased
+        assert_eq!(price_level.display_quantity(), 100);
+    }
+
+    #[test]
+    fn test_price_level_update_reserve_quantity() {
+        // Test lines 488, 498
+        let mut price_level = PriceLevel::new(10000);
+
+        // Add an iceberg order with visible and hidden quantities
+        let order = Order::IcebergOrder {
+            common: OrderCommon {
+                id: OrderId::from_u64(1),
+                price: 10000,
+                display_quantity: 50,
+                side: Side::Buy,
+                timestamp: 1616823000000,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+            reserve_quantity: 150,
+            original_display: 50,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
+        };
+        price_level.add_order(order).unwrap();
+
+        // Verify initial quantities
+        assert_eq!(price_level.display_quantity(), 50);
+        assert_eq!(price_level.reserve_quantity(), 150);
+
+        // Create a new iceberg order with different quantities
+        let new_order = Order::IcebergOrder {
+            common: OrderCommon {
+                id: OrderId::from_u64(1),
+                price: 10000,
+                display_quantity: 40,
+                side: Side::Buy,
+                timestamp: 1616823000000,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+            reserve_quantity: 200,
+            original_display: 40,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
+        };
+
+        // Test increasing hidden quantity
+        let result = price_level.update_order(OrderUpdate::Cancel {
+            order_id: OrderId::from_u64(1),
+        });
+        assert!(result.is_ok());
+        price_level.add_order(new_order).unwrap();
+
+        // Verify both visible and hidden quantities were updated
+        assert_eq!(price_level.display_quantity(), 40);
+        assert_eq!(price_level.reserve_quantity(), 200);
+    }
+
+    #[test]
+    fn test_price_level_update_price_and_quantity_same_price() {
+        // Test line 510
+        let mut price_level = PriceLevel::new(10000);
+
+        // Add an order
+        let order = Order::<()>::Standard {
+            common: OrderCommon {
+                id: OrderId::from_u64(1),
+                price: 10000,
+                display_quantity: 50,
+                side: Side::Buy,
+                timestamp: 1616823000000,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+        };
+        price_level.add_order(order).unwrap();
+
+        // Update both price and quantity with same price
+        let update = OrderUpdate::UpdatePriceAndQuantity {
+            order_id: OrderId::from_u64(1),
+            new_price: 10000, // Same price
+            new_quantity: 100,
+        };
+
+        let result = price_level.update_order(update);
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_some());
+
+        // Verify quantity was updated but price remained the same
+        assert_eq!(price_level.display_quantity(), 100);
+        assert_eq!(price_level.price(), 10000);
+    }
+
+    #[test]
+    fn test_price_level_from_price_level_data_conversion() {
+        // Test lines 521-523, 527, 537, 558-560, 562-564, 566-568, 607
+
+        // Create a price level
+        let mut price_level = PriceLevel::new(10000);
+
+        // Add some orders
+        let order1 = Order::<()>::Standard {
+            common: OrderCommon {
+                id: OrderId::from_u64(1),
+                price: 10000,
+                display_quantity: 50,
+                side: Side::Buy,
+                timestamp: 1616823000000,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+        };
+        price_level.add_order(order1).unwrap();
+
+        let order2 = Order::<()>::IcebergOrder {
+            common: OrderCommon {
+                id: OrderId::from_u64(2),
+                price: 10000,
+                display_quantity: 30,
+                side: Side::Buy,
+                timestamp: 1616823000001,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+            reserve_quantity: 70,
+            original_display: 30,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
+        };
+        price_level.add_order(order2).unwrap();
+
+        // Convert to PriceLevelData
+        let data: PriceLevelData = (&price_level).into();
+
+        // Verify data
+        assert_eq!(data.price, 10000);
+        assert_eq!(data.display_quantity, 80); // 50 + 30
+        assert_eq!(data.reserve_quantity, 70);
+        assert_eq!(data.order_count, 2);
+        assert_eq!(data.orders.len(), 2);
+
+        // Convert back to PriceLevel
+        let result = PriceLevel::try_from(data);
+        assert!(result.is_ok());
+
+        // Verify converted price level
+        let converted_level = result.unwrap();
+        assert_eq!(converted_level.price(), 10000);
+        assert_eq!(converted_level.display_quantity(), 80);
+        assert_eq!(converted_level.reserve_quantity(), 70);
+        assert_eq!(converted_level.order_count(), 2);
+
+        // Test display implementation
+        let display_string = price_level.to_string();
+        assert!(display_string.starts_with("PriceLevel:price=10000;"));
+        assert!(display_string.contains("display_quantity=80"));
+        assert!(display_string.contains("reserve_quantity=70"));
+        assert!(display_string.contains("order_count=2"));
+
+        // Test serialization
+        let serialized = serde_json::to_string(&price_level).unwrap();
+        assert!(serialized.contains("\"price\":10000"));
+        assert!(serialized.contains("\"display_quantity\":80"));
+        assert!(serialized.contains("\"reserve_quantity\":70"));
+        assert!(serialized.contains("\"order_count\":2"));
+
+        // Test deserialization
+        let deserialized: PriceLevel = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.price(), 10000);
+        assert_eq!(deserialized.display_quantity(), 80);
+        assert_eq!(deserialized.reserve_quantity(), 70);
+        assert_eq!(deserialized.order_count(), 2);
+    }
+
+    #[test]
+    fn test_to_dom_row() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 200))
+            .unwrap();
+
+        let row = price_level.to_dom_row(Side::Sell);
+        assert_eq!(row.price, 10000);
+        assert_eq!(row.visible_qty, 150);
+        assert_eq!(row.hidden_qty, 200);
+        assert_eq!(row.order_count, 2);
+        assert_eq!(row.side, Side::Sell);
+
+        let empty = PriceLevel::new(9900).to_dom_row(Side::Buy);
+        assert_eq!(empty.visible_qty, 0);
+        assert_eq!(empty.order_count, 0);
+        assert_eq!(empty.side, Side::Buy);
+    }
+
+    #[test]
+    fn test_match_reserve_after_update_quantity_keeps_aggregates() {
+        let transaction_id_generator = UuidGenerator::new(Uuid::nil());
+
+        // The default checked policy panics if an aggregate drifts from the orders
+        let mut price_level = PriceLevel::new(10000);
+        // A reserve order without auto-replenish discards its reserve on full removal
+        price_level
+            .add_order(create_reserve_order(1, 10000, 50, 100, 10, false, None))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 20, 60))
+            .unwrap();
+
+        for (order_id, new_quantity) in [(1, 30), (2, 35), (1, 45)] {
+            price_level
+                .update_order(OrderUpdate::UpdateQuantity {
+                    order_id: OrderId::from_u64(order_id),
+                    new_quantity,
+                })
+                .unwrap();
+            let snapshot = price_level.snapshot();
+            assert_eq!(price_level.display_quantity(), snapshot.display_quantity);
+            assert_eq!(price_level.reserve_quantity(), snapshot.reserve_quantity);
+        }
+
+        // The iceberg refreshes while the reserve order leaves with its reserve
+        let match_result =
+            price_level.match_order(80, OrderId::from_u64(999), &transaction_id_generator);
+        assert!(match_result.is_complete);
+        assert_eq!(match_result.filled_order_ids, vec![OrderId::from_u64(1)]);
+        let snapshot = price_level.snapshot();
+        assert_eq!(price_level.display_quantity(), snapshot.display_quantity);
+        assert_eq!(price_level.reserve_quantity(), snapshot.reserve_quantity);
+        assert_eq!(price_level.order_count(), 1);
+
+        let match_result = price_level.match_order(
+            price_level.total_quantity(),
+            OrderId::from_u64(1000),
+            &transaction_id_generator,
+        );
+        assert!(match_result.is_complete);
+        assert_eq!(price_level.display_quantity(), 0);
+        assert_eq!(price_level.reserve_quantity(), 0);
+        assert_eq!(price_level.order_count(), 0);
+    }
+
+    #[test]
+    fn test_saturating_policy_clamps_drifted_reserve_on_removal() {
+        let transaction_id_generator = UuidGenerator::new(Uuid::nil());
+        let mut price_level =
+            PriceLevel::new(10000).with_arithmetic_policy(ArithmeticPolicy::Saturating);
+        price_level
+            .add_order(create_reserve_order(1, 10000, 30, 100, 10, false, None))
+            .unwrap();
+
+        // An aggregate that drifted below the order's own reserve clamps at 0
+        price_level.reserve_quantity = 40;
+        let match_result =
+            price_level.match_order(30, OrderId::from_u64(999), &transaction_id_generator);
+
+        assert!(match_result.is_complete);
+        assert_eq!(price_level.display_quantity(), 0);
+        assert_eq!(price_level.reserve_quantity(), 0);
+        assert_eq!(price_level.order_count(), 0);
+    }
+
+    #[test]
+    fn test_arithmetic_policy_at_the_boundary() {
+        assert_eq!(
+            PriceLevel::new(10000).arithmetic_policy(),
+            ArithmeticPolicy::Checked
+        );
+
+        let mut saturating =
+            PriceLevel::new(10000).with_arithmetic_policy(ArithmeticPolicy::Saturating);
+        saturating
+            .add_order(create_standard_order(1, 10000, u64::MAX))
+            .unwrap();
+        saturating
+            .add_order(create_standard_order(2, 10000, 2))
+            .unwrap();
+        assert_eq!(saturating.arithmetic_policy(), ArithmeticPolicy::Saturating);
+        assert_eq!(saturating.display_quantity(), u64::MAX);
+        saturating.cancel_order(OrderId::from_u64(1), CancelReason::UserRequested);
+        saturating.cancel_order(OrderId::from_u64(2), CancelReason::UserRequested);
+        assert_eq!(saturating.display_quantity(), 0);
+
+        // The wrapped aggregate no longer matches the queue until both orders are gone
+        let mut wrapping = PriceLevel::new(10000)
+            .with_arithmetic_policy(ArithmeticPolicy::Wrapping)
+            .with_drop_check(false);
+        wrapping
+            .add_order(create_standard_order(1, 10000, u64::MAX))
+            .unwrap();
+        wrapping
+            .add_order(create_standard_order(2, 10000, 2))
+            .unwrap();
+        assert_eq!(wrapping.display_quantity(), 1);
+        wrapping.cancel_order(OrderId::from_u64(1), CancelReason::UserRequested);
+        assert_eq!(wrapping.display_quantity(), 2);
+        wrapping.cancel_order(OrderId::from_u64(2), CancelReason::UserRequested);
+        assert_eq!(wrapping.display_quantity(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Aggregate overflow")]
+    fn test_checked_arithmetic_policy_panics_on_overflow() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, u64::MAX))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 1))
+            .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Aggregate overflow")]
+    fn test_from_orders_applies_the_arithmetic_policy() {
+        let _ = PriceLevel::from_orders(
+            10000,
+            vec![
+                create_standard_order(1, 10000, u64::MAX),
+                create_standard_order(2, 10000, 1),
+            ],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Order count underflow")]
+    fn test_checked_arithmetic_policy_panics_on_count_drift() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 50))
+            .unwrap();
+        price_level.order_count = 0;
+        price_level.cancel_order(OrderId::from_u64(1), CancelReason::UserRequested);
+    }
+
+    #[test]
+    fn test_saturating_arithmetic_policy_applies_match_results() {
+        let transaction_id_generator = create_transaction_id_generator();
+        let mut live = PriceLevel::new(10000);
+        live.add_order(create_standard_order(1, 10000, 50)).unwrap();
+        let before = live.snapshot();
+        let result = live.match_order(50, OrderId::from_u64(900), &transaction_id_generator);
+
+        // Aggregates that drifted below the fill clamp at 0 instead of panicking
+        let mut replayed = PriceLevel::from_snapshot(before)
+            .unwrap()
+            .with_arithmetic_policy(ArithmeticPolicy::Saturating);
+        replayed.display_quantity = 10;
+        replayed.order_count = 0;
+        apply_match_result(&mut replayed, &result).unwrap();
+        assert_eq!(replayed.display_quantity(), 0);
+        assert_eq!(replayed.order_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Aggregate underflow")]
+    fn test_checked_arithmetic_policy_panics_on_drift() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 50))
+            .unwrap();
+        price_level.display_quantity = 10;
+        price_level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: OrderId::from_u64(1),
+                new_quantity: 20,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_add_order_with_duplicate_id_keeps_aggregates() {
+        let mut price_level = PriceLevel::new(10000);
+        let transaction_id_generator = create_transaction_id_generator();
+
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 50, 200))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 40, 60))
+            .unwrap();
+
+        assert_eq!(price_level.display_quantity(), 40);
+        assert_eq!(price_level.reserve_quantity(), 60);
+        assert_eq!(price_level.order_count(), 1);
+
+        let match_result =
+            price_level.match_order(200, OrderId::from_u64(999), &transaction_id_generator);
+
+        assert_eq!(match_result.executed_quantity(), 100);
+        assert_eq!(price_level.display_quantity(), 0);
+        assert_eq!(price_level.reserve_quantity(), 0);
+        assert_eq!(price_level.order_count(), 0);
+    }
+
+    #[test]
+    fn test_price_level_from_snapshot_strict() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 200))
+            .unwrap();
+
+        let restored = PriceLevel::from_snapshot_strict(price_level.snapshot())
+            .expect("Consistent snapshot should restore");
+        assert_eq!(restored.display_quantity(), 150);
+        assert_eq!(restored.reserve_quantity(), 200);
+        assert_eq!(restored.order_count(), 2);
+
+        let mut corrupted = price_level.snapshot();
+        corrupted.reserve_quantity = 180;
+        let err = PriceLevel::from_snapshot_strict(corrupted.clone())
+            .expect_err("Inconsistent snapshot should be rejected");
+        assert!(matches!(
+            err,
+            PriceLevelError::InconsistentSnapshot {
+                ref field,
+                stored: 180,
+                computed: 200,
+            } if field == "reserve_quantity"
+        ));
+
+        // The lenient path keeps repairing the aggregates
+        let repaired = PriceLevel::from_snapshot(corrupted).unwrap();
+        assert_eq!(repaired.reserve_quantity(), 200);
+
+        let mut corrupted = price_level.snapshot();
+        corrupted.order_count = 3;
+        assert!(matches!(
+            PriceLevel::from_snapshot_strict(corrupted),
+            Err(PriceLevelError::InconsistentSnapshot { .. })
+        ));
+    }
+
+    #[test]
+    fn test_price_level_is_empty() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut price_level = PriceLevel::new(10000);
+        assert!(price_level.is_empty());
+
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        assert!(!price_level.is_empty());
+
+        price_level.match_order(100, OrderId::from_u64(999), &transaction_id_generator);
+        assert!(price_level.is_empty());
+    }
+
+    #[test]
+    fn test_fillable_quantity_honors_replenishment() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 200))
+            .unwrap();
+        price_level
+            .add_order(create_reserve_order(3, 10000, 20, 80, 5, true, None))
+            .unwrap();
+        // Without auto-replenish the reserve is discarded once the visible part fills
+        price_level
+            .add_order(create_reserve_order(4, 10000, 30, 70, 5, false, None))
+            .unwrap();
+
+        assert_eq!(price_level.total_quantity(), 550);
+        assert_eq!(price_level.fillable_quantity(), 480);
+
+        let transaction_id_generator = create_transaction_id_generator();
+        let match_result =
+            price_level.match_order(1000, OrderId::from_u64(999), &transaction_id_generator);
+        assert_eq!(match_result.executed_quantity(), 480);
+    }
+
+    #[test]
+    fn test_match_order_reports_depletion_order() {
+        let transaction_id_generator = create_transaction_id_generator();
+        let ids =
+            |ids: &[u64]| -> Vec<OrderId> { ids.iter().map(|&id| OrderId::from_u64(id)).collect() };
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 5, 5))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 10))
+            .unwrap();
+
+        // 1 is filled, 2 refreshes its slice and 3 is partially filled
+        let result = price_level.match_order(18, OrderId::from_u64(100), &transaction_id_generator);
+        assert_eq!(result.depletion_order, ids(&[1, 2, 3]));
+        assert_eq!(result.filled_order_ids, ids(&[1]));
+
+        // Both survivors went to the back in the order they were touched
+        let result = price_level.match_order(12, OrderId::from_u64(101), &transaction_id_generator);
+        assert_eq!(result.depletion_order, ids(&[2, 3]));
+        assert_eq!(result.filled_order_ids, ids(&[2, 3]));
+    }
+
+    #[test]
+    fn test_front_borrows_next_maker() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut price_level = PriceLevel::new(10000);
+        assert!(price_level.front().is_none());
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 20))
+            .unwrap();
+
+        let front = price_level.front().unwrap();
+        assert_eq!(front.id(), OrderId::from_u64(1));
+        assert_eq!(front, &price_level.iter_orders()[0]);
+
+        price_level.match_order(10, OrderId::from_u64(100), &transaction_id_generator);
+        assert_eq!(
+            price_level.front().map(|order| order.id()),
+            Some(OrderId::from_u64(2))
+        );
+
+        // With a comparator the front follows the comparator, not arrival
+        let mut price_level = PriceLevel::new(10000)
+            .with_comparator(|a, b| b.display_quantity().cmp(&a.display_quantity()));
+        price_level
+            .add_order(create_standard_order(3, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(4, 10000, 30))
+            .unwrap();
+        assert_eq!(price_level.front().unwrap().id(), OrderId::from_u64(4));
+    }
+
+    #[test]
+    fn test_next_to_match_follows_priority() {
+        let mut price_level = PriceLevel::new(10000)
+            .with_comparator(|a, b| b.display_quantity().cmp(&a.display_quantity()));
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 30))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 20))
+            .unwrap();
+        let before = price_level.snapshot();
+
+        for _ in 0..3 {
+            let ids: Vec<OrderId> = price_level
+                .next_to_match(2)
+                .iter()
+                .map(|order| order.id())
+                .collect();
+            assert_eq!(ids, vec![OrderId::from_u64(2), OrderId::from_u64(3)]);
+        }
+        assert_eq!(price_level.next_to_match(5).len(), 3);
+        assert!(price_level.next_to_match(0).is_empty());
+        assert_eq!(price_level.snapshot(), before);
+    }
+
+    #[test]
+    fn test_missing_ids_in_range() {
+        let mut price_level = PriceLevel::new(10000);
+        for id in [10, 11, 13, 14, 16, 17] {
+            price_level
+                .add_order(create_standard_order(id, 10000, 5))
+                .unwrap();
+        }
+
+        assert_eq!(price_level.missing_ids_in_range(10, 17), vec![12, 15]);
+        assert!(price_level.missing_ids_in_range(10, 12).is_empty());
+        assert!(price_level.missing_ids_in_range(14, 14).is_empty());
+        assert_eq!(price_level.missing_ids_in_range(8, 10), vec![8, 9]);
+    }
+
+    #[test]
+    fn test_snapshot_into_reuses_buffer() {
+        let mut price_level = PriceLevel::new(10000);
+        for id in 1..=4 {
+            price_level
+                .add_order(create_standard_order(id, 10000, 10))
+                .unwrap();
+        }
+        price_level
+            .add_order(create_iceberg_order(5, 10000, 10, 40))
+            .unwrap();
+
+        let mut snapshot = PriceLevelSnapshot::new(0);
+        price_level.snapshot_into(&mut snapshot);
+        assert_eq!(snapshot, price_level.snapshot());
+
+        // Refilling with fewer orders keeps the allocation
+        let buffer = snapshot.orders.as_ptr();
+        let capacity = snapshot.orders.capacity();
+        price_level.cancel_order(OrderId::from_u64(2), CancelReason::UserRequested);
+        price_level.snapshot_into(&mut snapshot);
+        assert_eq!(snapshot, price_level.snapshot());
+        assert_eq!(snapshot.orders.as_ptr(), buffer);
+        assert_eq!(snapshot.orders.capacity(), capacity);
+
+        PriceLevel::new(9990).snapshot_into(&mut snapshot);
+        assert_eq!(snapshot, PriceLevelSnapshot::new(9990));
+    }
+
+    #[test]
+    fn test_rebase_timestamps() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level.rebase_timestamps(5_000);
+        assert_eq!(price_level.timestamp_range(), None);
+
+        price_level
+            .add_order(create_standard_order(1, 10000, 10).with_timestamp(1_300))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 10).with_timestamp(1_000))
+            .unwrap();
+        let mut reserve =
+            create_reserve_order(3, 10000, 10, 50, 5, true, None).with_timestamp(1_200);
+        if let Order::ReserveOrder { last_reveal_ms, .. } = &mut reserve {
+            *last_reveal_ms = Some(1_250);
+        }
+        price_level.add_order(reserve).unwrap();
+
+        price_level.rebase_timestamps(90_000);
+        assert_eq!(price_level.timestamp_range(), Some((90_000, 90_300)));
+        let orders = price_level.iter_orders();
+        let ids: Vec<OrderId> = orders.iter().map(|order| order.id()).collect();
+        assert_eq!(ids, (1..=3).map(OrderId::from_u64).collect::<Vec<_>>());
+        let timestamps: Vec<u64> = orders.iter().map(|order| order.timestamp()).collect();
+        assert_eq!(timestamps, vec![90_300, 90_000, 90_200]);
+        assert!(matches!(
+            orders[2],
+            Order::ReserveOrder {
+                last_reveal_ms: Some(90_250),
+                ..
+            }
+        ));
+        assert!(price_level.aggregates_match_queue());
+    }
+
+    #[test]
+    fn test_size_percentile() {
+        let mut price_level = PriceLevel::new(10000);
+        assert_eq!(price_level.size_percentile(50), None);
+
+        for (id, quantity) in [(1, 40), (2, 10), (3, 30), (4, 20)] {
+            price_level
+                .add_order(create_standard_order(id, 10000, quantity))
+                .unwrap();
+        }
+        assert_eq!(price_level.size_percentile(0), Some(10));
+        assert_eq!(price_level.size_percentile(50), Some(20));
+        assert_eq!(price_level.size_percentile(51), Some(30));
+        assert_eq!(price_level.size_percentile(100), Some(40));
+        assert_eq!(price_level.size_percentile(255), Some(40));
+
+        // Reserve quantity does not count toward an order's size
+        price_level
+            .add_order(create_iceberg_order(5, 10000, 5, 500))
+            .unwrap();
+        assert_eq!(price_level.size_percentile(0), Some(5));
+        assert_eq!(price_level.size_percentile(50), Some(20));
+    }
+
+    #[test]
+    fn test_expire_day_orders() {
+        let session_end = 1_700_000_000_000;
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut price_level = PriceLevel::new(10000).with_event_sender(sender);
+        price_level
+            .add_order(create_day_order(1, 10, 40, session_end - 2))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 20).with_timestamp(session_end - 1))
+            .unwrap();
+        price_level
+            .add_order(create_day_order(3, 30, 0, session_end - 1))
+            .unwrap();
+        price_level
+            .add_order(create_day_order(4, 5, 0, session_end))
+            .unwrap();
+        receiver.try_iter().for_each(drop);
+
+        let expired = price_level.expire_day_orders(session_end);
+        let ids: Vec<OrderId> = expired.iter().map(|order| order.id()).collect();
+        assert_eq!(ids, vec![OrderId::from_u64(1), OrderId::from_u64(3)]);
+        assert_eq!(expired[0].reserve_quantity(), 40);
+
+        // Both the visible and hidden parts of the iceberg left the aggregates
+        assert_eq!(price_level.order_count(), 2);
+        assert_eq!(price_level.display_quantity(), 25);
+        assert_eq!(price_level.reserve_quantity(), 0);
+        assert_eq!(price_level.stats().orders_removed(), 2);
+        assert!(price_level.aggregates_match_queue());
+        assert!(receiver.try_iter().all(|event| matches!(
+            event,
+            LevelEvent::OrderCancelled {
+                reason: CancelReason::Expired,
+                ..
+            }
+        )));
+
+        assert!(price_level.expire_day_orders(session_end).is_empty());
+    }
+
+    #[test]
+    fn test_purge_expired_and_match_order_at() {
+        let generator = UuidGenerator::new(Uuid::nil());
+        let now = 1_700_000_000_000;
+        let build = || {
+            let mut price_level = PriceLevel::new(10000);
+            price_level
+                .add_order(create_good_till_date_order(1, 10000, 10, now - 1))
+                .unwrap();
+            price_level
+                .add_order(create_standard_order(2, 10000, 20))
+                .unwrap();
+            price_level
+                .add_order(create_good_till_date_order(3, 10000, 30, now))
+                .unwrap();
+            price_level
+                .add_order(create_good_till_date_order(4, 10000, 40, now + 1))
+                .unwrap();
+            price_level
+        };
+
+        // Expired orders are skipped, never traded, and keep their place
+        let mut price_level = build();
+        let result = price_level.match_order_at(100, OrderId::from_u64(100), &generator, now);
+        let makers: Vec<OrderId> = result
+            .transactions
+            .as_vec()
+            .iter()
+            .map(|transaction| transaction.maker_order_id)
+            .collect();
+        assert_eq!(makers, vec![OrderId::from_u64(2), OrderId::from_u64(4)]);
+        assert_eq!(result.remaining_quantity, 40);
+        let ids: Vec<OrderId> = price_level.iter_orders().iter().map(|o| o.id()).collect();
+        assert_eq!(ids, vec![OrderId::from_u64(1), OrderId::from_u64(3)]);
+
+        let purged = price_level.purge_expired(now);
+        assert_eq!(purged.len(), 2);
+        assert!(price_level.is_empty());
+        assert_eq!(price_level.display_quantity(), 0);
+
+        // Purging only removes orders expired at the given time
+        let mut price_level = build();
+        let purged = price_level.purge_expired(now - 1);
+        assert_eq!(
+            purged.iter().map(|o| o.id()).collect::<Vec<_>>(),
+            vec![OrderId::from_u64(1)]
+        );
+        assert_eq!(price_level.order_count(), 3);
+        assert_eq!(price_level.display_quantity(), 90);
+        assert_eq!(price_level.stats().orders_removed(), 1);
+        assert!(price_level.purge_expired(now - 1).is_empty());
+    }
+
+    #[test]
+    fn test_execution_price() {
+        let mut price_level = PriceLevel::new(10000);
+        assert_eq!(price_level.execution_price(0), Some(10000));
+        assert_eq!(price_level.execution_price(1), None);
+
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        // Only the revealable part of this reserve order's hidden quantity is fillable
+        price_level
+            .add_order(create_reserve_order(2, 10000, 30, 70, 5, false, None))
+            .unwrap();
+        assert_eq!(price_level.fillable_quantity(), 130);
+        assert_eq!(price_level.execution_price(50), Some(10000));
+        assert_eq!(price_level.execution_price(130), Some(10000));
+        assert_eq!(price_level.execution_price(131), None);
+
+        // Across several ticks the price is the truncated volume-weighted average
+        let tranches = [(100, 10), (102, 10)];
+        assert_eq!(volume_weighted_price(&tranches, 10), Some(100));
+        assert_eq!(volume_weighted_price(&tranches, 15), Some(100));
+        assert_eq!(volume_weighted_price(&tranches, 20), Some(101));
+        assert_eq!(volume_weighted_price(&tranches, 21), None);
+    }
+
+    #[test]
+    fn test_index_contribution() {
+        let mut bid = PriceLevel::new(10000);
+        assert_eq!(bid.index_contribution(0.5), 0.0);
+
+        bid.add_order(create_standard_order(1, 10000, 100)).unwrap();
+        // Reserve quantity is not part of the contribution
+        bid.add_order(create_reserve_order(2, 10000, 50, 200, 5, true, None))
+            .unwrap();
+        assert_eq!(bid.index_contribution(1.0), 1_500_000.0);
+        assert_eq!(bid.index_contribution(0.5), 750_000.0);
+
+        let mut ask = PriceLevel::new(10010);
+        ask.add_order(create_iceberg_order(3, 10010, 10, 90))
+            .unwrap();
+        let levels = [bid, ask, PriceLevel::new(9990)];
+        assert_eq!(sum_contributions(&levels, 2.0), 3_000_000.0 + 200_200.0);
+        assert_eq!(sum_contributions(&[], 2.0), 0.0);
+    }
+
+    #[test]
+    fn test_match_order_min_age_skips_fresh_orders() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let now = 1_000_000;
+        let mut price_level = PriceLevel::new(10000);
+        // Just added, ahead of an order that has rested long enough
+        price_level
+            .add_order(create_standard_order(1, 10000, 10).with_timestamp(now - 100))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 10).with_timestamp(now - 500))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 10).with_timestamp(now - 1000))
+            .unwrap();
+
+        let result = price_level.match_order_min_age(
+            25,
+            OrderId::from_u64(100),
+            &transaction_id_generator,
+            500,
+            now,
+        );
+        assert_eq!(
+            result.filled_order_ids,
+            vec![OrderId::from_u64(2), OrderId::from_u64(3)]
+        );
+        assert_eq!(result.executed_quantity(), 20);
+        assert_eq!(result.remaining_quantity, 5);
+        assert!(!result.is_complete);
+
+        // The fresh order kept its place and trades once it is old enough
+        assert_eq!(
+            price_level.front().map(|order| order.id()),
+            Some(OrderId::from_u64(1))
+        );
+        assert_eq!(price_level.display_quantity(), 10);
+        let result = price_level.match_order_min_age(
+            5,
+            OrderId::from_u64(101),
+            &transaction_id_generator,
+            500,
+            now + 400,
+        );
+        assert!(result.is_complete);
+        assert_eq!(
+            result.transactions.as_vec()[0].maker_order_id,
+            OrderId::from_u64(1)
+        );
+    }
+
+    #[test]
+    fn test_match_order_capped_per_maker_round_robin() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut price_level = PriceLevel::new(10000);
+        for id in 1..=3 {
+            price_level
+                .add_order(create_standard_order(id, 10000, 10))
+                .unwrap();
+        }
+
+        let result = price_level.match_order_capped_per_maker(
+            24,
+            OrderId::from_u64(100),
+            &transaction_id_generator,
+            4,
+        );
+        assert!(result.is_complete);
+        let fills: Vec<(OrderId, u64)> = result
+            .transactions
+            .as_vec()
+            .iter()
+            .map(|t| (t.maker_order_id, t.quantity))
+            .collect();
+        let expected: Vec<(OrderId, u64)> = [1, 2, 3, 1, 2, 3]
+            .into_iter()
+            .map(|id| (OrderId::from_u64(id), 4))
+            .collect();
+        assert_eq!(fills, expected);
+        assert_eq!(price_level.display_quantity(), 6);
+        assert!(
+            price_level
+                .iter_orders()
+                .iter()
+                .all(|o| o.display_quantity() == 2)
+        );
+
+        // Once every maker is filled the rest of the incoming quantity is left over
+        let result = price_level.match_order_capped_per_maker(
+            10,
+            OrderId::from_u64(101),
+            &transaction_id_generator,
+            4,
+        );
+        assert_eq!(result.executed_quantity(), 6);
+        assert_eq!(result.remaining_quantity, 4);
+        assert_eq!(result.filled_order_ids.len(), 3);
+        assert!(price_level.is_empty());
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 10))
+            .unwrap();
+        price_level.match_order(10, OrderId::from_u64(102), &transaction_id_generator);
+
+        // A zero cap trades nothing and leaves no removed orders from the previous match
+        let result = price_level.match_order_capped_per_maker(
+            5,
+            OrderId::from_u64(103),
+            &transaction_id_generator,
+            0,
+        );
+        assert_eq!(result.executed_quantity(), 0);
+        assert_eq!(price_level.display_quantity(), 10);
+        assert!(price_level.take_last_removed().is_empty());
+    }
+
+    #[test]
+    fn test_quantity_at_or_better() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 200))
+            .unwrap();
+
+        // Buy taker: the level must be priced at or below the limit
+        assert_eq!(price_level.quantity_at_or_better(10000, Side::Buy), 350);
+        assert_eq!(price_level.quantity_at_or_better(10100, Side::Buy), 350);
+        assert_eq!(price_level.quantity_at_or_better(9900, Side::Buy), 0);
+
+        // Sell taker: the level must be priced at or above the limit
+        assert_eq!(price_level.quantity_at_or_better(10000, Side::Sell), 350);
+        assert_eq!(price_level.quantity_at_or_better(9900, Side::Sell), 350);
+        assert_eq!(price_level.quantity_at_or_better(10100, Side::Sell), 0);
+    }
+
+    #[test]
+    fn test_iter_orders_equal_timestamps_is_stable() {
+        let make_order = |id: u64| Order::Standard {
+            common: OrderCommon {
+                id: OrderId::from_u64(id),
+                price: 10000,
+                display_quantity: 10,
+                side: Side::Sell,
+                timestamp: 1616823000000,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+        };
+
+        // Higher ID first, so neither ID nor timestamp could explain the ordering
+        let mut price_level = PriceLevel::new(10000);
+        price_level.add_order(make_order(2)).unwrap();
+        price_level.add_order(make_order(1)).unwrap();
+
+        let expected = vec![OrderId::from_u64(2), OrderId::from_u64(1)];
+        let ids = |level: &PriceLevel| -> Vec<OrderId> {
+            level.iter_orders().iter().map(|order| order.id()).collect()
+        };
+        assert_eq!(ids(&price_level), expected);
+
+        let json = price_level.snapshot_to_json().unwrap();
+        let restored = PriceLevel::from_snapshot_json(&json).unwrap();
+        assert_eq!(ids(&restored), expected);
+
+        let reparsed = PriceLevel::from_str(&restored.to_string()).unwrap();
+        assert_eq!(ids(&reparsed), expected);
+
+        let transaction_id_generator = create_transaction_id_generator();
+        let mut restored = restored;
+        let match_result =
+            restored.match_order(10, OrderId::from_u64(999), &transaction_id_generator);
+        assert_eq!(match_result.filled_order_ids, vec![OrderId::from_u64(2)]);
+    }
+
+    #[test]
+    fn test_half_spread_to() {
+        let price_level = PriceLevel::new(10000);
+        assert_eq!(price_level.half_spread_to(9950), 50);
+        assert_eq!(price_level.half_spread_to(10050), -50);
+        assert_eq!(price_level.half_spread_to(10000), 0);
+        assert_eq!(PriceLevel::new(u64::MAX).half_spread_to(0), i64::MAX);
+        assert_eq!(PriceLevel::new(0).half_spread_to(u64::MAX), i64::MIN);
+    }
+
+    #[test]
+    fn test_is_inside() {
+        let bid = PriceLevel::new(10000);
+        assert!(bid.is_inside(10010, Side::Buy));
+        assert!(!bid.is_inside(10000, Side::Buy));
+        assert!(!bid.is_inside(9990, Side::Buy));
+
+        let ask = PriceLevel::new(10010);
+        assert!(ask.is_inside(10000, Side::Sell));
+        assert!(!ask.is_inside(10010, Side::Sell));
+        assert!(!ask.is_inside(10020, Side::Sell));
+    }
+
+    #[test]
+    fn test_snapshot_with_stats_roundtrip() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 50))
+            .unwrap();
+        price_level.match_order(120, OrderId::from_u64(999), &transaction_id_generator);
+
+        let json = price_level
+            .snapshot_with_stats()
+            .unwrap()
+            .to_json()
+            .unwrap();
+        let package = PriceLevelSnapshotWithStats::from_json(&json).unwrap();
+        let restored = PriceLevel::from_snapshot_with_stats(package).unwrap();
+
+        assert_eq!(restored.display_quantity(), 30);
+        assert_eq!(restored.order_count(), 1);
+        assert_eq!(restored.stats().orders_added(), 2);
+        assert_eq!(restored.stats().orders_executed(), 2);
+        assert_eq!(restored.stats().quantity_executed(), 120);
+        assert_eq!(restored.stats().value_executed(), 1_200_000);
+
+        // The stats-less path still starts from fresh counters
+        let plain =
+            PriceLevel::from_snapshot_json(&price_level.snapshot_to_json().unwrap()).unwrap();
+        assert_eq!(plain.stats().orders_added(), 0);
+    }
+
+    #[test]
+    fn test_snapshot_bytes_matches_json() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+
+        let bytes = price_level.snapshot_bytes().unwrap();
+        assert_eq!(bytes, price_level.snapshot_to_json().unwrap().into_bytes());
+
+        let package: PriceLevelSnapshotPackage = serde_json::from_slice(&bytes).unwrap();
+        let restored = PriceLevel::from_snapshot_package(package).unwrap();
+        assert_eq!(restored.display_quantity(), 100);
+    }
+
+    #[test]
+    fn test_from_orders() {
+        let orders = vec![
+            create_standard_order(1, 10000, 100),
+            create_iceberg_order(2, 10000, 50, 150),
+            create_standard_order(3, 10000, 25),
+        ];
+
+        let price_level = PriceLevel::from_orders(10000, orders).unwrap();
+        assert_eq!(price_level.price(), 10000);
+        assert_eq!(price_level.display_quantity(), 175);
+        assert_eq!(price_level.reserve_quantity(), 150);
+        assert_eq!(price_level.order_count(), 3);
+        let ids: Vec<OrderId> = price_level.iter_orders().iter().map(|o| o.id()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                OrderId::from_u64(1),
+                OrderId::from_u64(2),
+                OrderId::from_u64(3)
+            ]
+        );
+
+        let mismatched = vec![
+            create_standard_order(1, 10000, 100),
+            create_standard_order(2, 10001, 100),
+        ];
+        match PriceLevel::from_orders(10000, mismatched) {
+            Err(PriceLevelError::InvalidFieldValue { field, .. }) => assert_eq!(field, "price"),
+            other => panic!("expected InvalidFieldValue, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_order_at_cumulative() {
+        let mut price_level = PriceLevel::new(10000);
+        assert!(price_level.order_at_cumulative(1).is_none());
+
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 500))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 30))
+            .unwrap();
+
+        let (order, into) = price_level.order_at_cumulative(40).unwrap();
+        assert_eq!(order.id(), OrderId::from_u64(1));
+        assert_eq!(into, 40);
+
+        // Exactly exhausting the first order still lands in it
+        let (order, into) = price_level.order_at_cumulative(100).unwrap();
+        assert_eq!(order.id(), OrderId::from_u64(1));
+        assert_eq!(into, 100);
+
+        let (order, into) = price_level.order_at_cumulative(120).unwrap();
+        assert_eq!(order.id(), OrderId::from_u64(2));
+        assert_eq!(into, 20);
+
+        let (order, into) = price_level.order_at_cumulative(180).unwrap();
+        assert_eq!(order.id(), OrderId::from_u64(3));
+        assert_eq!(into, 30);
+
+        // Past the visible size, the refreshed iceberg is hit again from the back
+        let (order, into) = price_level.order_at_cumulative(181).unwrap();
+        assert_eq!(order.id(), OrderId::from_u64(2));
+        assert_eq!(into, 51);
+
+        // Beyond everything matching could reveal
+        assert!(price_level.order_at_cumulative(680).is_some());
+        assert!(price_level.order_at_cumulative(681).is_none());
+
+        let (order, into) = price_level.order_at_cumulative(0).unwrap();
+        assert_eq!(order.id(), OrderId::from_u64(1));
+        assert_eq!(into, 0);
+    }
+
+    #[test]
+    fn test_tick_replenish_reveals_on_timer() {
+        let mut order = create_reserve_order(1, 10000, 10, 100, 0, false, Some(20));
+        if let Order::ReserveOrder {
+            common,
+            auto_replenish_interval_ms,
+            ..
+        } = &mut order
+        {
+            common.timestamp = 1_000;
+            *auto_replenish_interval_ms = Some(500);
+        }
+
+        // Round-trips through both text and JSON representations
+        let parsed = Order::<()>::from_str(&order.to_string()).unwrap();
+        assert_eq!(parsed, order);
+        let json = serde_json::to_string(&order).unwrap();
+        assert_eq!(serde_json::from_str::<Order<()>>(&json).unwrap(), order);
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level.add_order(order).unwrap();
+        price_level
+            .add_order(create_reserve_order(2, 10000, 10, 100, 0, false, Some(20)))
+            .unwrap();
+
+        // Before the first interval elapses nothing is revealed
+        assert_eq!(price_level.tick_replenish(1_499), 0);
+        assert_eq!(price_level.display_quantity(), 20);
+
+        assert_eq!(price_level.tick_replenish(1_500), 20);
+        assert_eq!(price_level.display_quantity(), 40);
+        assert_eq!(price_level.reserve_quantity(), 180);
+
+        // The interval restarts from the last reveal
+        assert_eq!(price_level.tick_replenish(1_900), 0);
+        assert_eq!(price_level.tick_replenish(2_000), 20);
+
+        let orders = price_level.iter_orders();
+        assert_eq!(orders[0].id(), OrderId::from_u64(1));
+        assert_eq!(orders[0].display_quantity(), 50);
+        assert_eq!(orders[0].reserve_quantity(), 60);
+        assert_eq!(orders[1].display_quantity(), 10);
+        assert_eq!(price_level.display_quantity(), 60);
+        assert_eq!(price_level.reserve_quantity(), 160);
+    }
+
+    #[test]
+    fn test_match_with_participation_cap_binds() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 100))
+            .unwrap();
+
+        // 10% of 500 recent volume caps the fill at 50
+        let result = price_level.match_with_participation(
+            120,
+            OrderId::from_u64(999),
+            &transaction_id_generator,
+            500,
+            10,
+        );
+        assert_eq!(result.executed_quantity(), 50);
+        assert_eq!(result.remaining_quantity, 70);
+        assert!(!result.is_complete);
+        assert_eq!(result.participation_cap, Some(50));
+        assert_eq!(price_level.display_quantity(), 150);
+
+        let parsed = MatchResult::from_str(&result.to_string()).unwrap();
+        assert_eq!(parsed.participation_cap, Some(50));
+
+        // A cap that does not bind behaves like a plain match
+        let result = price_level.match_with_participation(
+            30,
+            OrderId::from_u64(1000),
+            &transaction_id_generator,
+            500,
+            10,
+        );
+        assert_eq!(result.executed_quantity(), 30);
+        assert!(result.is_complete);
+        assert_eq!(result.participation_cap, None);
+    }
+
+    #[test]
+    fn test_cancel_all() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 150))
+            .unwrap();
+
+        assert_eq!(price_level.cancel_all(CancelReason::SessionRollover), 2);
+        assert!(price_level.is_empty());
+        assert_eq!(price_level.display_quantity(), 0);
+        assert_eq!(price_level.reserve_quantity(), 0);
+        assert_eq!(price_level.stats().orders_removed(), 2);
+
+        price_level
+            .add_order(create_standard_order(3, 10000, 10))
+            .unwrap();
+        assert_eq!(price_level.order_count(), 1);
+        assert_eq!(price_level.display_quantity(), 10);
+    }
+
+    #[test]
+    fn test_children_of() {
+        let parent = OrderId::from_u64(100);
+        let child = |id: u64, quantity: u64| {
+            let mut order = create_standard_order(id, 10000, quantity);
+            if let Order::Standard { common } = &mut order {
+                common.parent_id = Some(parent);
+            }
+            order
+        };
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level.add_order(child(1, 10)).unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 20))
+            .unwrap();
+        price_level.add_order(child(3, 30)).unwrap();
+
+        let children = price_level.children_of(parent);
+        let ids: Vec<OrderId> = children.iter().map(|o| o.id()).collect();
+        assert_eq!(ids, vec![OrderId::from_u64(1), OrderId::from_u64(3)]);
+        assert!(price_level.children_of(OrderId::from_u64(2)).is_empty());
+
+        // The link survives a snapshot round trip
+        let restored =
+            PriceLevel::from_snapshot_json(&price_level.snapshot_to_json().unwrap()).unwrap();
+        assert_eq!(restored.children_of(parent).len(), 2);
+    }
+
+    #[test]
+    fn test_try_match_fok() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        // The iceberg's hidden quantity is revealed during matching, so it counts
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 20))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 10, 50))
+            .unwrap();
+        assert!(
+            price_level
+                .try_match_fok(81, OrderId::from_u64(999), &transaction_id_generator)
+                .is_none()
+        );
+        assert_eq!(price_level.total_quantity(), 80);
+        assert_eq!(price_level.order_count(), 2);
+        assert_eq!(price_level.stats().orders_executed(), 0);
+
+        let result = price_level
+            .try_match_fok(70, OrderId::from_u64(999), &transaction_id_generator)
+            .unwrap();
+        assert!(result.is_complete);
+        assert_eq!(result.executed_quantity(), 70);
+        assert_eq!(price_level.total_quantity(), 10);
+
+        // A reserve order without auto-replenish never reveals its reserve
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_reserve_order(1, 10000, 30, 100, 10, false, None))
+            .unwrap();
+        let before = price_level.snapshot();
+        assert!(
+            price_level
+                .try_match_fok(31, OrderId::from_u64(999), &transaction_id_generator)
+                .is_none()
+        );
+        assert_eq!(price_level.snapshot(), before);
+        assert!(
+            price_level
+                .try_match_fok(30, OrderId::from_u64(999), &transaction_id_generator)
+                .is_some()
+        );
+        assert!(price_level.is_empty());
+
+        // An iceberg left with nothing displayed is passed over instead of requeued forever
+        let mut price_level =
+            PriceLevel::from_orders(10000, vec![create_iceberg_order(1, 10000, 5, 0)]).unwrap();
+        price_level
+            .orders
+            .push(create_iceberg_order(4, 10000, 0, 50));
+        price_level.reserve_quantity += 50;
+        price_level.order_count += 1;
+        assert_eq!(
+            price_level.fill_distribution(10),
+            vec![(OrderId::from_u64(1), 5)]
+        );
+        assert!(
+            price_level
+                .try_match_fok(10, OrderId::from_u64(999), &transaction_id_generator)
+                .is_none()
+        );
+        assert_eq!(price_level.total_quantity(), 55);
+        assert_eq!(price_level.order_count(), 2);
+    }
+
+    #[test]
+    fn test_fill_distribution_matches_match_order() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 10, 30))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 15))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 50))
+            .unwrap();
+
+        // A refreshed iceberg rejoins the back of the queue
+        assert_eq!(
+            price_level.fill_distribution(60),
+            vec![
+                (OrderId::from_u64(1), 10),
+                (OrderId::from_u64(2), 15),
+                (OrderId::from_u64(3), 35),
+            ]
+        );
+
+        // Repeated refreshes of the same maker are summed into one entry
+        let distribution = price_level.fill_distribution(100);
+        assert_eq!(
+            distribution,
+            vec![
+                (OrderId::from_u64(1), 35),
+                (OrderId::from_u64(2), 15),
+                (OrderId::from_u64(3), 50),
+            ]
+        );
+
+        // Read-only: nothing changed
+        assert_eq!(price_level.display_quantity(), 75);
+        assert_eq!(price_level.reserve_quantity(), 30);
+
+        let result =
+            price_level.match_order(100, OrderId::from_u64(999), &transaction_id_generator);
+        let by_maker = result.by_maker();
+        for (maker, quantity) in distribution {
+            let executed: u64 = by_maker[&maker].iter().map(|t| t.quantity).sum();
+            assert_eq!(executed, quantity);
+        }
+    }
+
+    #[test]
+    fn test_comparator_levels_resort_in_place_changes() {
+        let transaction_id_generator = create_transaction_id_generator();
+        let largest_first =
+            |a: &Order<()>, b: &Order<()>| b.display_quantity().cmp(&a.display_quantity());
+        let ids = |level: &PriceLevel| -> Vec<OrderId> {
+            level.iter_orders().iter().map(|o| o.id()).collect()
+        };
+
+        // A refreshed iceberg is re-sorted ahead of the smaller order, in the dry run as well
+        let mut price_level = PriceLevel::new(10000).with_comparator(largest_first);
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 30, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 20, 0))
+            .unwrap();
+        let distribution = price_level.fill_distribution(40);
+        assert_eq!(distribution, vec![(OrderId::from_u64(1), 40)]);
+        let result = price_level.match_order(40, OrderId::from_u64(999), &transaction_id_generator);
+        let by_maker = result.by_maker();
+        assert_eq!(by_maker.len(), 1);
+        let executed: u64 = by_maker[&OrderId::from_u64(1)]
+            .iter()
+            .map(|t| t.quantity)
+            .sum();
+        assert_eq!(executed, 40);
+
+        // A priority-preserving replacement that grows moves ahead
+        let mut price_level = PriceLevel::new(10000).with_comparator(largest_first);
+        price_level
+            .add_order(create_standard_order(3, 10000, 50))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(4, 10000, 10))
+            .unwrap();
+        price_level
+            .update_order(OrderUpdate::ReplaceFull {
+                order_id: OrderId::from_u64(4),
+                new_order: Box::new(create_standard_order(4, 10000, 60)),
+                preserve_priority: true,
+            })
+            .unwrap();
+        assert_eq!(ids(&price_level), [4, 3].map(OrderId::from_u64).to_vec());
+
+        // A timed reveal moves the reserve order ahead
+        let mut reserve = create_reserve_order(5, 10000, 10, 100, 0, false, Some(50));
+        if let Order::ReserveOrder {
+            common,
+            auto_replenish_interval_ms,
+            ..
+        } = &mut reserve
+        {
+            common.timestamp = 1_000;
+            *auto_replenish_interval_ms = Some(500);
+        }
+        let mut price_level = PriceLevel::new(10000).with_comparator(largest_first);
+        price_level.add_order(reserve).unwrap();
+        price_level
+            .add_order(create_standard_order(6, 10000, 30))
+            .unwrap();
+        assert_eq!(ids(&price_level), [6, 5].map(OrderId::from_u64).to_vec());
+        assert_eq!(price_level.tick_replenish(1_500), 50);
+        assert_eq!(ids(&price_level), [5, 6].map(OrderId::from_u64).to_vec());
+
+        // The coalesced survivor is re-sorted by its merged size
+        let mut price_level = PriceLevel::new(10000).with_comparator(largest_first);
+        price_level
+            .add_order(create_standard_order(7, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(8, 10000, 25))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(9, 10000, 30))
+            .unwrap();
+        let owner_of = |order: &Order<()>| (order.id() != OrderId::from_u64(9)).then_some(1);
+        let merged = price_level.coalesce_owner(1, owner_of).unwrap();
+        assert_eq!(merged.id(), OrderId::from_u64(7));
+        assert_eq!(ids(&price_level), [7, 9].map(OrderId::from_u64).to_vec());
+    }
+
+    #[test]
+    fn test_move_order_between_levels() {
+        let mut from = PriceLevel::new(10000);
+        let mut to = PriceLevel::new(10100);
+        from.add_order(create_iceberg_order(1, 10000, 50, 100))
+            .unwrap();
+        from.add_order(create_standard_order(2, 10000, 30)).unwrap();
+        to.add_order(create_standard_order(3, 10100, 20)).unwrap();
+
+        let moved = move_order(&mut from, &mut to, OrderId::from_u64(1), Some(40)).unwrap();
+        assert_eq!(moved.id(), OrderId::from_u64(1));
+        assert_eq!(moved.price(), 10100);
+        assert_eq!(moved.display_quantity(), 40);
+        assert_eq!(moved.reserve_quantity(), 100);
+
+        assert_eq!(from.order_count(), 1);
+        assert_eq!(from.display_quantity(), 30);
+        assert_eq!(from.reserve_quantity(), 0);
+        assert_eq!(from.stats().orders_removed(), 1);
+
+        assert_eq!(to.order_count(), 2);
+        assert_eq!(to.display_quantity(), 60);
+        assert_eq!(to.reserve_quantity(), 100);
+        assert_eq!(to.iter_orders()[1].id(), OrderId::from_u64(1));
+
+        // Keeping the quantity
+        let moved = move_order(&mut from, &mut to, OrderId::from_u64(2), None).unwrap();
+        assert_eq!(moved.display_quantity(), 30);
+        assert!(from.is_empty());
+
+        // Not found
+        assert!(matches!(
+            move_order(&mut from, &mut to, OrderId::from_u64(99), None),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+
+        // Same price
+        let mut twin = PriceLevel::new(10100);
+        assert!(matches!(
+            move_order(&mut to, &mut twin, OrderId::from_u64(3), None),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert_eq!(to.order_count(), 3);
+
+        // Zero quantity
+        assert!(matches!(
+            move_order(&mut to, &mut from, OrderId::from_u64(3), Some(0)),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert_eq!(to.order_count(), 3);
+        assert!(from.is_empty());
+
+        // The destination's limits apply, and a rejected move leaves the order in place
+        let mut capped = PriceLevel::new(10200).with_lot_size(7);
+        assert!(matches!(
+            move_order(&mut to, &mut capped, OrderId::from_u64(3), None),
+            Err(PriceLevelError::InvalidLotSize { .. })
+        ));
+        assert_eq!(to.order_count(), 3);
+        assert!(capped.is_empty());
+    }
+
+    #[test]
+    fn test_orders_by_priority_follows_matching() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 10, 40))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 20))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 30))
+            .unwrap();
+
+        // Refreshing the iceberg sends it to the back of the queue
+        price_level.match_order(10, OrderId::from_u64(999), &transaction_id_generator);
+
+        let priority: Vec<OrderId> = price_level
+            .orders_by_priority()
+            .iter()
+            .map(|order| order.id())
+            .collect();
+        assert_eq!(
+            priority,
+            vec![
+                OrderId::from_u64(2),
+                OrderId::from_u64(3),
+                OrderId::from_u64(1)
+            ]
+        );
+
+        // The next match consumes makers in exactly that order
+        let result =
+            price_level.match_order(60, OrderId::from_u64(1000), &transaction_id_generator);
+        let makers: Vec<OrderId> = result
+            .transactions
+            .as_vec()
+            .iter()
+            .map(|t| t.maker_order_id)
+            .collect();
+        assert_eq!(makers, priority);
+    }
+
+    #[test]
+    fn test_cancel_order_with_reason() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 50, 100))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 30))
+            .unwrap();
+
+        let removed = price_level
+            .cancel_order(OrderId::from_u64(1), CancelReason::RiskTrip)
+            .unwrap();
+        assert_eq!(removed.id(), OrderId::from_u64(1));
+        assert_eq!(price_level.display_quantity(), 30);
+        assert_eq!(price_level.reserve_quantity(), 0);
+        assert_eq!(price_level.order_count(), 1);
+        assert_eq!(price_level.stats().orders_removed(), 1);
+
+        assert!(
+            price_level
+                .cancel_order(OrderId::from_u64(1), CancelReason::RiskTrip)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_pressure() {
+        let mut price_level = PriceLevel::new(10000);
+        assert_eq!(price_level.pressure(), 0);
+
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        for id in 2..=5 {
+            price_level
+                .add_order(create_standard_order(id, 10000, 1))
+                .unwrap();
+        }
+
+        assert_eq!(price_level.pressure(), 20);
+        assert_eq!(
+            price_level.pressure_with(PressureFormula::OrdersPerThousand),
+            48
+        );
+        assert_eq!(
+            price_level.pressure_with(PressureFormula::WeightedQuantity),
+            520
+        );
+    }
+
+    #[test]
+    fn test_match_order_sided_skips_same_side_makers() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        // Buy resting orders interleaved with a sell (iceberg helpers are sells)
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 20, 0))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 30))
+            .unwrap();
+
+        let result = price_level.match_order_sided(
+            35,
+            OrderId::from_u64(999),
+            &transaction_id_generator,
+            Side::Buy,
+        );
+
+        assert_eq!(result.executed_quantity(), 20);
+        assert_eq!(result.remaining_quantity, 15);
+        assert_eq!(result.filled_order_ids, vec![OrderId::from_u64(2)]);
+        for transaction in result.transactions.as_vec() {
+            assert_eq!(transaction.maker_order_id, OrderId::from_u64(2));
+            assert_eq!(transaction.taker_side, Side::Buy);
+        }
+
+        // Same-side orders are untouched and keep their priority
+        let ids: Vec<OrderId> = price_level.iter_orders().iter().map(|o| o.id()).collect();
+        assert_eq!(ids, vec![OrderId::from_u64(1), OrderId::from_u64(3)]);
+        assert_eq!(price_level.display_quantity(), 40);
+    }
+
+    #[test]
+    fn test_take_last_removed() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 20))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 30))
+            .unwrap();
+        assert!(price_level.take_last_removed().is_empty());
+
+        price_level.match_order(35, OrderId::from_u64(999), &transaction_id_generator);
+        let removed = price_level.take_last_removed();
+        let ids: Vec<OrderId> = removed.iter().map(|o| o.id()).collect();
+        assert_eq!(ids, vec![OrderId::from_u64(1), OrderId::from_u64(2)]);
+        assert_eq!(removed[1].display_quantity(), 20);
+
+        // Taking clears the buffer
+        assert!(price_level.take_last_removed().is_empty());
+
+        // Each match replaces the buffer; a partial fill removes nothing
+        price_level.match_order(35, OrderId::from_u64(1000), &transaction_id_generator);
+        price_level.match_order(5, OrderId::from_u64(1001), &transaction_id_generator);
+        assert!(price_level.take_last_removed().is_empty());
+    }
+
+    #[test]
+    fn test_lot_size_validation() {
+        let mut price_level = PriceLevel::new(10000).with_lot_size(10);
+        assert_eq!(price_level.lot_size(), 10);
+
+        price_level
+            .add_order(create_standard_order(1, 10000, 50))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 20, 100))
+            .unwrap();
+        assert_eq!(price_level.total_lots(), 17);
+
+        // Odd lot in the display quantity
+        match price_level.add_order(create_standard_order(3, 10000, 55)) {
+            Err(PriceLevelError::InvalidLotSize { quantity, lot_size }) => {
+                assert_eq!(quantity, 55);
+                assert_eq!(lot_size, 10);
+            }
+            other => panic!("expected InvalidLotSize, got {other:?}"),
+        }
+        // Odd lot hidden in the reserve
+        assert!(matches!(
+            price_level.add_order(create_iceberg_order(4, 10000, 20, 105)),
+            Err(PriceLevelError::InvalidLotSize { quantity: 105, .. })
+        ));
+        assert_eq!(price_level.order_count(), 2);
+
+        // The default lot size of 1 accepts anything
+        let mut default_level = PriceLevel::new(10000);
+        assert_eq!(default_level.lot_size(), 1);
+        assert!(
+            default_level
+                .add_order(create_standard_order(5, 10000, 55))
+                .is_ok()
+        );
+        assert_eq!(PriceLevel::new(10000).with_lot_size(0).lot_size(), 1);
+    }
+
+    #[test]
+    fn test_builders_combine() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut price_level = PriceLevel::new(10000)
+            .with_lot_size(10)
+            .with_event_sender(sender)
+            .with_arithmetic_policy(ArithmeticPolicy::Saturating);
+        assert_eq!(price_level.lot_size(), 10);
+        assert_eq!(
+            price_level.arithmetic_policy(),
+            ArithmeticPolicy::Saturating
+        );
+
+        price_level
+            .add_order(create_standard_order(1, 10000, 50))
+            .unwrap();
+        assert!(
+            price_level
+                .add_order(create_standard_order(2, 10000, 55))
+                .is_err()
+        );
+        let events: Vec<LevelEvent> = receiver.try_iter().collect();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], LevelEvent::OrderAdded { .. }));
+
+        // A comparator set after orders rest re-sorts them
+        let mut sorted = PriceLevel::new(10000);
+        sorted
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        sorted
+            .add_order(create_standard_order(2, 10000, 30))
+            .unwrap();
+        let sorted = sorted.with_comparator(|a, b| b.display_quantity().cmp(&a.display_quantity()));
+        assert_eq!(sorted.front().unwrap().id(), OrderId::from_u64(2));
+        assert_eq!(sorted.order_count(), 2);
+    }
+
+    #[test]
+    fn test_orders_owned_is_detached() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 20, 40))
+            .unwrap();
+
+        let mut owned = price_level.orders_owned();
+        assert_eq!(owned, price_level.snapshot().orders);
+
+        // Mutating the copies leaves the level untouched
+        owned[0] = owned[0].with_reduced_quantity(1);
+        assert_eq!(price_level.iter_orders()[0].display_quantity(), 10);
+    }
+
+    #[test]
+    fn test_price_band_rejects_fat_fingers() {
+        // 50 bps around a 10000 reference accepts 9950..=10050
+        let mut price_level = PriceLevel::new(10050).with_price_band(10000, 50);
+        assert!(
+            price_level
+                .add_order(create_standard_order(1, 10050, 10))
+                .is_ok()
+        );
+
+        let mut outside = PriceLevel::new(10051).with_price_band(10000, 50);
+        match outside.add_order(create_standard_order(2, 10051, 10)) {
+            Err(PriceLevelError::PriceOutOfBand {
+                price,
+                reference,
+                band_bps,
+            }) => {
+                assert_eq!((price, reference, band_bps), (10051, 10000, 50));
+            }
+            other => panic!("expected PriceOutOfBand, got {other:?}"),
+        }
+        assert!(outside.is_empty());
+
+        let mut lower_edge = PriceLevel::new(9950).with_price_band(10000, 50);
+        assert!(
+            lower_edge
+                .add_order(create_standard_order(3, 9950, 10))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_restored_replenishing_orders_match_like_originals() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut timed_reserve = create_reserve_order(3, 10000, 15, 90, 5, true, Some(12));
+        if let Order::ReserveOrder {
+            auto_replenish_interval_ms,
+            last_reveal_ms,
+            ..
+        } = &mut timed_reserve
+        {
+            *auto_replenish_interval_ms = Some(1_000);
+            *last_reveal_ms = Some(5_000);
+        }
+
+        let mut live = PriceLevel::new(10000);
+        live.add_order(create_iceberg_order(1, 10000, 20, 100))
+            .unwrap();
+        live.add_order(create_reserve_order(2, 10000, 10, 50, 4, true, Some(7)))
+            .unwrap();
+        live.add_order(timed_reserve).unwrap();
+
+        // Partially reveal and partially consume the hidden quantities
+        live.match_order(27, OrderId::from_u64(900), &transaction_id_generator);
+        live.match_order(13, OrderId::from_u64(901), &transaction_id_generator);
+
+        let mut restored =
+            PriceLevel::from_snapshot_json(&live.snapshot_to_json().unwrap()).unwrap();
+        assert_eq!(restored.iter_orders(), live.iter_orders());
+        assert!(restored.iter_orders().iter().any(|order| matches!(
+            order,
+            Order::IcebergOrder {
+                last_reveal_ms: Some(_),
+                ..
+            }
+        )));
+
+        // Each level stamps its own reveals with the time of its executions
+        let without_reveal_times = |level: &PriceLevel| -> Vec<Order<()>> {
+            let mut orders = level.iter_orders();
+            for order in &mut orders {
+                if let Order::IcebergOrder { last_reveal_ms, .. } = order {
+                    *last_reveal_ms = None;
+                }
+            }
+            orders
+        };
+        let fills = |result: &MatchResult| -> Vec<(OrderId, u64, u64)> {
+            result
+                .transactions
+                .as_vec()
+                .iter()
+                .map(|t| (t.maker_order_id, t.price, t.quantity))
+                .collect()
+        };
+
+        for (taker, quantity) in [(902, 9), (903, 31), (904, 44), (905, 200)] {
+            let expected = live.match_order(
+                quantity,
+                OrderId::from_u64(taker),
+                &transaction_id_generator,
+            );
+            let actual = restored.match_order(
+                quantity,
+                OrderId::from_u64(taker),
+                &transaction_id_generator,
+            );
+            assert_eq!(fills(&actual), fills(&expected));
+            assert_eq!(actual.filled_order_ids, expected.filled_order_ids);
+            assert_eq!(without_reveal_times(&restored), without_reveal_times(&live));
+            assert_eq!(restored.tick_replenish(7_000), live.tick_replenish(7_000));
+        }
+    }
+
+    #[test]
+    fn test_oco_group_reported_and_cancelled() {
+        let transaction_id_generator = create_transaction_id_generator();
+        let with_group = |mut order: Order<()>, group: u64| {
+            if let Order::Standard { common } = &mut order {
+                common.oco_group = Some(group);
+            }
+            order
+        };
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(with_group(create_standard_order(1, 10000, 10), 7))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(with_group(create_standard_order(3, 10000, 10), 9))
+            .unwrap();
+        price_level
+            .add_order(with_group(create_standard_order(4, 10000, 10), 7))
+            .unwrap();
+
+        // A partial fill of a leg is enough to trigger its group
+        let result = price_level.match_order(15, OrderId::from_u64(100), &transaction_id_generator);
+        assert_eq!(result.triggered_oco_groups, vec![7]);
+        let parsed = MatchResult::from_str(&result.to_string()).unwrap();
+        assert_eq!(parsed.triggered_oco_groups, vec![7]);
+
+        let cancelled = price_level.cancel_oco_group(7);
+        assert_eq!(
+            cancelled.iter().map(|o| o.id()).collect::<Vec<_>>(),
+            vec![OrderId::from_u64(4)]
+        );
+        assert_eq!(price_level.order_count(), 2);
+        assert_eq!(price_level.display_quantity(), 15);
+        assert!(price_level.cancel_oco_group(7).is_empty());
+
+        let result = price_level.match_order(15, OrderId::from_u64(101), &transaction_id_generator);
+        assert_eq!(result.triggered_oco_groups, vec![9]);
+    }
+
+    #[test]
+    fn test_to_depth_bar() {
+        let mut price_level = PriceLevel::new(10000);
+        assert_eq!(price_level.to_depth_bar(10), "10000 | 0 (0)");
+        assert_eq!(price_level.to_depth_bar_scaled(10, 100), "10000 | 0 (0)");
+
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 150))
+            .unwrap();
+        assert_eq!(price_level.to_depth_bar(10), "10000 | █████ 150 (2)");
+        assert_eq!(
+            price_level.to_depth_bar_scaled(8, 150),
+            "10000 | ████████ 150 (2)"
+        );
+        assert_eq!(
+            price_level.to_depth_bar_scaled(8, 1_000_000),
+            "10000 | █ 150 (2)"
+        );
+        assert_eq!(
+            price_level.to_depth_bar_scaled(8, 100),
+            "10000 | ████████ 150 (2)"
+        );
+        assert_eq!(price_level.to_depth_bar_scaled(8, 0), "10000 | 150 (2)");
+        assert!(!price_level.to_depth_bar(10).contains('\n'));
+    }
+
+    #[test]
+    fn test_match_order_with_fees() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 30))
+            .unwrap();
+
+        // Maker rebate of 1 bp, taker fee of 3 bps
+        let fees = FeeSchedule::new(-1, 3);
+        let result = price_level.match_order_with_fees(
+            25,
+            OrderId::from_u64(100),
+            &transaction_id_generator,
+            &fees,
+        );
+
+        let transactions = result.transactions.as_vec();
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(
+            (transactions[0].maker_fee, transactions[0].taker_fee),
+            (-10, 30)
+        );
+        assert_eq!(
+            (transactions[1].maker_fee, transactions[1].taker_fee),
+            (-15, 45)
+        );
+
+        // Fees survive the text round trip; fee-free matches keep the old format
+        let parsed = MatchResult::from_str(&result.to_string()).unwrap();
+        assert_eq!(parsed.transactions, result.transactions);
+        let plain = price_level.match_order(5, OrderId::from_u64(101), &transaction_id_generator);
+        assert_eq!(plain.transactions.as_vec()[0].maker_fee, 0);
+        assert!(!plain.to_string().contains("maker_fee"));
+    }
+
+    #[test]
+    fn test_add_order_max_notional() {
+        let mut price_level = PriceLevel::new(10000).with_max_order_notional(1_000_000);
+
+        // 10000 * 100 is exactly the cap
+        assert!(
+            price_level
+                .add_order(create_standard_order(1, 10000, 100))
+                .is_ok()
+        );
+
+        // Reserve quantity counts towards the notional
+        let err = price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 51))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            PriceLevelError::NotionalTooLarge {
+                notional: 1_010_000,
+                max_notional: 1_000_000,
+            }
+        ));
+        assert_eq!(price_level.order_count(), 1);
+
+        // Benchmark-scale values do not overflow
+        let huge = create_standard_order(3, u64::MAX, u64::MAX);
+        assert_eq!(huge.notional(), u64::MAX as u128 * u64::MAX as u128);
+        assert!(price_level.add_order(huge).is_err());
+    }
+
+    #[test]
+    fn test_add_order_just_below_notional_cap() {
+        let mut price_level = PriceLevel::new(10000).with_max_order_notional(1_000_001);
+
+        // 10000 * 100 is one below the cap
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        assert_eq!(price_level.order_count(), 1);
+    }
+
+    #[test]
+    fn test_add_order_just_above_notional_cap() {
+        let mut price_level = PriceLevel::new(10000).with_max_order_notional(999_999);
+
+        // 10000 * 100 is one above the cap, rejected in every build profile
+        assert!(matches!(
+            price_level.add_order(create_standard_order(1, 10000, 100)),
+            Err(PriceLevelError::NotionalTooLarge {
+                notional: 1_000_000,
+                max_notional: 999_999,
+            })
+        ));
+        assert!(price_level.is_empty());
+        assert_eq!(price_level.stats().orders_added(), 0);
+    }
+
+    #[test]
+    fn test_apply_match_result_reproduces_match() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut live = PriceLevel::new(10000);
+        live.add_order(create_standard_order(1, 10000, 15)).unwrap();
+        live.add_order(create_iceberg_order(2, 10000, 10, 25))
+            .unwrap();
+        live.add_order(create_reserve_order(3, 10000, 20, 30, 8, true, Some(12)))
+            .unwrap();
+        live.add_order(create_reserve_order(4, 10000, 5, 40, 1, false, None))
+            .unwrap();
+        live.add_order(create_standard_order(5, 10000, 50)).unwrap();
+
+        let mut replayed = PriceLevel::from_snapshot(live.snapshot()).unwrap();
+        for (taker, quantity) in [(100, 18), (101, 27), (102, 16), (103, 60), (104, 90)] {
+            let result = live.match_order(
+                quantity,
+                OrderId::from_u64(taker),
+                &transaction_id_generator,
+            );
+            apply_match_result(&mut replayed, &result).unwrap();
+
+            assert!(replayed.content_eq(&live));
+            assert_eq!(replayed.take_last_removed(), live.take_last_removed());
+        }
+        assert!(live.is_empty());
+        assert_eq!(
+            replayed.stats().quantity_executed(),
+            live.stats().quantity_executed()
+        );
+
+        // The revealed slices are part of the text form, so captured results replay too
+        let mut live = PriceLevel::new(10000);
+        live.add_order(create_iceberg_order(1, 10000, 10, 25))
+            .unwrap();
+        let before = live.snapshot();
+        let result = live.match_order(12, OrderId::from_u64(105), &transaction_id_generator);
+        assert_eq!(result.replenishments, vec![(OrderId::from_u64(1), 10)]);
+        let parsed = MatchResult::from_str(&result.to_string()).unwrap();
+        let mut replayed = PriceLevel::from_snapshot(before).unwrap();
+        apply_match_result(&mut replayed, &parsed).unwrap();
+        assert!(replayed.content_eq(&live));
+    }
+
+    #[test]
+    fn test_apply_match_result_rejects_inconsistent_results() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut live = PriceLevel::new(10000);
+        live.add_order(create_standard_order(1, 10000, 10)).unwrap();
+        live.add_order(create_standard_order(2, 10000, 20)).unwrap();
+        let before = live.snapshot();
+        let result = live.match_order(15, OrderId::from_u64(100), &transaction_id_generator);
+
+        // Replaying twice runs out of display on the filled maker
+        let mut replayed = PriceLevel::from_snapshot(before.clone()).unwrap();
+        apply_match_result(&mut replayed, &result).unwrap();
+        let after = replayed.snapshot();
+        assert!(apply_match_result(&mut replayed, &result).is_err());
+        assert_eq!(replayed.snapshot(), after);
+
+        let mut wrong_fills = result.clone();
+        wrong_fills.filled_order_ids.clear();
+        let mut replayed = PriceLevel::from_snapshot(before.clone()).unwrap();
+        assert!(apply_match_result(&mut replayed, &wrong_fills).is_err());
+        assert_eq!(replayed.snapshot(), before);
+
+        let mut stray_reveal = result.clone();
+        stray_reveal.add_replenishment(OrderId::from_u64(2), 5);
+        let mut replayed = PriceLevel::from_snapshot(before.clone()).unwrap();
+        assert!(apply_match_result(&mut replayed, &stray_reveal).is_err());
+        assert_eq!(replayed.snapshot(), before);
+    }
+
+    #[test]
+    fn test_replay_transactions_rebuilds_resting_state() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let initial = vec![
+            create_standard_order(1, 10000, 15),
+            create_iceberg_order(2, 10000, 10, 25),
+            create_reserve_order(3, 10000, 20, 30, 8, true, Some(12)),
+            create_standard_order(4, 10000, 50),
+        ];
+        let mut live = PriceLevel::from_orders(10000, initial.clone()).unwrap();
+        let mut tape = Vec::new();
+        for (taker, quantity) in [(100, 18), (101, 27), (102, 16)] {
+            let result = live.match_order(
+                quantity,
+                OrderId::from_u64(taker),
+                &transaction_id_generator,
+            );
+            tape.extend(result.transactions.as_vec().iter().copied());
+        }
+
+        // Order 1 filled and left, order 2 refreshed its slice, order 3 was partially filled
+        let mut replayed = PriceLevel::replay_transactions(10000, initial.clone(), &tape).unwrap();
+        assert!(replayed.content_eq(&live));
+        let removed: Vec<OrderId> = replayed
+            .take_last_removed()
+            .iter()
+            .map(|order| order.id())
+            .collect();
+        assert_eq!(removed, vec![OrderId::from_u64(1)]);
+        assert!(
+            !replayed
+                .iter_orders()
+                .iter()
+                .any(|o| o.id() == OrderId::from_u64(1))
+        );
+        assert_eq!(
+            replayed.stats().quantity_executed(),
+            live.stats().quantity_executed()
+        );
+
+        // A transaction against a maker that is not resting is rejected
+        let mut stray = tape.clone();
+        stray.push(tape[0]);
+        assert!(matches!(
+            PriceLevel::replay_transactions(10000, initial.clone(), &stray),
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+
+        let mut oversized = tape[0];
+        oversized.quantity = 16;
+        assert!(PriceLevel::replay_transactions(10000, initial, &[oversized]).is_err());
+    }
+
+    #[test]
+    fn test_reprice_pegged_policies() {
+        // Pegged orders sit 50 below the best ask, which moves from 10050 to 10060
+        let reference = |reference_type| match reference_type {
+            PegReferenceType::BestAsk => Some(10060),
+            _ => None,
+        };
+        let build = || {
+            let mut level = PriceLevel::new(10000);
+            level.add_order(create_pegged_order(1, 10000, 10)).unwrap();
+            level
+                .add_order(create_standard_order(2, 10000, 20))
+                .unwrap();
+            level.add_order(create_pegged_order(3, 10000, 30)).unwrap();
+            level
+        };
+
+        let mut level = build();
+        let timestamps: Vec<u64> = level.iter_orders().iter().map(|o| o.timestamp()).collect();
+        let repriced = level.reprice_pegged(reference, PegRepricePolicy::PreserveTimestamp, 42);
+        assert_eq!(level.order_count(), 1);
+        assert_eq!(level.display_quantity(), 20);
+        let ids: Vec<OrderId> = repriced.iter().map(|r| r.order.id()).collect();
+        assert_eq!(ids, vec![OrderId::from_u64(1), OrderId::from_u64(3)]);
+        for (repriced, original) in repriced.iter().zip([timestamps[0], timestamps[2]]) {
+            assert_eq!(repriced.order.price(), 10010);
+            assert_eq!(repriced.order.timestamp(), original);
+            assert_eq!(repriced.original_timestamp, original);
+        }
+
+        let mut level = build();
+        let repriced = level.reprice_pegged(reference, PegRepricePolicy::NewTimestamp, 42);
+        assert_eq!(repriced.len(), 2);
+        assert!(repriced.iter().all(|r| r.order.timestamp() == 42));
+        // The original timestamps still tell which order had priority
+        assert!(repriced[0].original_timestamp < repriced[1].original_timestamp);
+
+        // A peg that still points at this level, or an unknown reference, leaves orders alone
+        let mut level = build();
+        assert!(
+            level
+                .reprice_pegged(|_| Some(10050), PegRepricePolicy::default(), 42)
+                .is_empty()
+        );
+        assert!(
+            level
+                .reprice_pegged(|_| None, PegRepricePolicy::default(), 42)
+                .is_empty()
+        );
+        assert_eq!(level.order_count(), 3);
+    }
+
+    #[test]
+    fn test_hidden_execution_ratio_attributes_iceberg_reserve() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut level = PriceLevel::new(10000);
+        level
+            .add_order(create_standard_order(1, 10000, 20))
+            .unwrap();
+        level
+            .add_order(create_iceberg_order(2, 10000, 10, 10))
+            .unwrap();
+        level.match_order(40, OrderId::from_u64(100), &transaction_id_generator);
+
+        // 20 visible from the standard order, 10 visible and 10 revealed from the iceberg
+        assert!(level.is_empty());
+        assert_eq!(level.stats().quantity_executed(), 40);
+        assert_eq!(level.stats().hidden_volume(), 10);
+        assert_eq!(level.stats().hidden_execution_ratio(), 0.25);
+    }
+
+    #[test]
+    fn test_hidden_execution_ratio_counts_only_executed_reveals() {
+        let transaction_id_generator = UuidGenerator::new(Uuid::nil());
+
+        // Filling exactly the visible slice reveals the next one without executing it
+        let mut level = PriceLevel::new(10000);
+        level
+            .add_order(create_iceberg_order(1, 10000, 10, 90))
+            .unwrap();
+        let result = level.match_order(10, OrderId::from_u64(100), &transaction_id_generator);
+        assert_eq!(result.replenishments, vec![(OrderId::from_u64(1), 10)]);
+        assert_eq!(level.stats().hidden_volume(), 0);
+        assert_eq!(level.stats().hidden_execution_ratio(), 0.0);
+
+        // The revealed slice counts once it trades
+        let result = level.match_order(4, OrderId::from_u64(101), &transaction_id_generator);
+        assert_eq!(level.stats().hidden_volume(), 4);
+        assert_eq!(level.stats().hidden_execution_ratio(), 4.0 / 14.0);
+
+        // A revealed slice canceled before it trades is never counted
+        level.cancel_order(OrderId::from_u64(1), CancelReason::UserRequested);
+        assert_eq!(level.stats().hidden_volume(), 4);
+
+        // A reserve order's remaining visible quantity trades before its revealed slice
+        let mut reserve = PriceLevel::new(10000);
+        reserve
+            .add_order(create_reserve_order(1, 10000, 50, 150, 20, true, Some(30)))
+            .unwrap();
+        reserve.match_order(40, OrderId::from_u64(100), &transaction_id_generator);
+        assert_eq!(reserve.display_quantity(), 40);
+        assert_eq!(reserve.stats().hidden_volume(), 0);
+        reserve.match_order(15, OrderId::from_u64(101), &transaction_id_generator);
+        assert_eq!(reserve.stats().hidden_volume(), 5);
+
+        // Applying a captured result attributes hidden volume the same way
+        let mut live = PriceLevel::new(10000);
+        live.add_order(create_iceberg_order(1, 10000, 10, 90))
+            .unwrap();
+        live.match_order(10, OrderId::from_u64(100), &transaction_id_generator);
+        apply_match_result(&mut live, &result).unwrap();
+        assert_eq!(live.stats().hidden_volume(), 4);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "do not match its 1 orders")]
+    fn test_drop_asserts_aggregates_in_debug_builds() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level.display_quantity += 5;
+    }
+
+    #[test]
+    fn test_drop_check_opt_out() {
+        let mut price_level = PriceLevel::new(10000).with_drop_check(false);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level.display_quantity += 5;
+        price_level.order_count = 3;
+        drop(price_level);
+    }
+
+    #[test]
+    fn test_zero_display_hidden_orders_reveal_on_entry() {
+        let transaction_id_generator = create_transaction_id_generator();
+
+        let mut price_level = PriceLevel::new(10000);
+        let iceberg = price_level
+            .add_order(create_iceberg_order(1, 10000, 0, 200))
+            .unwrap();
+        assert_eq!(iceberg.display_quantity(), DEFAULT_RESERVE_REPLENISH_AMOUNT);
+        assert_eq!(
+            iceberg.reserve_quantity(),
+            200 - DEFAULT_RESERVE_REPLENISH_AMOUNT
+        );
+
+        let reserve = price_level
+            .add_order(create_reserve_order(2, 10000, 0, 20, 5, true, Some(30)))
+            .unwrap();
+        assert_eq!(reserve.display_quantity(), 20);
+        assert_eq!(reserve.reserve_quantity(), 0);
+
+        let zero_amount = price_level
+            .add_order(create_reserve_order(3, 10000, 0, 20, 5, true, Some(0)))
+            .unwrap();
+        assert_eq!(zero_amount.display_quantity(), 1);
+
+        assert_eq!(
+            price_level.display_quantity(),
+            DEFAULT_RESERVE_REPLENISH_AMOUNT + 21
+        );
+        assert_eq!(price_level.total_quantity(), 240);
+
+        let result =
+            price_level.match_order(100, OrderId::from_u64(100), &transaction_id_generator);
+        assert!(result.is_complete);
+
+        // Levels built from orders or restored from snapshots reveal the same slice
+        let mut built = PriceLevel::from_orders(
+            10000,
+            vec![
+                create_iceberg_order(4, 10000, 0, 50),
+                create_iceberg_order(5, 10000, 5, 0),
+            ],
+        )
+        .unwrap();
+        assert_eq!(built.front().unwrap().display_quantity(), 50);
+        assert_eq!(built.display_quantity(), 55);
+        assert_eq!(built.reserve_quantity(), 0);
+        let result = built.match_order(10, OrderId::from_u64(101), &transaction_id_generator);
+        assert_eq!(result.executed_quantity(), 10);
+
+        let mut snapshot = PriceLevel::new(10000).snapshot();
+        snapshot.orders = vec![create_iceberg_order(6, 10000, 0, 50)];
+        for restored in [
+            PriceLevel::from_snapshot(snapshot.clone()).unwrap(),
+            PriceLevel::from(&snapshot),
+        ] {
+            assert_eq!(restored.display_quantity(), 50);
+            assert_eq!(restored.reserve_quantity(), 0);
+        }
+    }
+
+    #[test]
+    fn test_impact_of_removing() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 150))
+            .unwrap();
+
+        assert_eq!(
+            price_level.impact_of_removing(OrderId::from_u64(1)),
+            Some((100, 0))
+        );
+        assert_eq!(
+            price_level.impact_of_removing(OrderId::from_u64(2)),
+            Some((50, 150))
+        );
+        assert_eq!(price_level.impact_of_removing(OrderId::from_u64(3)), None);
+
+        // Read-only: the level is unchanged
+        assert_eq!(price_level.order_count(), 2);
+        assert_eq!(price_level.total_quantity(), 300);
+    }
+
+    #[test]
+    fn test_event_sender_publishes_level_events() {
+        let transaction_id_generator = create_transaction_id_generator();
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let mut price_level = PriceLevel::new(10000).with_event_sender(sender);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 20))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 30))
+            .unwrap();
+        price_level.match_order(15, OrderId::from_u64(100), &transaction_id_generator);
+        price_level.cancel_order(OrderId::from_u64(3), CancelReason::RiskTrip);
+        price_level.cancel_all(CancelReason::SessionRollover);
+
+        // Events are drained on another thread, as a feed consumer would
+        let consumer = std::thread::spawn(move || receiver.try_iter().collect::<Vec<_>>());
+        let events = consumer.join().unwrap();
+
+        assert_eq!(events.len(), 7);
+        assert!(events.iter().all(|event| event.price() == 10000));
+        assert!(matches!(
+            events[0],
+            LevelEvent::OrderAdded { order, .. } if order.id() == OrderId::from_u64(1)
+        ));
+        let fills: Vec<(OrderId, u64)> = events
+            .iter()
+            .filter_map(|event| match event {
+                LevelEvent::OrderFilled { transaction, .. } => {
+                    Some((transaction.maker_order_id, transaction.quantity))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            fills,
+            vec![(OrderId::from_u64(1), 10), (OrderId::from_u64(2), 5)]
+        );
+        assert!(matches!(
+            events[5],
+            LevelEvent::OrderCancelled { order_id, reason: CancelReason::RiskTrip, .. }
+                if order_id == OrderId::from_u64(3)
+        ));
+        assert!(matches!(
+            events[6],
+            LevelEvent::OrderCancelled { order_id, reason: CancelReason::SessionRollover, .. }
+                if order_id == OrderId::from_u64(2)
+        ));
+
+        // Dropping the receiver never fails or blocks the level
+        price_level
+            .add_order(create_standard_order(4, 10000, 10))
+            .unwrap();
+        assert_eq!(price_level.order_count(), 1);
+    }
+
+    #[test]
+    fn test_lifecycle_counts_discarded_reserve() {
+        let transaction_id_generator = create_transaction_id_generator();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut price_level = PriceLevel::new(10000).with_event_sender(sender);
+
+        // One order never replenishes, the other stops at its display cap
+        price_level
+            .add_order(create_reserve_order(1, 10000, 10, 40, 2, false, None))
+            .unwrap();
+        let mut capped = create_reserve_order(2, 10000, 10, 40, 2, true, Some(10));
+        if let Order::ReserveOrder {
+            max_total_display, ..
+        } = &mut capped
+        {
+            *max_total_display = Some(10);
+        }
+        price_level.add_order(capped).unwrap();
+        price_level.match_order(30, OrderId::from_u64(900), &transaction_id_generator);
+        assert!(price_level.is_empty());
+
+        let events: Vec<LevelEvent> = receiver.try_iter().collect();
+        for (order_id, filled, discarded) in [(1, 10, 40), (2, 20, 30)] {
+            let summary = LifecycleValidator::new(OrderId::from_u64(order_id))
+                .validate(&events)
+                .unwrap();
+            assert_eq!(summary.filled, filled);
+            assert_eq!(summary.discarded, discarded);
+            assert!(summary.is_closed());
+        }
+    }
+
+    #[test]
+    fn test_event_sender_publishes_updates_and_moves() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut price_level = PriceLevel::new(10000).with_event_sender(sender);
+        for id in 1..=6 {
+            price_level
+                .add_order(create_standard_order(id, 10000, 10))
+                .unwrap();
+        }
+        price_level
+            .add_order(create_pegged_order(7, 10000, 10))
+            .unwrap();
+
+        let id = OrderId::from_u64;
+        price_level
+            .update_order(OrderUpdate::UpdateQuantity {
+                order_id: id(1),
+                new_quantity: 4,
+            })
+            .unwrap();
+        price_level
+            .update_order(OrderUpdate::UpdatePrice {
+                order_id: id(2),
+                new_price: 10100,
+            })
+            .unwrap();
+        price_level
+            .update_order(OrderUpdate::UpdatePriceAndQuantity {
+                order_id: id(3),
+                new_price: 10200,
+                new_quantity: 5,
+            })
+            .unwrap();
+        price_level
+            .update_order(OrderUpdate::Replace {
+                order_id: id(4),
+                price: 10300,
+                quantity: 5,
+                side: Side::Buy,
+            })
+            .unwrap();
+        price_level
+            .update_order(OrderUpdate::ReplaceFull {
+                order_id: id(5),
+                new_order: Box::new(create_standard_order(5, 10400, 10)),
+                preserve_priority: false,
+            })
+            .unwrap();
+        price_level.coalesce_owner(1, |order| (order.id() != id(7)).then_some(1));
+        price_level.reprice_pegged(|_| Some(10550), PegRepricePolicy::default(), 42);
+
+        let events: Vec<LevelEvent> = receiver.try_iter().collect();
+        let moves: Vec<(OrderId, Price)> = events
+            .iter()
+            .filter_map(|event| match *event {
+                LevelEvent::OrderMoved {
+                    order_id,
+                    new_price,
+                    ..
+                } => Some((order_id, new_price)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            moves,
+            vec![
+                (id(2), 10100),
+                (id(3), 10200),
+                (id(4), 10300),
+                (id(5), 10400),
+                (id(7), 10500),
+            ]
+        );
+        let updates: Vec<(OrderId, Quantity)> = events
+            .iter()
+            .filter_map(|event| match event {
+                LevelEvent::OrderUpdated { order, .. } => {
+                    Some((order.id(), order.display_quantity()))
+                }
+                _ => None,
+            })
+            .collect();
+        // The quantity update, then the survivor of orders 1 and 6 coalescing
+        assert_eq!(updates, vec![(id(1), 4), (id(1), 14)]);
+
+        // Every change in the aggregates is accounted for by the event stream
+        let resting: Quantity = (1..=7)
+            .map(|order_id| {
+                LifecycleValidator::new(id(order_id))
+                    .validate(&events)
+                    .unwrap()
+                    .resting_display
+            })
+            .sum();
+        assert_eq!(resting, price_level.display_quantity());
+    }
+
+    #[test]
+    fn test_from_iter_at_streams_orders() {
+        let generated = (1..=10_000u64).map(|id| {
+            if id % 4 == 0 {
+                create_iceberg_order(id, 10000, 5, 10)
+            } else {
+                create_standard_order(id, 10000, 2)
+            }
+        });
+
+        let price_level = PriceLevel::from_iter_at(10000, generated).unwrap();
+        assert_eq!(price_level.order_count(), 10_000);
+        assert_eq!(price_level.display_quantity(), 7_500 * 2 + 2_500 * 5);
+        assert_eq!(price_level.reserve_quantity(), 2_500 * 10);
+        assert_eq!(price_level.iter_orders()[0].id(), OrderId::from_u64(1));
+        assert_eq!(
+            price_level.iter_orders()[9_999].id(),
+            OrderId::from_u64(10_000)
+        );
+
+        // The first mismatched price stops the build
+        let mixed = (1..=100u64).map(|id| create_standard_order(id, 10000 + id / 50, 1));
+        assert!(matches!(
+            PriceLevel::from_iter_at(10000, mixed),
+            Err(PriceLevelError::InvalidFieldValue { .. })
+        ));
+
+        // A repeated ID replaces the earlier order without double counting
+        let repeated = [
+            create_standard_order(1, 10000, 10),
+            create_standard_order(1, 10000, 4),
+        ];
+        let price_level = PriceLevel::from_iter_at(10000, repeated).unwrap();
+        assert_eq!(price_level.order_count(), 1);
+        assert_eq!(price_level.display_quantity(), 4);
+    }
+
+    #[test]
+    fn test_iceberg_overhang() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 100))
+            .unwrap();
+        assert_eq!(price_level.iceberg_overhang(), 0);
+
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 50, 150))
+            .unwrap();
+        price_level
+            .add_order(create_reserve_order(3, 10000, 20, 60, 5, true, Some(10)))
+            .unwrap();
+        assert_eq!(price_level.iceberg_overhang(), 210);
+        assert_eq!(price_level.reserve_quantity(), 210);
+
+        // Reserve that is discarded with its order counts towards the raw reserve only
+        price_level
+            .add_order(create_reserve_order(4, 10000, 20, 40, 5, false, Some(10)))
+            .unwrap();
+        assert_eq!(price_level.reserve_quantity(), 250);
+        assert_eq!(price_level.iceberg_overhang(), 210);
+        assert_eq!(
+            price_level.iceberg_overhang(),
+            price_level.fillable_quantity() - price_level.display_quantity()
+        );
+    }
+
+    #[test]
+    fn test_coalesce_owner() {
+        use crate::order::OrderMetadata;
+        use std::collections::HashMap;
+
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 20))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 30))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(4, 10000, 40, 100))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(5, 10000, 50))
+            .unwrap();
+
+        let metadata: HashMap<OrderId, OrderMetadata> = [(2, 7), (4, 7), (5, 7), (3, 8)]
+            .into_iter()
+            .map(|(id, user)| {
+                let metadata = OrderMetadata {
+                    user_id: Some(user),
+                    ..OrderMetadata::default()
+                };
+                (OrderId::from_u64(id), metadata)
+            })
+            .collect();
+        let owner_of = |order: &Order<()>| metadata.get(&order.id()).and_then(|m| m.user_id);
+
+        // Orders 2 and 5 merge into 2; the iceberg 4 is not a standard order
+        let merged = price_level.coalesce_owner(7, owner_of).unwrap();
+        assert_eq!(merged.id(), OrderId::from_u64(2));
+        assert_eq!(merged.display_quantity(), 70);
+
+        let ids: Vec<OrderId> = price_level.iter_orders().iter().map(|o| o.id()).collect();
+        assert_eq!(ids, [1, 2, 3, 4].map(OrderId::from_u64).to_vec());
+        assert_eq!(price_level.order_count(), 4);
+        assert_eq!(price_level.display_quantity(), 150);
+        assert_eq!(price_level.reserve_quantity(), 100);
+
+        assert!(price_level.coalesce_owner(9, owner_of).is_none());
+        let single = price_level.coalesce_owner(8, owner_of).unwrap();
+        assert_eq!(single.display_quantity(), 30);
+    }
+
+    #[test]
+    fn test_potential_wash() {
+        use crate::order::OrderMetadata;
+        use std::collections::HashMap;
+
+        let generator = UuidGenerator::new(Uuid::nil());
+        let mut price_level = PriceLevel::new(10000);
+        for id in 1..=4 {
+            price_level
+                .add_order(create_standard_order(id, 10000, 10))
+                .unwrap();
+        }
+
+        // User 7 owns makers 1 and 3 and the taker 100; maker 2 and taker 101 differ
+        let metadata: HashMap<OrderId, OrderMetadata> =
+            [(1, 7), (3, 7), (100, 7), (2, 8), (101, 9)]
+                .into_iter()
+                .map(|(id, user)| {
+                    let metadata = OrderMetadata {
+                        user_id: Some(user),
+                        ..OrderMetadata::default()
+                    };
+                    (OrderId::from_u64(id), metadata)
+                })
+                .collect();
+        let owner_of = |id: OrderId| metadata.get(&id).and_then(|m| m.user_id);
+
+        let mut transactions = price_level
+            .match_order(5, OrderId::from_u64(100), &generator)
+            .transactions
+            .as_vec()
+            .clone();
+        transactions.extend(
+            price_level
+                .match_order(10, OrderId::from_u64(101), &generator)
+                .transactions
+                .as_vec()
+                .iter()
+                .copied(),
+        );
+
+        // Maker 1 traded against its own user's taker; order 3 of that user is still resting
+        assert_eq!(
+            price_level.potential_wash(&transactions, owner_of),
+            vec![OrderId::from_u64(1), OrderId::from_u64(3)]
+        );
+        assert!(price_level.potential_wash(&[], owner_of).is_empty());
+        assert!(
+            PriceLevel::new(10001)
+                .potential_wash(&transactions, owner_of)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_match_order_with_comparator() {
+        let generator = UuidGenerator::new(Uuid::nil());
+        let mut level = PriceLevel::new(10000)
+            .with_comparator(|a, b| b.display_quantity().cmp(&a.display_quantity()));
+        level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        level
+            .add_order(create_standard_order(2, 10000, 30))
+            .unwrap();
+        level
+            .add_order(create_standard_order(3, 10000, 20))
+            .unwrap();
+
+        let result = level.match_order(35, OrderId::from_u64(100), &generator);
+        assert!(result.is_complete);
+        assert_eq!(result.filled_order_ids, vec![OrderId::from_u64(2)]);
+        let makers: Vec<OrderId> = result
+            .transactions
+            .as_vec()
+            .iter()
+            .map(|t| t.maker_order_id)
+            .collect();
+        assert_eq!(makers, [2, 3].map(OrderId::from_u64));
+
+        let remaining: Vec<(OrderId, u64)> = level
+            .iter_orders()
+            .iter()
+            .map(|o| (o.id(), o.display_quantity()))
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![(OrderId::from_u64(3), 15), (OrderId::from_u64(1), 10)]
+        );
+    }
+
+    #[test]
+    fn test_comparator_survives_snapshot_round_trip() {
+        let generator = UuidGenerator::new(Uuid::nil());
+        let by_size =
+            |a: &Order<()>, b: &Order<()>| b.display_quantity().cmp(&a.display_quantity());
+        let mut level = PriceLevel::new(10000).with_comparator(by_size);
+        for (id, quantity) in [(1, 10), (2, 30), (3, 20)] {
+            level
+                .add_order(create_standard_order(id, 10000, quantity))
+                .unwrap();
+        }
+
+        let mut restored = PriceLevel::from_snapshot(level.snapshot())
+            .unwrap()
+            .with_comparator(by_size);
+        let late = create_standard_order(4, 10000, 25);
+        level.add_order(late).unwrap();
+        restored.add_order(late).unwrap();
+        assert!(restored.content_eq(&level));
+
+        let makers = |result: MatchResult| -> Vec<OrderId> {
+            result
+                .transactions
+                .as_vec()
+                .iter()
+                .map(|t| t.maker_order_id)
+                .collect()
+        };
+        let expected = makers(level.match_order(60, OrderId::from_u64(100), &generator));
+        assert_eq!(expected, [2, 4, 3].map(OrderId::from_u64));
+        assert_eq!(
+            makers(restored.match_order(60, OrderId::from_u64(100), &generator)),
+            expected
+        );
+        assert!(restored.content_eq(&level));
     }
 
     #[test]
-    fn test_price_level_update_reserve_quantity() {
-        // Test lines 488, 498
-        let mut price_level = PriceLevel::new(10000);
+    fn test_timestamp_range() {
+        use crate::{Order, OrderCommon, OrderId, TimeInForce};
 
-        // Add an iceberg order with visible and hidden quantities
-        let order = Order::IcebergOrder {
+        let order = |id: u64, timestamp: u64| Order::Standard {
             common: OrderCommon {
-                id: OrderId::from_u64(1),
+                id: OrderId::from_u64(id),
                 price: 10000,
-                display_quantity: 50,
+                display_quantity: 10,
                 side: Side::Buy,
-                timestamp: 1616823000000,
+                timestamp,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
-            reserve_quantity: 150,
         };
-        price_level.add_order(order);
 
-        // Verify initial quantities
-        assert_eq!(price_level.display_quantity(), 50);
-        assert_eq!(price_level.reserve_quantity(), 150);
+        let mut price_level = PriceLevel::new(10000);
+        assert_eq!(price_level.timestamp_range(), None);
 
-        // Create a new iceberg order with different quantities
-        let new_order = Order::IcebergOrder {
+        price_level.add_order(order(1, 1616823000500)).unwrap();
+        assert_eq!(
+            price_level.timestamp_range(),
+            Some((1616823000500, 1616823000500))
+        );
+
+        // Out-of-order timestamps are scanned, not read off the queue ends
+        price_level.add_order(order(2, 1616823000100)).unwrap();
+        price_level.add_order(order(3, 1616823000900)).unwrap();
+        price_level.add_order(order(4, 1616823000300)).unwrap();
+        assert_eq!(
+            price_level.timestamp_range(),
+            Some((1616823000100, 1616823000900))
+        );
+
+        price_level.cancel_order(OrderId::from_u64(2), CancelReason::UserRequested);
+        assert_eq!(
+            price_level.timestamp_range(),
+            Some((1616823000300, 1616823000900))
+        );
+    }
+
+    fn create_sell_taker(id: u64, price: u64, quantity: u64, tif: TimeInForce) -> Order<()> {
+        Order::Standard {
             common: OrderCommon {
-                id: OrderId::from_u64(1),
-                price: 10000,
-                display_quantity: 40,
-                side: Side::Buy,
+                id: OrderId::from_u64(id),
+                price,
+                display_quantity: quantity,
+                side: Side::Sell,
                 timestamp: 1616823000000,
-                time_in_force: TimeInForce::Gtc,
+                time_in_force: tif,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
-            reserve_quantity: 200,
-        };
+        }
+    }
 
-        // Test increasing hidden quantity
-        let result = price_level.update_order(OrderUpdate::Cancel {
-            order_id: OrderId::from_u64(1),
-        });
-        assert!(result.is_ok());
-        price_level.add_order(new_order);
+    #[test]
+    fn test_match_taker_fill_or_kill() {
+        let generator = UuidGenerator::new(Uuid::nil());
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 30))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 20))
+            .unwrap();
+        // A resting seller cannot fill a sell taker
+        price_level
+            .add_order(create_iceberg_order(3, 10000, 10, 20))
+            .unwrap();
+
+        // 50 is available to a seller, so 70 is killed without touching the level
+        let taker = create_sell_taker(100, 10000, 70, TimeInForce::Fok);
+        let result = price_level.match_taker(&taker, &generator);
+        assert!(result.transactions.as_vec().is_empty());
+        assert_eq!(result.remaining_quantity, 70);
+        assert_eq!(result.cancelled_quantity, 70);
+        assert_eq!(price_level.total_quantity(), 80);
+
+        // A limit above the level price is not marketable for a seller
+        let taker = create_sell_taker(101, 10001, 10, TimeInForce::Fok);
+        let result = price_level.match_taker(&taker, &generator);
+        assert_eq!(result.cancelled_quantity, 10);
+        assert_eq!(price_level.total_quantity(), 80);
+
+        let taker = create_sell_taker(102, 9990, 50, TimeInForce::Fok);
+        let result = price_level.match_taker(&taker, &generator);
+        assert!(result.is_complete);
+        assert_eq!(result.executed_quantity(), 50);
+        assert_eq!(result.cancelled_quantity, 0);
+        assert_eq!(price_level.total_quantity(), 30);
+
+        // A buy iceberg with nothing displayed cannot trade, whatever its reserve
+        let mut stuck = create_iceberg_order(4, 10000, 0, 50);
+        if let Order::IcebergOrder { common, .. } = &mut stuck {
+            common.side = Side::Buy;
+        }
+        price_level.orders.push(stuck);
+        price_level.reserve_quantity += 50;
+        price_level.order_count += 1;
+        let taker = create_sell_taker(103, 10000, 10, TimeInForce::Fok);
+        let result = price_level.match_taker(&taker, &generator);
+        assert!(result.transactions.as_vec().is_empty());
+        assert_eq!(result.cancelled_quantity, 10);
+        assert_eq!(price_level.total_quantity(), 80);
+        // The killed taker does not report the previous match's removed orders
+        assert!(price_level.take_last_removed().is_empty());
+    }
 
-        // Verify both visible and hidden quantities were updated
-        assert_eq!(price_level.display_quantity(), 40);
-        assert_eq!(price_level.reserve_quantity(), 200);
+    #[test]
+    fn test_match_taker_immediate_or_cancel() {
+        let generator = UuidGenerator::new(Uuid::nil());
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 30))
+            .unwrap();
+
+        // The fillable part trades and the rest is canceled
+        let taker = create_sell_taker(100, 10000, 45, TimeInForce::Ioc);
+        let result = price_level.match_taker(&taker, &generator);
+        assert_eq!(result.executed_quantity(), 30);
+        assert_eq!(result.remaining_quantity, 15);
+        assert_eq!(result.cancelled_quantity, 15);
+        assert_eq!(result.filled_order_ids, vec![OrderId::from_u64(1)]);
+        assert!(price_level.is_empty());
+
+        // A good-till-cancel remainder is left for the caller to rest
+        price_level
+            .add_order(create_standard_order(2, 10000, 30))
+            .unwrap();
+        let taker = create_sell_taker(101, 10000, 45, TimeInForce::Gtc);
+        let result = price_level.match_taker(&taker, &generator);
+        assert_eq!(result.remaining_quantity, 15);
+        assert_eq!(result.cancelled_quantity, 0);
+
+        // Same-side makers are not matched
+        price_level
+            .add_order(create_sell_taker(3, 10000, 30, TimeInForce::Gtc))
+            .unwrap();
+        let taker = create_sell_taker(102, 10000, 10, TimeInForce::Ioc);
+        let result = price_level.match_taker(&taker, &generator);
+        assert_eq!(result.executed_quantity(), 0);
+        assert_eq!(result.cancelled_quantity, 10);
+        assert_eq!(price_level.total_quantity(), 30);
     }
 
     #[test]
-    fn test_price_level_update_price_and_quantity_same_price() {
-        // Test line 510
+    fn test_stats_snapshot_is_detached() {
+        let generator = UuidGenerator::new(Uuid::nil());
         let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 30))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 30))
+            .unwrap();
+
+        let snapshot = price_level.stats_snapshot();
+        price_level.match_order(40, OrderId::from_u64(100), &generator);
+        price_level
+            .add_order(create_standard_order(3, 10000, 30))
+            .unwrap();
+
+        assert_eq!(snapshot.orders_added(), 2);
+        assert_eq!(snapshot.orders_executed(), 0);
+        assert_eq!(snapshot.quantity_executed(), 0);
+        assert_eq!(price_level.stats().orders_added(), 3);
+        assert_eq!(price_level.stats().quantity_executed(), 40);
+    }
 
-        // Add an order
-        let order = Order::<()>::Standard {
-            common: OrderCommon {
-                id: OrderId::from_u64(1),
-                price: 10000,
-                display_quantity: 50,
-                side: Side::Buy,
-                timestamp: 1616823000000,
-                time_in_force: TimeInForce::Gtc,
-                extra_fields: (),
-            },
-        };
-        price_level.add_order(order);
+    #[test]
+    fn test_replace_full_standard_to_iceberg_preserves_priority() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 20))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(3, 10000, 30))
+            .unwrap();
+
+        let mut replacement = create_iceberg_order(2, 10000, 5, 45);
+        if let Order::IcebergOrder { common, .. } = &mut replacement {
+            common.side = Side::Buy;
+        }
+        let result = price_level
+            .update_order(OrderUpdate::ReplaceFull {
+                order_id: OrderId::from_u64(2),
+                new_order: Box::new(replacement),
+                preserve_priority: true,
+            })
+            .unwrap();
+        assert_eq!(result, Some(replacement));
 
-        // Update both price and quantity with same price
-        let update = OrderUpdate::UpdatePriceAndQuantity {
+        let orders = price_level.iter_orders();
+        let ids: Vec<OrderId> = orders.iter().map(|o| o.id()).collect();
+        assert_eq!(ids, [1, 2, 3].map(OrderId::from_u64));
+        assert!(matches!(orders[1], Order::IcebergOrder { .. }));
+        assert_eq!(price_level.display_quantity(), 45);
+        assert_eq!(price_level.reserve_quantity(), 45);
+        assert_eq!(price_level.order_count(), 3);
+    }
+
+    #[test]
+    fn test_replace_full_iceberg_to_reserve_loses_priority() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 10, 40))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 20))
+            .unwrap();
+
+        let replacement = create_reserve_order(1, 10000, 15, 60, 5, true, Some(15));
+        let result = price_level
+            .update_order(OrderUpdate::ReplaceFull {
+                order_id: OrderId::from_u64(1),
+                new_order: Box::new(replacement),
+                preserve_priority: false,
+            })
+            .unwrap();
+        assert_eq!(result, Some(replacement));
+
+        let orders = price_level.iter_orders();
+        let ids: Vec<OrderId> = orders.iter().map(|o| o.id()).collect();
+        assert_eq!(ids, [2, 1].map(OrderId::from_u64));
+        assert!(matches!(orders[1], Order::ReserveOrder { .. }));
+        assert_eq!(price_level.display_quantity(), 35);
+        assert_eq!(price_level.reserve_quantity(), 60);
+
+        // Back to a plain order: the reserve leaves the level
+        let mut replacement = create_standard_order(1, 10000, 8);
+        if let Order::Standard { common } = &mut replacement {
+            common.side = Side::Sell;
+        }
+        price_level
+            .update_order(OrderUpdate::ReplaceFull {
+                order_id: OrderId::from_u64(1),
+                new_order: Box::new(replacement),
+                preserve_priority: true,
+            })
+            .unwrap();
+        assert_eq!(price_level.display_quantity(), 28);
+        assert_eq!(price_level.reserve_quantity(), 0);
+        assert!(matches!(
+            price_level.iter_orders()[1],
+            Order::Standard { .. }
+        ));
+    }
+
+    #[test]
+    fn test_replace_full_reroutes_and_rejects() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 10, 40))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, 10000, 20))
+            .unwrap();
+
+        // A mismatched ID is rejected and nothing changes
+        let result = price_level.update_order(OrderUpdate::ReplaceFull {
             order_id: OrderId::from_u64(1),
-            new_price: 10000, // Same price
-            new_quantity: 100,
+            new_order: Box::new(create_standard_order(9, 10000, 5)),
+            preserve_priority: true,
+        });
+        assert!(matches!(
+            result,
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert_eq!(price_level.total_quantity(), 70);
+
+        // Unknown orders are not found
+        let result = price_level.update_order(OrderUpdate::ReplaceFull {
+            order_id: OrderId::from_u64(9),
+            new_order: Box::new(create_standard_order(9, 10000, 5)),
+            preserve_priority: true,
+        });
+        assert_eq!(result.unwrap(), None);
+
+        // The replacement must stay on the order's side
+        let side_change = OrderUpdate::ReplaceFull {
+            order_id: OrderId::from_u64(1),
+            new_order: Box::new(create_standard_order(1, 10100, 50)),
+            preserve_priority: true,
         };
+        assert!(price_level.preview_update(&side_change).is_err());
+        let result = price_level.update_order(side_change);
+        assert!(matches!(
+            result,
+            Err(PriceLevelError::InvalidOperation { .. })
+        ));
+        assert_eq!(price_level.order_count(), 2);
+        assert_eq!(price_level.total_quantity(), 70);
 
-        let result = price_level.update_order(update);
-        assert!(result.is_ok());
-        assert!(result.unwrap().is_some());
+        // A new price removes the old order and hands back the replacement
+        let mut replacement = create_standard_order(1, 10100, 50);
+        if let Order::Standard { common } = &mut replacement {
+            common.side = Side::Sell;
+        }
+        let result = price_level
+            .update_order(OrderUpdate::ReplaceFull {
+                order_id: OrderId::from_u64(1),
+                new_order: Box::new(replacement),
+                preserve_priority: true,
+            })
+            .unwrap();
+        assert_eq!(result, Some(replacement));
+        assert_eq!(price_level.order_count(), 1);
+        assert_eq!(price_level.display_quantity(), 20);
+        assert_eq!(price_level.reserve_quantity(), 0);
+        assert_eq!(price_level.stats().orders_removed(), 1);
+    }
 
-        // Verify quantity was updated but price remained the same
-        assert_eq!(price_level.display_quantity(), 100);
-        assert_eq!(price_level.price(), 10000);
+    #[test]
+    fn test_replace_full_normalizes_like_add_order() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 10, 40))
+            .unwrap();
+
+        // A replacement showing nothing reveals its first slice, as it would on entry
+        let update = OrderUpdate::ReplaceFull {
+            order_id: OrderId::from_u64(1),
+            new_order: Box::new(create_iceberg_order(1, 10000, 0, 200)),
+            preserve_priority: true,
+        };
+        let preview = price_level.preview_update(&update).unwrap();
+        let replaced = price_level.update_order(update).unwrap().unwrap();
+        assert_eq!(
+            replaced.display_quantity(),
+            DEFAULT_RESERVE_REPLENISH_AMOUNT
+        );
+        assert_eq!(price_level.iter_orders(), vec![replaced]);
+        assert_eq!(
+            price_level.display_quantity(),
+            DEFAULT_RESERVE_REPLENISH_AMOUNT
+        );
+        assert_eq!(
+            price_level.reserve_quantity(),
+            200 - DEFAULT_RESERVE_REPLENISH_AMOUNT
+        );
+        assert_eq!(preview, price_level.aggregate());
+        assert!(matches!(
+            replaced,
+            Order::IcebergOrder {
+                original_display: DEFAULT_RESERVE_REPLENISH_AMOUNT,
+                ..
+            }
+        ));
     }
 
     #[test]
-    fn test_price_level_from_price_level_data_conversion() {
-        // Test lines 521-523, 527, 537, 558-560, 562-564, 566-568, 607
+    fn test_crossed_quantity() {
+        let mut bid = PriceLevel::new(10010);
+        bid.add_order(create_standard_order(1, 10010, 30)).unwrap();
+        bid.add_order(create_standard_order(2, 10010, 20)).unwrap();
+        let mut ask = PriceLevel::new(10000);
+        ask.add_order(create_iceberg_order(3, 10000, 10, 25))
+            .unwrap();
+
+        // Crossed: the ask is limited by its fillable quantity, reserve included
+        assert_eq!(crossed_quantity(&bid, &ask), 35);
+        ask.add_order(create_iceberg_order(4, 10000, 10, 50))
+            .unwrap();
+        assert_eq!(crossed_quantity(&bid, &ask), 50);
+
+        // Locked at the same price still trades
+        let mut locked = PriceLevel::new(10010);
+        locked
+            .add_order(create_iceberg_order(5, 10010, 5, 0))
+            .unwrap();
+        assert_eq!(crossed_quantity(&bid, &locked), 5);
+
+        // Not crossing, or nothing on one side
+        let mut above = PriceLevel::new(10011);
+        above
+            .add_order(create_iceberg_order(6, 10011, 5, 0))
+            .unwrap();
+        assert_eq!(crossed_quantity(&bid, &above), 0);
+        assert_eq!(crossed_quantity(&bid, &PriceLevel::new(10000)), 0);
+
+        // Read-only
+        assert_eq!(bid.total_quantity(), 50);
+        assert_eq!(ask.total_quantity(), 95);
+    }
+
+    #[test]
+    fn test_reserve_order_max_total_display() {
+        let generator = UuidGenerator::new(Uuid::nil());
+        let mut order = create_reserve_order(1, 10000, 10, 100, 0, true, Some(10));
+        if let Order::ReserveOrder {
+            max_total_display, ..
+        } = &mut order
+        {
+            *max_total_display = Some(25);
+        }
 
-        // Create a price level
         let mut price_level = PriceLevel::new(10000);
+        price_level.add_order(order).unwrap();
+        // Only 25 of the reserve can ever be shown
+        assert_eq!(price_level.fillable_quantity(), 35);
+
+        price_level.match_order(10, OrderId::from_u64(100), &generator);
+        price_level.match_order(10, OrderId::from_u64(101), &generator);
+        let result = price_level.match_order(10, OrderId::from_u64(102), &generator);
+        // The last reveal is cut to what is left under the cap
+        assert_eq!(result.replenishments, vec![(OrderId::from_u64(1), 5)]);
+        assert_eq!(price_level.display_quantity(), 5);
+        assert_eq!(price_level.reserve_quantity(), 75);
+        match price_level.iter_orders()[0] {
+            Order::ReserveOrder {
+                revealed_quantity, ..
+            } => assert_eq!(revealed_quantity, 25),
+            _ => panic!("Expected ReserveOrder"),
+        }
 
-        // Add some orders
-        let order1 = Order::<()>::Standard {
-            common: OrderCommon {
-                id: OrderId::from_u64(1),
-                price: 10000,
-                display_quantity: 50,
-                side: Side::Buy,
-                timestamp: 1616823000000,
-                time_in_force: TimeInForce::Gtc,
-                extra_fields: (),
-            },
-        };
-        price_level.add_order(order1);
+        // Once the visible part is consumed the order leaves, discarding its reserve
+        let result = price_level.match_order(10, OrderId::from_u64(103), &generator);
+        assert_eq!(result.executed_quantity(), 5);
+        assert!(result.replenishments.is_empty());
+        assert_eq!(result.filled_order_ids, vec![OrderId::from_u64(1)]);
+        assert!(price_level.is_empty());
+        assert_eq!(price_level.reserve_quantity(), 0);
+    }
 
-        let order2 = Order::<()>::IcebergOrder {
-            common: OrderCommon {
-                id: OrderId::from_u64(2),
-                price: 10000,
-                display_quantity: 30,
-                side: Side::Buy,
-                timestamp: 1616823000001,
-                time_in_force: TimeInForce::Gtc,
-                extra_fields: (),
-            },
-            reserve_quantity: 70,
-        };
-        price_level.add_order(order2);
+    #[test]
+    fn test_try_for_each_order_stops_early() {
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_standard_order(1, 10000, 10))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 5, 20))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(3, 10000, 5, 20))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(4, 10000, 10))
+            .unwrap();
+
+        // Stop at the first order with hidden quantity
+        let mut visited = Vec::new();
+        let result = price_level.try_for_each_order(|order| {
+            visited.push(order.id());
+            if order.reserve_quantity() > 0 {
+                Err(order.id())
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(result, Err(OrderId::from_u64(2)));
+        assert_eq!(visited, [1, 2].map(OrderId::from_u64));
+
+        // Without an error every order is visited
+        let mut count = 0;
+        let result: Result<(), ()> = price_level.try_for_each_order(|_| {
+            count += 1;
+            Ok(())
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(count, 4);
+    }
 
-        // Convert to PriceLevelData
-        let data: PriceLevelData = (&price_level).into();
+    #[test]
+    fn test_stats_split_volume_by_taker_side() {
+        let generator = UuidGenerator::new(Uuid::nil());
+        let mut price_level = PriceLevel::new(10000);
+        // Resting buys are hit by sell takers, resting sells lifted by buy takers
+        price_level
+            .add_order(create_standard_order(1, 10000, 30))
+            .unwrap();
+        price_level
+            .add_order(create_iceberg_order(2, 10000, 20, 0))
+            .unwrap();
 
-        // Verify data
-        assert_eq!(data.price, 10000);
-        assert_eq!(data.display_quantity, 80); // 50 + 30
-        assert_eq!(data.reserve_quantity, 70);
-        assert_eq!(data.order_count, 2);
-        assert_eq!(data.orders.len(), 2);
+        price_level.match_order_sided(25, OrderId::from_u64(100), &generator, Side::Sell);
+        price_level.match_order_sided(15, OrderId::from_u64(101), &generator, Side::Buy);
 
-        // Convert back to PriceLevel
-        let result = PriceLevel::try_from(data);
-        assert!(result.is_ok());
+        let stats = price_level.stats();
+        assert_eq!(stats.sell_volume(), 25);
+        assert_eq!(stats.buy_volume(), 15);
+        assert_eq!(stats.quantity_executed(), 40);
+
+        // The split survives serialization
+        let json = serde_json::to_string(stats).unwrap();
+        let restored: PriceLevelStatistics = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.buy_volume(), 15);
+        assert_eq!(restored.sell_volume(), 25);
+        let restored = PriceLevelStatistics::from_str(&stats.to_string()).unwrap();
+        assert_eq!(restored.buy_volume(), 15);
+        assert_eq!(restored.sell_volume(), 25);
+    }
 
-        // Verify converted price level
-        let converted_level = result.unwrap();
-        assert_eq!(converted_level.price(), 10000);
-        assert_eq!(converted_level.display_quantity(), 80);
-        assert_eq!(converted_level.reserve_quantity(), 70);
-        assert_eq!(converted_level.order_count(), 2);
+    #[test]
+    fn test_value_executed_beyond_u64() {
+        let generator = UuidGenerator::new(Uuid::nil());
+        let price = 1_000_000_000_000;
+        let quantity = 100_000_000;
+        let mut price_level = PriceLevel::new(price);
+        price_level
+            .add_order(create_standard_order(1, price, quantity))
+            .unwrap();
+        price_level
+            .add_order(create_standard_order(2, price, quantity))
+            .unwrap();
+
+        // Each execution alone is worth more than u64::MAX
+        let result = price_level.match_order(2 * quantity, OrderId::from_u64(100), &generator);
+        let expected = 2 * price as u128 * quantity as u128;
+        assert!(expected > u64::MAX as u128);
+        assert_eq!(result.executed_value(), expected);
+        assert_eq!(result.average_price(), Some(price as f64));
+        assert_eq!(price_level.stats().value_executed(), expected);
+        assert_eq!(price_level.stats().average_execution_price(), price as f64);
+
+        let json = serde_json::to_string(price_level.stats()).unwrap();
+        let restored: PriceLevelStatistics = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.value_executed(), expected);
+        let restored = PriceLevelStatistics::from_str(&price_level.stats().to_string()).unwrap();
+        assert_eq!(restored.value_executed(), expected);
+    }
 
-        // Test display implementation
-        let display_string = price_level.to_string();
-        assert!(display_string.starts_with("PriceLevel:price=10000;"));
-        assert!(display_string.contains("display_quantity=80"));
-        assert!(display_string.contains("reserve_quantity=70"));
-        assert!(display_string.contains("order_count=2"));
+    #[test]
+    fn test_suppressed_orders_match_but_stay_out_of_feed() {
+        let generator = UuidGenerator::new(Uuid::nil());
+        let mut hidden = create_iceberg_order(2, 10000, 20, 40);
+        if let Order::IcebergOrder { common, .. } = &mut hidden {
+            common.suppress_in_feed = true;
+        }
 
-        // Test serialization
-        let serialized = serde_json::to_string(&price_level).unwrap();
-        assert!(serialized.contains("\"price\":10000"));
-        assert!(serialized.contains("\"display_quantity\":80"));
-        assert!(serialized.contains("\"reserve_quantity\":70"));
-        assert!(serialized.contains("\"order_count\":2"));
+        let mut price_level = PriceLevel::new(10000);
+        price_level
+            .add_order(create_iceberg_order(1, 10000, 10, 5))
+            .unwrap();
+        price_level.add_order(hidden).unwrap();
+        price_level
+            .add_order(create_iceberg_order(3, 10000, 30, 0))
+            .unwrap();
+
+        // Internal aggregates count everything, public ones leave the suppressed order out
+        assert_eq!(price_level.display_quantity(), 60);
+        assert_eq!(price_level.public_display_quantity(), 40);
+        assert_eq!(price_level.public_reserve_quantity(), 5);
+        assert_eq!(price_level.public_order_count(), 2);
+        let row = price_level.to_dom_row(Side::Sell);
+        assert_eq!(
+            (row.visible_qty, row.hidden_qty, row.order_count),
+            (40, 5, 2)
+        );
 
-        // Test deserialization
-        let deserialized: PriceLevel = serde_json::from_str(&serialized).unwrap();
-        assert_eq!(deserialized.price(), 10000);
-        assert_eq!(deserialized.display_quantity(), 80);
-        assert_eq!(deserialized.reserve_quantity(), 70);
-        assert_eq!(deserialized.order_count(), 2);
+        let public = price_level.public_snapshot();
+        assert_eq!(public.display_quantity, 40);
+        assert_eq!(public.reserve_quantity, 5);
+        assert_eq!(public.order_count, 2);
+        assert!(
+            public
+                .orders
+                .iter()
+                .all(|order| order.id() != OrderId::from_u64(2))
+        );
+        assert_eq!(price_level.snapshot().order_count, 3);
+        assert_eq!(price_level.iter_public_orders().len(), 2);
+        assert_eq!(price_level.iter_orders().len(), 3);
+
+        // The suppressed order keeps its queue position for matching
+        let result = price_level.match_order(25, OrderId::from_u64(100), &generator);
+        let makers: Vec<OrderId> = result
+            .transactions
+            .as_vec()
+            .iter()
+            .map(|t| t.maker_order_id)
+            .collect();
+        assert_eq!(makers, [1, 2].map(OrderId::from_u64));
+        assert_eq!(price_level.public_display_quantity(), 35);
+        assert_eq!(price_level.display_quantity(), 40);
+    }
+
+    #[test]
+    fn test_public_aggregates_clamp_drifted_totals() {
+        let mut hidden = create_iceberg_order(1, 10000, 20, 40);
+        if let Order::IcebergOrder { common, .. } = &mut hidden {
+            common.suppress_in_feed = true;
+        }
+        let mut price_level = PriceLevel::new(10000)
+            .with_arithmetic_policy(ArithmeticPolicy::Saturating)
+            .with_drop_check(false);
+        price_level.add_order(hidden).unwrap();
+
+        // Aggregates that drifted below the suppressed totals clamp at 0
+        price_level.display_quantity = 10;
+        price_level.reserve_quantity = 0;
+        price_level.order_count = 0;
+        assert_eq!(price_level.public_display_quantity(), 0);
+        assert_eq!(price_level.public_reserve_quantity(), 0);
+        assert_eq!(price_level.public_order_count(), 0);
+        let row = price_level.to_dom_row(Side::Buy);
+        assert_eq!(
+            (row.visible_qty, row.hidden_qty, row.order_count),
+            (0, 0, 0)
+        );
     }
 }
 
 #[cfg(test)]
 mod tests_eq {
-    use crate::PriceLevel;
+    use crate::{PriceLevel, Side};
 
     #[test]
     fn test_price_level_partial_eq() {
@@ -2667,4 +8570,73 @@ mod tests_eq {
         assert_eq!(price_level2.cmp(&price_level1), std::cmp::Ordering::Greater);
         assert_eq!(price_level2.cmp(&price_level2), std::cmp::Ordering::Equal);
     }
+
+    #[test]
+    fn test_price_level_cmp_for_side() {
+        let mut bids = [
+            PriceLevel::new(9900),
+            PriceLevel::new(10000),
+            PriceLevel::new(9800),
+        ];
+        bids.sort_by(|a, b| a.cmp_for_side(b, Side::Buy));
+        let bid_prices: Vec<u64> = bids.iter().map(|level| level.price()).collect();
+        assert_eq!(bid_prices, vec![10000, 9900, 9800]);
+
+        let mut asks = [
+            PriceLevel::new(10200),
+            PriceLevel::new(10100),
+            PriceLevel::new(10300),
+        ];
+        asks.sort_by(|a, b| a.cmp_for_side(b, Side::Sell));
+        let ask_prices: Vec<u64> = asks.iter().map(|level| level.price()).collect();
+        assert_eq!(ask_prices, vec![10100, 10200, 10300]);
+
+        let level = PriceLevel::new(10000);
+        assert_eq!(
+            level.cmp_for_side(&PriceLevel::new(10000), Side::Buy),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_price_level_content_eq() {
+        use crate::{Order, OrderCommon, OrderId, TimeInForce};
+
+        let order = |id: u64, quantity: u64| Order::Standard {
+            common: OrderCommon {
+                id: OrderId::from_u64(id),
+                price: 10000,
+                display_quantity: quantity,
+                side: Side::Buy,
+                timestamp: 1616823000000,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+        };
+
+        let mut level1 = PriceLevel::new(10000);
+        let mut level2 = PriceLevel::new(10000);
+        level1.add_order(order(1, 100)).unwrap();
+        level2.add_order(order(2, 100)).unwrap();
+
+        // Same price compares equal, but the contents differ
+        assert_eq!(level1, level2);
+        assert!(!level1.content_eq(&level2));
+        assert_ne!(level1.snapshot(), level2.snapshot());
+
+        let mut level3 = PriceLevel::new(10000);
+        level3.add_order(order(1, 100)).unwrap();
+        assert!(level1.content_eq(&level3));
+        assert_eq!(level1.snapshot(), level3.snapshot());
+
+        // Queue order is part of the contents
+        level1.add_order(order(2, 100)).unwrap();
+        level3.add_order(order(2, 100)).unwrap();
+        level2.add_order(order(1, 100)).unwrap();
+        assert!(level1.content_eq(&level3));
+        assert!(!level1.content_eq(&level2));
+    }
 }
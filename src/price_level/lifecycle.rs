@@ -0,0 +1,427 @@
+use crate::errors::PriceLevelError;
+use crate::order::{OrderId, Quantity};
+use crate::price_level::LevelEvent;
+
+/// Quantity accounting for one order over its lifecycle, as checked by [`LifecycleValidator`].
+///
+/// `initial == filled + cancelled + moved + discarded + resting` always holds for a summary
+/// returned by the validator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LifecycleSummary {
+    /// Total (display + reserve) quantity the order was added with, plus any increase from
+    /// later updates
+    pub initial: Quantity,
+    /// Quantity executed against the order
+    pub filled: Quantity,
+    /// Quantity removed by a cancellation or by an update that reduced the order
+    pub cancelled: Quantity,
+    /// Quantity that left the level when the order was repriced to another one
+    pub moved: Quantity,
+    /// Reserve that left the level with the order when a fill exhausted its display and
+    /// nothing was revealed, e.g. without auto-replenishment or at the display cap
+    pub discarded: Quantity,
+    /// Display quantity still resting at the end of the trace
+    pub resting_display: Quantity,
+    /// Reserve quantity still resting at the end of the trace
+    pub resting_reserve: Quantity,
+}
+
+impl LifecycleSummary {
+    /// Get the total quantity still resting at the end of the trace
+    pub fn resting(&self) -> Quantity {
+        self.resting_display + self.resting_reserve
+    }
+
+    /// Whether the order left the level, by being fully filled, cancelled or moved
+    pub fn is_closed(&self) -> bool {
+        self.resting() == 0
+    }
+}
+
+/// Checks the [`LevelEvent`] trace of a single order for legal transitions and consistent
+/// quantities.
+///
+/// The order must be added exactly once, before anything else happens to it. Fills may only
+/// execute displayed quantity, replenishments may only reveal reserve the order still has,
+/// and nothing may happen after the order is fully filled, cancelled or moved. Events for other
+/// orders are ignored, so a whole level's event stream can be passed in. This catches, for
+/// instance, a replenishment counted twice, which would reveal more than the reserve.
+///
+/// Intended for tests and audits; the error describes the first violation.
+///
+/// # Examples
+///
+/// ```
+/// use pricelevel::{
+///     IcebergRefresh, LevelEvent, LifecycleValidator, Order, OrderCommon, OrderId, PriceLevel,
+///     Side, TimeInForce, UuidGenerator,
+/// };
+/// use uuid::Uuid;
+///
+/// let (sender, receiver) = std::sync::mpsc::channel();
+/// let mut level = PriceLevel::new(10000).with_event_sender(sender);
+/// let generator = UuidGenerator::new(Uuid::nil());
+///
+/// // An iceberg showing 10 with 15 in reserve
+/// level.add_order(Order::IcebergOrder {
+///     common: OrderCommon {
+///         id: OrderId::from_u64(1),
+///         price: 10000,
+///         display_quantity: 10,
+///         side: Side::Sell,
+///         timestamp: 1616823000000,
+///         time_in_force: TimeInForce::Gtc,
+///         extra_fields: (),
+///         parent_id: None,
+///         oco_group: None,
+///         suppress_in_feed: false,
+///     },
+///     reserve_quantity: 15,
+///     original_display: 10,
+///     refresh_strategy: IcebergRefresh::OriginalDisplay,
+///     last_reveal_ms: None,
+/// }).unwrap();
+/// level.match_order(4, OrderId::from_u64(100), &generator); // partial fill
+/// level.match_order(6, OrderId::from_u64(101), &generator); // slice consumed, refreshed
+/// level.match_order(3, OrderId::from_u64(102), &generator);
+///
+/// let events: Vec<LevelEvent> = receiver.try_iter().collect();
+/// let summary = LifecycleValidator::new(OrderId::from_u64(1))
+///     .validate(&events)
+///     .unwrap();
+/// assert_eq!(summary.initial, 25);
+/// assert_eq!(summary.filled, 13);
+/// assert_eq!(summary.resting(), 12);
+/// assert_eq!(summary.resting_display, level.display_quantity());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifecycleValidator {
+    order_id: OrderId,
+}
+
+impl LifecycleValidator {
+    /// Create a validator for the lifecycle of `order_id`
+    pub fn new(order_id: OrderId) -> Self {
+        Self { order_id }
+    }
+
+    /// Get the order ID this validator checks
+    pub fn order_id(&self) -> OrderId {
+        self.order_id
+    }
+
+    /// Validate the events of the order, returning its quantity accounting.
+    ///
+    /// A fill that exhausts the display removes the order unless a replenishment follows
+    /// it, so its remaining reserve then counts as discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PriceLevelError::InvalidLifecycle`] describing the first violation.
+    pub fn validate(&self, events: &[LevelEvent]) -> Result<LifecycleSummary, PriceLevelError> {
+        let order_id = self.order_id;
+        let invalid = |message: String| PriceLevelError::InvalidLifecycle { order_id, message };
+        let mut summary: Option<LifecycleSummary> = None;
+        // The last fill exhausted the display, so the order left unless a refresh follows
+        let mut drained = false;
+
+        for (index, event) in events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| event.order_id() == order_id)
+        {
+            let state = match (event, summary.as_mut()) {
+                (LevelEvent::OrderAdded { order, .. }, None) => {
+                    let added = LifecycleSummary {
+                        initial: order.display_quantity() + order.reserve_quantity(),
+                        resting_display: order.display_quantity(),
+                        resting_reserve: order.reserve_quantity(),
+                        ..LifecycleSummary::default()
+                    };
+                    summary = Some(added);
+                    continue;
+                }
+                (_, None) => {
+                    return Err(invalid(format!("event {index}: used before it was added")));
+                }
+                (LevelEvent::OrderAdded { .. }, Some(_)) => {
+                    return Err(invalid(format!("event {index}: added twice")));
+                }
+                (_, Some(state)) => {
+                    if drained && !matches!(event, LevelEvent::OrderReplenished { .. }) {
+                        discard_reserve(state);
+                    }
+                    drained = false;
+                    if state.is_closed() {
+                        return Err(invalid(format!("event {index}: already left the level")));
+                    }
+                    state
+                }
+            };
+
+            match event {
+                LevelEvent::OrderFilled { transaction, .. } => {
+                    if transaction.quantity == 0 || transaction.quantity > state.resting_display {
+                        return Err(invalid(format!(
+                            "event {index}: fill of {} against {} displayed",
+                            transaction.quantity, state.resting_display
+                        )));
+                    }
+                    state.resting_display -= transaction.quantity;
+                    state.filled += transaction.quantity;
+                    drained = state.resting_display == 0;
+                }
+                LevelEvent::OrderReplenished { quantity, .. } => {
+                    if *quantity > state.resting_reserve {
+                        return Err(invalid(format!(
+                            "event {index}: replenishment of {quantity} from {} in reserve",
+                            state.resting_reserve
+                        )));
+                    }
+                    state.resting_reserve -= quantity;
+                    state.resting_display += quantity;
+                }
+                LevelEvent::OrderUpdated { order, .. } => {
+                    let total = order.display_quantity() + order.reserve_quantity();
+                    if total > state.resting() {
+                        state.initial += total - state.resting();
+                    } else {
+                        state.cancelled += state.resting() - total;
+                    }
+                    state.resting_display = order.display_quantity();
+                    state.resting_reserve = order.reserve_quantity();
+                }
+                LevelEvent::OrderCancelled { .. } => {
+                    state.cancelled += state.resting();
+                    state.resting_display = 0;
+                    state.resting_reserve = 0;
+                }
+                LevelEvent::OrderMoved { .. } => {
+                    state.moved += state.resting();
+                    state.resting_display = 0;
+                    state.resting_reserve = 0;
+                }
+                LevelEvent::OrderAdded { .. } => unreachable!("handled above"),
+            }
+        }
+
+        let mut summary = summary.ok_or_else(|| invalid("never added".to_string()))?;
+        if drained {
+            discard_reserve(&mut summary);
+        }
+        if summary.initial
+            != summary.filled
+                + summary.cancelled
+                + summary.moved
+                + summary.discarded
+                + summary.resting()
+        {
+            return Err(invalid(format!(
+                "started with {} but {} filled, {} cancelled, {} moved, {} discarded and {} \
+                 resting",
+                summary.initial,
+                summary.filled,
+                summary.cancelled,
+                summary.moved,
+                summary.discarded,
+                summary.resting()
+            )));
+        }
+        Ok(summary)
+    }
+}
+
+/// Count the reserve of an order that left the level with an exhausted display as discarded
+fn discard_reserve(state: &mut LifecycleSummary) {
+    state.discarded += state.resting_reserve;
+    state.resting_reserve = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::PriceLevelError;
+    use crate::execution::Transaction;
+    use crate::order::{
+        CancelReason, IcebergRefresh, Order, OrderCommon, OrderId, Side, TimeInForce,
+    };
+    use crate::price_level::{LevelEvent, LifecycleValidator};
+    use uuid::Uuid;
+
+    const PRICE: u64 = 10000;
+
+    fn added(id: u64, display: u64, reserve: u64) -> LevelEvent {
+        LevelEvent::OrderAdded {
+            price: PRICE,
+            order: Order::IcebergOrder {
+                common: OrderCommon {
+                    id: OrderId::from_u64(id),
+                    price: PRICE,
+                    display_quantity: display,
+                    side: Side::Sell,
+                    timestamp: 1616823000000,
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
+                },
+                reserve_quantity: reserve,
+                original_display: display,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
+            },
+        }
+    }
+
+    fn filled(id: u64, quantity: u64) -> LevelEvent {
+        LevelEvent::OrderFilled {
+            price: PRICE,
+            transaction: Transaction::new(
+                Uuid::nil(),
+                OrderId::from_u64(999),
+                OrderId::from_u64(id),
+                PRICE,
+                quantity,
+                Side::Buy,
+            ),
+        }
+    }
+
+    fn replenished(id: u64, quantity: u64) -> LevelEvent {
+        LevelEvent::OrderReplenished {
+            price: PRICE,
+            order_id: OrderId::from_u64(id),
+            quantity,
+        }
+    }
+
+    fn cancelled(id: u64) -> LevelEvent {
+        LevelEvent::OrderCancelled {
+            price: PRICE,
+            order_id: OrderId::from_u64(id),
+            reason: CancelReason::UserRequested,
+        }
+    }
+
+    fn updated(id: u64, display: u64, reserve: u64) -> LevelEvent {
+        let LevelEvent::OrderAdded { order, .. } = added(id, display, reserve) else {
+            unreachable!("added builds an OrderAdded event");
+        };
+        LevelEvent::OrderUpdated {
+            price: PRICE,
+            order,
+        }
+    }
+
+    fn moved(id: u64) -> LevelEvent {
+        LevelEvent::OrderMoved {
+            price: PRICE,
+            order_id: OrderId::from_u64(id),
+            new_price: PRICE + 1,
+        }
+    }
+
+    #[test]
+    fn test_valid_lifecycles() {
+        let validator = LifecycleValidator::new(OrderId::from_u64(1));
+
+        let events = [
+            added(1, 10, 20),
+            added(2, 5, 0),
+            filled(1, 10),
+            replenished(1, 10),
+            filled(2, 5),
+            filled(1, 4),
+            cancelled(1),
+        ];
+        let summary = validator.validate(&events).unwrap();
+        assert_eq!(summary.initial, 30);
+        assert_eq!(summary.filled, 14);
+        assert_eq!(summary.cancelled, 16);
+        assert!(summary.is_closed());
+
+        let summary = validator
+            .validate(&[added(1, 10, 0), filled(1, 10)])
+            .unwrap();
+        assert_eq!(summary.filled, 10);
+        assert!(summary.is_closed());
+
+        // Updates adjust what was added or count as a partial cancel; a move closes the order
+        let events = [
+            added(1, 10, 0),
+            updated(1, 25, 0),
+            filled(1, 5),
+            updated(1, 8, 0),
+            moved(1),
+        ];
+        let summary = validator.validate(&events).unwrap();
+        assert_eq!(summary.initial, 25);
+        assert_eq!(summary.filled, 5);
+        assert_eq!(summary.cancelled, 12);
+        assert_eq!(summary.moved, 8);
+        assert!(summary.is_closed());
+        assert!(
+            validator
+                .validate(&[added(1, 10, 0), moved(1), filled(1, 1)])
+                .is_err()
+        );
+
+        // Reserve left behind by a fill that exhausted the display is discarded, not resting
+        let summary = validator
+            .validate(&[added(1, 10, 20), filled(1, 10)])
+            .unwrap();
+        assert_eq!(summary.discarded, 20);
+        assert_eq!(summary.resting(), 0);
+        assert!(summary.is_closed());
+        let summary = validator
+            .validate(&[added(1, 10, 20), filled(1, 10), replenished(1, 10)])
+            .unwrap();
+        assert_eq!(summary.discarded, 0);
+        assert_eq!(summary.resting(), 20);
+    }
+
+    #[test]
+    fn test_invalid_lifecycles() {
+        let validator = LifecycleValidator::new(OrderId::from_u64(1));
+
+        // A replenishment counted twice reveals more than the reserve
+        let double_counted = [
+            added(1, 10, 15),
+            filled(1, 10),
+            replenished(1, 10),
+            replenished(1, 10),
+        ];
+        assert!(validator.validate(&double_counted).is_err());
+
+        assert!(validator.validate(&[filled(1, 5)]).is_err());
+        assert!(
+            validator
+                .validate(&[added(1, 10, 0), filled(1, 11)])
+                .is_err()
+        );
+        assert!(
+            validator
+                .validate(&[added(1, 10, 0), filled(1, 10), cancelled(1)])
+                .is_err()
+        );
+        assert!(
+            validator
+                .validate(&[added(1, 10, 0), added(1, 10, 0)])
+                .is_err()
+        );
+        assert!(validator.validate(&[added(2, 10, 0)]).is_err());
+        assert!(
+            validator
+                .validate(&[added(1, 10, 5), filled(1, 10), cancelled(1)])
+                .is_err()
+        );
+
+        match validator.validate(&[added(1, 10, 0), added(1, 10, 0)]) {
+            Err(PriceLevelError::InvalidLifecycle { order_id, message }) => {
+                assert_eq!(order_id, OrderId::from_u64(1));
+                assert_eq!(message, "event 1: added twice");
+            }
+            other => panic!("Expected InvalidLifecycle, got {other:?}"),
+        }
+    }
+}
@@ -1,10 +1,36 @@
+mod aggregate;
+mod arithmetic;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod determinism;
+mod dom_row;
 mod entry;
+mod event;
 mod level;
+mod lifecycle;
 mod order_queue;
+mod pressure;
+mod signed;
 mod snapshot;
 mod statistics;
 
-pub use level::{PriceLevel, PriceLevelData};
+pub use aggregate::PriceLevelAggregate;
+pub use arithmetic::ArithmeticPolicy;
+pub use determinism::{DeterminismLog, LevelOp};
+pub use dom_row::DomRow;
+pub use event::LevelEvent;
+pub use level::{
+    PriceLevel, PriceLevelData, apply_match_result, crossed_quantity, move_order, sum_contributions,
+};
+pub use lifecycle::{LifecycleSummary, LifecycleValidator};
 pub use order_queue::OrderQueue;
-pub use snapshot::{PriceLevelSnapshot, PriceLevelSnapshotPackage};
-pub use statistics::PriceLevelStatistics;
+pub use pressure::PressureFormula;
+pub use signed::{
+    SignedMatchResult, SignedPriceLevel, SignedTransaction, decode_signed_price,
+    encode_signed_price,
+};
+pub use snapshot::{
+    PriceLevelSnapshot, PriceLevelSnapshotPackage, PriceLevelSnapshotWithStats,
+    verify_snapshot_batch,
+};
+pub use statistics::{PriceLevelStatistics, WAITING_TIME_SAMPLES};
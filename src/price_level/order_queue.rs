@@ -1,26 +1,43 @@
+use crate::errors::PriceLevelError;
 use crate::order::{Order, OrderId};
 use serde::de::{SeqAccess, Visitor};
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use slab::Slab;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt;
 use std::marker::PhantomData;
 use std::str::FromStr;
+use std::sync::Arc;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Entry {
     order: Order<()>,
     prev: Option<usize>,
     next: Option<usize>,
 }
 
-#[derive(Debug)]
+/// Orders two resting orders by matching priority; `Less` matches first.
+type Comparator = Arc<dyn Fn(&Order<()>, &Order<()>) -> Ordering + Send + Sync>;
+
+/// Cloning a queue copies its orders and shares its comparator.
+#[derive(Debug, Clone)]
 pub struct OrderQueue {
     orders: Slab<Entry>,
     index: HashMap<OrderId, usize>,
     head: Option<usize>,
     tail: Option<usize>,
+    comparator: Option<QueueComparator>,
+}
+
+#[derive(Clone)]
+struct QueueComparator(Comparator);
+
+impl fmt::Debug for QueueComparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("QueueComparator")
+    }
 }
 
 impl OrderQueue {
@@ -31,9 +48,48 @@ impl OrderQueue {
             index: HashMap::new(),
             head: None,
             tail: None,
+            comparator: None,
+        }
+    }
+
+    /// Create a new empty order queue that keeps its orders sorted by `cmp` instead of by
+    /// arrival.
+    ///
+    /// Orders that compare `Less` are matched first, and orders that compare `Equal` keep
+    /// their arrival order, so a comparator that always returns `Equal` is plain FIFO. An
+    /// order pushed back after a partial fill is re-sorted by its new state. Restoring
+    /// orders that were popped but not consumed still puts them at the head.
+    pub fn with_comparator(
+        cmp: impl Fn(&Order<()>, &Order<()>) -> Ordering + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            comparator: Some(QueueComparator(Arc::new(cmp))),
+            ..Self::new()
+        }
+    }
+
+    /// Sort the queue by `cmp` from now on, re-sorting the orders already queued.
+    ///
+    /// Orders that compare `Equal` keep their current relative order. Comparators are not
+    /// serialized, so a queue restored with [`from_json`](Self::from_json) or deserialized
+    /// is FIFO until its comparator is set again with this method.
+    pub fn set_comparator(
+        &mut self,
+        cmp: impl Fn(&Order<()>, &Order<()>) -> Ordering + Send + Sync + 'static,
+    ) {
+        let orders = self.to_vec();
+        self.clear(true);
+        self.comparator = Some(QueueComparator(Arc::new(cmp)));
+        for order in orders {
+            self.push(order);
         }
     }
 
+    /// Whether the queue is sorted by a [comparator](Self::with_comparator) rather than FIFO
+    pub(crate) fn has_comparator(&self) -> bool {
+        self.comparator.is_some()
+    }
+
     /// Create a new empty order queue with capacity
     pub fn with_capacity(cap: usize) -> Self {
         Self {
@@ -41,34 +97,63 @@ impl OrderQueue {
             index: HashMap::with_capacity(cap),
             head: None,
             tail: None,
+            comparator: None,
         }
     }
 
     /// Add an order to the queue (FIFO push_back)
+    ///
+    /// With a [comparator](Self::with_comparator) the order is instead inserted behind every
+    /// order that does not compare greater than it.
     pub fn push(&mut self, order: Order<()>) -> &Order<()> {
-        let order_id = order.id();
+        if self.index.contains_key(&order.id()) {
+            let _ = self.remove(&order.id());
+        }
 
-        if self.index.contains_key(&order_id) {
-            let _ = self.remove(&order_id);
+        let mut prev = self.tail;
+        if let Some(QueueComparator(cmp)) = &self.comparator {
+            while let Some(key) = prev
+                && cmp(&self.orders[key].order, &order) == Ordering::Greater
+            {
+                prev = self.orders[key].prev;
+            }
         }
 
-        let prev = self.tail;
-        let entry = Entry {
-            order,
-            prev,
-            next: None,
+        let key = self.link_after(prev, order);
+        &self.orders[key].order
+    }
+
+    /// Insert an order right after `prev`, or at the head when `prev` is `None`.
+    fn link_after(&mut self, prev: Option<usize>, order: Order<()>) -> usize {
+        let order_id = order.id();
+        let next = match prev {
+            Some(pk) => self.orders[pk].next,
+            None => self.head,
         };
-        let key = self.orders.insert(entry);
+        let key = self.orders.insert(Entry { order, prev, next });
 
-        if let Some(t) = self.tail {
-            self.orders[t].next = Some(key);
-        } else {
-            self.head = Some(key);
+        match prev {
+            Some(pk) => self.orders[pk].next = Some(key),
+            None => self.head = Some(key),
+        }
+        match next {
+            Some(nk) => self.orders[nk].prev = Some(key),
+            None => self.tail = Some(key),
         }
-        self.tail = Some(key);
 
         self.index.insert(order_id, key);
-        &self.orders[key].order
+        key
+    }
+
+    /// Add an order to the head of the queue, ahead of every resting order.
+    ///
+    /// Used to restore orders that were popped but not consumed.
+    pub(crate) fn push_front(&mut self, order: Order<()>) {
+        if self.index.contains_key(&order.id()) {
+            let _ = self.remove(&order.id());
+        }
+
+        self.link_after(None, order);
     }
 
     /// Attempt to pop an order from the head of the queue
@@ -89,6 +174,19 @@ impl OrderQueue {
         Some(order)
     }
 
+    /// Get the order at the head of the queue without removing it
+    pub fn front(&self) -> Option<&Order<()>> {
+        self.head.map(|key| &self.orders[key].order)
+    }
+
+    /// Get the first `n` orders from the head of the queue without removing them.
+    ///
+    /// Like [`front`](Self::front), this only borrows the orders; fewer than `n` are returned
+    /// if the queue is shorter.
+    pub fn peek_n(&self, n: usize) -> Vec<&Order<()>> {
+        self.iter().take(n).collect()
+    }
+
     /// Find an order by ID
     pub fn find(&self, order_id: &OrderId) -> Option<&Order<()>> {
         self.index
@@ -97,6 +195,14 @@ impl OrderQueue {
             .map(|e| &e.order)
     }
 
+    /// Find an order by ID for in-place modification.
+    ///
+    /// Callers must not change the order ID, since the index is keyed by it.
+    pub(crate) fn find_mut(&mut self, order_id: &OrderId) -> Option<&mut Order<()>> {
+        let key = *self.index.get(order_id)?;
+        self.orders.get_mut(key).map(|e| &mut e.order)
+    }
+
     /// Remove an order by ID (O(1), no tombstone)
     pub fn remove(&mut self, order_id: &OrderId) -> Option<Order<()>> {
         let key = *self.index.get(order_id)?;
@@ -121,6 +227,21 @@ impl OrderQueue {
         Some(order)
     }
 
+    /// Remove every order from the queue in one shot.
+    ///
+    /// With `retain_capacity` the backing storage is kept for reuse; otherwise it is freed.
+    pub fn clear(&mut self, retain_capacity: bool) {
+        if retain_capacity {
+            self.orders.clear();
+            self.index.clear();
+        } else {
+            self.orders = Slab::new();
+            self.index = HashMap::new();
+        }
+        self.head = None;
+        self.tail = None;
+    }
+
     /// Convert queue to vector (for iteration)
     pub fn to_vec(&self) -> Vec<Order<()>> {
         self.iter().cloned().collect()
@@ -136,6 +257,27 @@ impl OrderQueue {
         q
     }
 
+    /// Serialize just the orders, in queue order, to a JSON array.
+    ///
+    /// Unlike a level snapshot this carries no aggregates or statistics, which can be
+    /// derived from the orders when the queue is restored. The comparator is not included
+    /// either; reinstall it on the restored queue with [`set_comparator`](Self::set_comparator).
+    pub fn to_json(&self) -> Result<String, PriceLevelError> {
+        serde_json::to_string(self).map_err(|error| PriceLevelError::SerializationError {
+            message: error.to_string(),
+        })
+    }
+
+    /// Rebuild a queue, including its ID index, from a JSON array of orders.
+    ///
+    /// The queue is FIFO in the order of the array; a queue that was sorted by a comparator
+    /// needs it set again with [`set_comparator`](Self::set_comparator).
+    pub fn from_json(data: &str) -> Result<Self, PriceLevelError> {
+        serde_json::from_str(data).map_err(|error| PriceLevelError::DeserializationError {
+            message: error.to_string(),
+        })
+    }
+
     /// Check if the queue is empty
     pub fn is_empty(&self) -> bool {
         self.index.is_empty()
@@ -267,10 +409,33 @@ mod tests {
                 time_in_force: TimeInForce::Gtc,
                 timestamp: 0,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         }
     }
 
+    #[test]
+    fn test_peek_n_leaves_queue_unchanged() {
+        let mut queue = OrderQueue::new();
+        assert!(queue.peek_n(2).is_empty());
+        for id in 1..=3 {
+            queue.push(create_test_order(id, 100, 10 * id));
+        }
+
+        for _ in 0..3 {
+            let peeked = queue.peek_n(2);
+            assert_eq!(peeked.len(), 2);
+            assert_eq!(peeked[0].id(), OrderId::from_u64(1));
+            assert_eq!(peeked[1].id(), OrderId::from_u64(2));
+            assert!(std::ptr::eq(peeked[0], queue.front().unwrap()));
+            assert_eq!(queue.len(), 3);
+        }
+        assert_eq!(queue.peek_n(10).len(), 3);
+        assert_eq!(queue.pop().unwrap().id(), OrderId::from_u64(1));
+    }
+
     #[test]
     fn test_display() {
         let mut queue = OrderQueue::new();
@@ -386,7 +551,8 @@ mod tests {
         let found_order = queue.find(&order1_id).unwrap();
         assert_eq!(found_order.price(), 100);
 
-        // Test pop (FIFO order)
+        // Test front and pop (FIFO order)
+        assert_eq!(queue.front().map(|order| order.id()), Some(order1_id));
         let popped = queue.pop().unwrap();
         assert_eq!(popped.price(), 100);
         assert_eq!(queue.len(), 1);
@@ -396,6 +562,7 @@ mod tests {
         assert_eq!(removed.price(), 101);
         assert_eq!(queue.len(), 0);
         assert!(queue.is_empty());
+        assert!(queue.front().is_none());
     }
 
     #[test]
@@ -490,4 +657,131 @@ mod tests {
         assert_eq!(remaining[0].price(), 104);
         assert_eq!(remaining[1].price(), 105);
     }
+
+    #[test]
+    fn test_order_queue_clear() {
+        for retain_capacity in [true, false] {
+            let mut queue = OrderQueue::new();
+            for id in 1..=3 {
+                queue.push(create_test_order(id, 1000, 10));
+            }
+
+            queue.clear(retain_capacity);
+            assert_eq!(queue.len(), 0);
+            assert!(queue.is_empty());
+            assert!(queue.pop().is_none());
+            assert!(queue.find(&OrderId::from_u64(1)).is_none());
+
+            queue.push(create_test_order(4, 1000, 10));
+            queue.push(create_test_order(5, 1000, 20));
+            assert_eq!(queue.len(), 2);
+            assert_eq!(queue.pop().unwrap().id(), OrderId::from_u64(4));
+            assert_eq!(queue.pop().unwrap().id(), OrderId::from_u64(5));
+            assert!(queue.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_order_queue_push_front() {
+        let mut queue = OrderQueue::new();
+        queue.push_front(create_test_order(2, 1000, 10));
+        queue.push(create_test_order(3, 1000, 10));
+        queue.push_front(create_test_order(1, 1000, 10));
+
+        let ids: Vec<OrderId> = queue.iter().map(|o| o.id()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                OrderId::from_u64(1),
+                OrderId::from_u64(2),
+                OrderId::from_u64(3)
+            ]
+        );
+        assert!(queue.find(&OrderId::from_u64(1)).is_some());
+        assert_eq!(queue.pop().unwrap().id(), OrderId::from_u64(1));
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn test_order_queue_json_round_trip() {
+        let mut queue = OrderQueue::new();
+        for id in [3, 1, 2] {
+            queue.push(create_test_order(id, 1000, id * 10));
+        }
+        queue.remove(&OrderId::from_u64(1));
+        queue.push(create_test_order(4, 1000, 40));
+
+        let json = queue.to_json().unwrap();
+        let restored = OrderQueue::from_json(&json).unwrap();
+        assert_eq!(restored.len(), queue.len());
+        assert_eq!(restored.to_vec(), queue.to_vec());
+
+        // The index is rebuilt, so lookups work on the restored queue
+        assert_eq!(
+            restored
+                .find(&OrderId::from_u64(2))
+                .map(|o| o.display_quantity()),
+            Some(20)
+        );
+        assert!(restored.find(&OrderId::from_u64(1)).is_none());
+
+        assert!(OrderQueue::from_json("{\"orders\":[]}").is_err());
+    }
+
+    #[test]
+    fn test_order_queue_with_comparator() {
+        // Largest orders first, ties in arrival order
+        let mut queue =
+            OrderQueue::with_comparator(|a, b| b.display_quantity().cmp(&a.display_quantity()));
+        for (id, quantity) in [(1, 10), (2, 30), (3, 20), (4, 30), (5, 5)] {
+            queue.push(create_test_order(id, 1000, quantity));
+        }
+        let ids: Vec<OrderId> = queue.iter().map(|o| o.id()).collect();
+        assert_eq!(ids, [2, 4, 3, 1, 5].map(OrderId::from_u64));
+
+        // Restored orders still go to the head; pushes are re-sorted
+        let first = queue.pop().unwrap();
+        queue.push_front(first);
+        assert_eq!(queue.iter().next().unwrap().id(), OrderId::from_u64(2));
+        queue.push(create_test_order(2, 1000, 1));
+        let ids: Vec<OrderId> = queue.iter().map(|o| o.id()).collect();
+        assert_eq!(ids, [4, 3, 1, 5, 2].map(OrderId::from_u64));
+
+        // The comparator survives a clear
+        queue.clear(false);
+        queue.push(create_test_order(6, 1000, 1));
+        queue.push(create_test_order(7, 1000, 2));
+        assert_eq!(queue.iter().next().unwrap().id(), OrderId::from_u64(7));
+    }
+
+    #[test]
+    fn test_order_queue_comparator_after_restore() {
+        let by_size =
+            |a: &Order<()>, b: &Order<()>| b.display_quantity().cmp(&a.display_quantity());
+        let mut queue = OrderQueue::with_comparator(by_size);
+        for (id, quantity) in [(1, 10), (2, 30), (3, 20)] {
+            queue.push(create_test_order(id, 1000, quantity));
+        }
+
+        // The restored queue keeps the order, but new pushes are FIFO until the comparator
+        // is set again
+        let mut restored = OrderQueue::from_json(&queue.to_json().unwrap()).unwrap();
+        let ids: Vec<OrderId> = restored.iter().map(|o| o.id()).collect();
+        assert_eq!(ids, [2, 3, 1].map(OrderId::from_u64));
+        restored.set_comparator(by_size);
+        restored.push(create_test_order(4, 1000, 25));
+        queue.push(create_test_order(4, 1000, 25));
+        let ids: Vec<OrderId> = restored.iter().map(|o| o.id()).collect();
+        assert_eq!(ids, [2, 4, 3, 1].map(OrderId::from_u64));
+        assert_eq!(restored.to_vec(), queue.to_vec());
+
+        // Setting a comparator re-sorts a FIFO queue, keeping ties in queue order
+        let mut fifo = OrderQueue::new();
+        for (id, quantity) in [(1, 10), (2, 30), (3, 10)] {
+            fifo.push(create_test_order(id, 1000, quantity));
+        }
+        fifo.set_comparator(by_size);
+        let ids: Vec<OrderId> = fifo.iter().map(|o| o.id()).collect();
+        assert_eq!(ids, [2, 1, 3].map(OrderId::from_u64));
+    }
 }
@@ -0,0 +1,56 @@
+use crate::order::Quantity;
+use serde::{Deserialize, Serialize};
+
+/// Formula used by [`PriceLevel::pressure_with`](crate::PriceLevel::pressure_with) to turn a
+/// level's visible liquidity into a single density score.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PressureFormula {
+    /// Average visible size per order: `display_quantity / max(order_count, 1)`.
+    /// Low values on a busy level point to many tiny orders.
+    #[default]
+    AverageOrderSize,
+
+    /// Orders per 1000 units of visible quantity: `order_count * 1000 / max(display_quantity, 1)`.
+    /// High values point to many tiny orders.
+    OrdersPerThousand,
+
+    /// Visible quantity weighted by the number of orders: `display_quantity * order_count`.
+    WeightedQuantity,
+}
+
+impl PressureFormula {
+    /// Compute the score for the given visible quantity and order count, saturating at
+    /// `u64::MAX`.
+    pub fn score(&self, display_quantity: Quantity, order_count: usize) -> u64 {
+        let count = order_count as u64;
+        match self {
+            PressureFormula::AverageOrderSize => display_quantity / count.max(1),
+            PressureFormula::OrdersPerThousand => {
+                count.saturating_mul(1000) / display_quantity.max(1)
+            }
+            PressureFormula::WeightedQuantity => display_quantity.saturating_mul(count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::price_level::PressureFormula;
+
+    #[test]
+    fn test_pressure_formulas() {
+        assert_eq!(PressureFormula::AverageOrderSize.score(100, 4), 25);
+        assert_eq!(PressureFormula::AverageOrderSize.score(100, 0), 100);
+        assert_eq!(PressureFormula::OrdersPerThousand.score(100, 4), 40);
+        assert_eq!(PressureFormula::OrdersPerThousand.score(0, 4), 4000);
+        assert_eq!(PressureFormula::WeightedQuantity.score(100, 4), 400);
+        assert_eq!(
+            PressureFormula::WeightedQuantity.score(u64::MAX, 2),
+            u64::MAX
+        );
+        assert_eq!(
+            PressureFormula::default(),
+            PressureFormula::AverageOrderSize
+        );
+    }
+}
@@ -0,0 +1,351 @@
+use crate::UuidGenerator;
+use crate::errors::PriceLevelError;
+use crate::execution::{MatchResult, Transaction};
+use crate::order::{CancelReason, Order, OrderId, Price, Quantity, Side, Timestamp};
+use crate::price_level::PriceLevel;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Bit flipped to map signed prices onto the unsigned price space
+const SIGN_BIT: u64 = 1 << 63;
+
+/// Map a signed price onto the unsigned [`Price`] space, preserving order.
+///
+/// `i64::MIN` maps to 0, 0 to `2^63` and `i64::MAX` to `u64::MAX`, so comparing encoded
+/// prices gives the same result as comparing the signed ones.
+pub fn encode_signed_price(price: i64) -> Price {
+    (price as u64) ^ SIGN_BIT
+}
+
+/// Recover the signed price from a price produced by [`encode_signed_price`].
+pub fn decode_signed_price(price: Price) -> i64 {
+    (price ^ SIGN_BIT) as i64
+}
+
+/// A price level for instruments that can trade at zero or negative prices, such as
+/// spreads and some energy contracts.
+///
+/// It wraps a [`PriceLevel`] whose price is the [`encode_signed_price`] of the signed
+/// price, so matching, statistics and snapshots all reuse the unsigned implementation.
+/// Orders going in must already carry the encoded price, and matches come back as a
+/// [`SignedMatchResult`] with decoded prices. The underlying [`level`](Self::level) still
+/// works on encoded prices: its orders carry them, and value-based statistics such as the
+/// average execution price are not meaningful for these levels.
+#[derive(Debug)]
+pub struct SignedPriceLevel {
+    level: PriceLevel,
+}
+
+impl SignedPriceLevel {
+    /// Create a new level at a signed price
+    pub fn new(price: i64) -> Self {
+        Self {
+            level: PriceLevel::new(encode_signed_price(price)),
+        }
+    }
+
+    /// Get the signed price of this level
+    pub fn price(&self) -> i64 {
+        decode_signed_price(self.level.price())
+    }
+
+    /// Get the underlying level, whose price is encoded
+    pub fn level(&self) -> &PriceLevel {
+        &self.level
+    }
+
+    /// Get the display quantity
+    pub fn display_quantity(&self) -> Quantity {
+        self.level.display_quantity()
+    }
+
+    /// Get the reserve quantity
+    pub fn reserve_quantity(&self) -> Quantity {
+        self.level.reserve_quantity()
+    }
+
+    /// Get the number of orders
+    pub fn order_count(&self) -> usize {
+        self.level.order_count()
+    }
+
+    /// Check if the level is empty
+    pub fn is_empty(&self) -> bool {
+        self.level.is_empty()
+    }
+
+    /// Add an order to this level.
+    ///
+    /// The order must carry the [`encode_signed_price`] of the level price; any other price
+    /// is rejected with [`PriceLevelError::InvalidFieldValue`].
+    pub fn add_order(&mut self, order: Order<()>) -> Result<&Order<()>, PriceLevelError> {
+        if order.price() != self.level.price() {
+            return Err(PriceLevelError::InvalidFieldValue {
+                field: "price".to_string(),
+                value: format!(
+                    "order {} has price {}, expected the encoded level price {}",
+                    order.id(),
+                    order.price(),
+                    self.level.price()
+                ),
+            });
+        }
+        self.level.add_order(order)
+    }
+
+    /// Match an incoming order against this level.
+    ///
+    /// Every execution happens at [`price`](Self::price), which the transactions in the
+    /// result carry as a signed price.
+    pub fn match_order(
+        &mut self,
+        incoming_quantity: Quantity,
+        taker_order_id: OrderId,
+        transaction_id_generator: &UuidGenerator,
+    ) -> SignedMatchResult {
+        SignedMatchResult::from_encoded(self.level.match_order(
+            incoming_quantity,
+            taker_order_id,
+            transaction_id_generator,
+        ))
+    }
+
+    /// Cancel a resting order
+    pub fn cancel_order(&mut self, order_id: OrderId, reason: CancelReason) -> Option<Order<()>> {
+        self.level.cancel_order(order_id, reason)
+    }
+}
+
+impl PartialEq for SignedPriceLevel {
+    fn eq(&self, other: &Self) -> bool {
+        self.level == other.level
+    }
+}
+
+impl Eq for SignedPriceLevel {}
+
+impl PartialOrd for SignedPriceLevel {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders levels by ascending signed price, so more negative prices come first.
+impl Ord for SignedPriceLevel {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.price().cmp(&other.price())
+    }
+}
+
+/// A [`Transaction`] executed on a [`SignedPriceLevel`], with its price decoded
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct SignedTransaction {
+    /// Unique transaction ID
+    pub transaction_id: Uuid,
+
+    /// ID of the aggressive order that caused the match
+    pub taker_order_id: OrderId,
+
+    /// ID of the passive order that was in the book
+    pub maker_order_id: OrderId,
+
+    /// Signed price at which the transaction occurred
+    pub price: i64,
+
+    /// Quantity that was traded
+    pub quantity: Quantity,
+
+    /// Side of the taker order
+    pub taker_side: Side,
+
+    /// Timestamp when the transaction occurred
+    pub timestamp: Timestamp,
+
+    /// Fee charged to the maker, in price units; negative values are rebates
+    pub maker_fee: i128,
+
+    /// Fee charged to the taker, in price units; negative values are rebates
+    pub taker_fee: i128,
+}
+
+impl SignedTransaction {
+    /// Decode a transaction produced by the underlying level
+    fn from_encoded(transaction: &Transaction) -> Self {
+        Self {
+            transaction_id: transaction.transaction_id,
+            taker_order_id: transaction.taker_order_id,
+            maker_order_id: transaction.maker_order_id,
+            price: decode_signed_price(transaction.price),
+            quantity: transaction.quantity,
+            taker_side: transaction.taker_side,
+            timestamp: transaction.timestamp,
+            maker_fee: transaction.maker_fee,
+            taker_fee: transaction.taker_fee,
+        }
+    }
+
+    /// Returns the signed value of this transaction, negative at negative prices
+    pub fn value(&self) -> i128 {
+        self.price as i128 * self.quantity as i128
+    }
+}
+
+/// The result of matching against a [`SignedPriceLevel`].
+///
+/// It carries the fields of a [`MatchResult`] that a plain match can set, with the
+/// transactions decoded into [`SignedTransaction`]s.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedMatchResult {
+    /// The ID of the incoming order that initiated the match
+    pub order_id: OrderId,
+
+    /// Transactions that resulted from the match, in execution order
+    pub transactions: Vec<SignedTransaction>,
+
+    /// Remaining quantity of the incoming order after matching
+    pub remaining_quantity: Quantity,
+
+    /// Whether the order was completely filled
+    pub is_complete: bool,
+
+    /// Any orders that were completely filled and removed from the book
+    pub filled_order_ids: Vec<OrderId>,
+
+    /// One-cancels-other groups of makers that traded in this match
+    pub triggered_oco_groups: Vec<u64>,
+
+    /// Quantities moved from reserve to display during the match, in execution order
+    pub replenishments: Vec<(OrderId, Quantity)>,
+
+    /// Makers in the order they were touched during the sweep
+    pub depletion_order: Vec<OrderId>,
+}
+
+impl SignedMatchResult {
+    /// Decode a match result produced by the underlying level
+    fn from_encoded(result: MatchResult) -> Self {
+        Self {
+            order_id: result.order_id,
+            transactions: result
+                .transactions
+                .as_vec()
+                .iter()
+                .map(SignedTransaction::from_encoded)
+                .collect(),
+            remaining_quantity: result.remaining_quantity,
+            is_complete: result.is_complete,
+            filled_order_ids: result.filled_order_ids,
+            triggered_oco_groups: result.triggered_oco_groups,
+            replenishments: result.replenishments,
+            depletion_order: result.depletion_order,
+        }
+    }
+
+    /// Get the total executed quantity
+    pub fn executed_quantity(&self) -> Quantity {
+        self.transactions.iter().map(|t| t.quantity).sum()
+    }
+
+    /// Get the total signed value executed
+    pub fn executed_value(&self) -> i128 {
+        self.transactions
+            .iter()
+            .map(SignedTransaction::value)
+            .fold(0, i128::saturating_add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::UuidGenerator;
+    use crate::errors::PriceLevelError;
+    use crate::order::{Order, OrderCommon, OrderId, Side, TimeInForce};
+    use crate::price_level::{SignedPriceLevel, decode_signed_price, encode_signed_price};
+    use uuid::Uuid;
+
+    fn create_order(id: u64, price: i64, quantity: u64) -> Order<()> {
+        Order::Standard {
+            common: OrderCommon {
+                id: OrderId::from_u64(id),
+                price: encode_signed_price(price),
+                display_quantity: quantity,
+                side: Side::Sell,
+                timestamp: 1616823000000 + id,
+                time_in_force: TimeInForce::Gtc,
+                extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
+            },
+        }
+    }
+
+    #[test]
+    fn test_signed_price_encoding() {
+        for price in [i64::MIN, -250, -1, 0, 1, 37, i64::MAX] {
+            assert_eq!(decode_signed_price(encode_signed_price(price)), price);
+        }
+        assert!(encode_signed_price(-250) < encode_signed_price(-1));
+        assert!(encode_signed_price(-1) < encode_signed_price(0));
+        assert!(encode_signed_price(0) < encode_signed_price(1));
+    }
+
+    #[test]
+    fn test_match_at_negative_price() {
+        let generator = UuidGenerator::new(Uuid::nil());
+        let mut level = SignedPriceLevel::new(-250);
+        level.add_order(create_order(1, -250, 10)).unwrap();
+        level.add_order(create_order(2, -250, 20)).unwrap();
+        assert_eq!(level.price(), -250);
+        assert_eq!(level.display_quantity(), 30);
+        assert_eq!(
+            decode_signed_price(level.level().iter_orders()[0].price()),
+            -250
+        );
+
+        let result = level.match_order(15, OrderId::from_u64(100), &generator);
+        assert!(result.is_complete);
+        assert_eq!(result.filled_order_ids, vec![OrderId::from_u64(1)]);
+        assert_eq!(result.transactions.len(), 2);
+        for transaction in &result.transactions {
+            assert_eq!(transaction.price, -250);
+        }
+        assert_eq!(result.transactions[0].value(), -2500);
+        assert_eq!(result.executed_quantity(), 15);
+        assert_eq!(result.executed_value(), -3750);
+        assert_eq!(level.display_quantity(), 15);
+    }
+
+    #[test]
+    fn test_add_order_rejects_other_prices() {
+        let mut level = SignedPriceLevel::new(-250);
+        for order in [create_order(1, 250, 10), create_order(2, 0, 10)] {
+            assert!(matches!(
+                level.add_order(order),
+                Err(PriceLevelError::InvalidFieldValue { .. })
+            ));
+        }
+
+        // An unencoded price is rejected rather than reinterpreted
+        let mut unencoded = create_order(3, -250, 10);
+        if let Order::Standard { common } = &mut unencoded {
+            common.price = 250;
+        }
+        assert!(level.add_order(unencoded).is_err());
+        assert!(level.is_empty());
+    }
+
+    #[test]
+    fn test_signed_level_ordering() {
+        let mut levels = [
+            SignedPriceLevel::new(5),
+            SignedPriceLevel::new(-10),
+            SignedPriceLevel::new(0),
+            SignedPriceLevel::new(-3),
+        ];
+        levels.sort();
+        let prices: Vec<i64> = levels.iter().map(|level| level.price()).collect();
+        assert_eq!(prices, vec![-10, -3, 0, 5]);
+        assert_eq!(SignedPriceLevel::new(-3), SignedPriceLevel::new(-3));
+    }
+}
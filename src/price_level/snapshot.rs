@@ -1,5 +1,6 @@
 use crate::errors::PriceLevelError;
-use crate::order::Order;
+use crate::order::{Order, Price, Quantity};
+use crate::price_level::PriceLevelStatistics;
 use serde::de::{self, MapAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -10,14 +11,17 @@ use std::str::FromStr;
 /// A snapshot of a price level in the order book. This struct provides a summary of the state of a specific price level
 /// at a given point in time, including the price, visible and hidden quantities, order count, and a vector of the orders
 /// at that level.
-#[derive(Debug, Default, Clone)]
+///
+/// Unlike [`PriceLevel`](crate::PriceLevel), equality compares the full contents: price,
+/// aggregates, and the orders in queue order.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct PriceLevelSnapshot {
     /// The price of this level.
-    pub price: u64,
+    pub price: Price,
     /// Total display quantity at this level. This represents the sum of the display quantities of all orders at this price level.
-    pub display_quantity: u64,
+    pub display_quantity: Quantity,
     /// Total reserve quantity at this level. This represents the sum of the reserve quantities of all orders at this price level.
-    pub reserve_quantity: u64,
+    pub reserve_quantity: Quantity,
     /// Number of orders at this level.
     pub order_count: usize,
     /// Orders at this level.  This is a vector of `Arc<OrderType<()>>` representing each individual order at this price level.
@@ -26,7 +30,7 @@ pub struct PriceLevelSnapshot {
 
 impl PriceLevelSnapshot {
     /// Create a new empty snapshot
-    pub fn new(price: u64) -> Self {
+    pub fn new(price: Price) -> Self {
         Self {
             price,
             display_quantity: 0,
@@ -37,7 +41,7 @@ impl PriceLevelSnapshot {
     }
 
     /// Get the total quantity (display + reserve) at this price level
-    pub fn total_quantity(&self) -> u64 {
+    pub fn total_quantity(&self) -> Quantity {
         self.display_quantity + self.reserve_quantity
     }
 
@@ -62,21 +66,92 @@ impl PriceLevelSnapshot {
         self.reserve_quantity = reserve_total;
     }
 
+    /// Verifies that the stored aggregate fields match the values recomputed from the orders.
+    ///
+    /// Unlike [`refresh_aggregates`](Self::refresh_aggregates), this never modifies the snapshot
+    /// and reports the first mismatching field as [`PriceLevelError::InconsistentSnapshot`].
+    pub fn verify_aggregates(&self) -> Result<(), PriceLevelError> {
+        let mut display_total: u64 = 0;
+        let mut reserve_total: u64 = 0;
+
+        for order in &self.orders {
+            display_total = display_total.saturating_add(order.display_quantity());
+            reserve_total = reserve_total.saturating_add(order.reserve_quantity());
+        }
+
+        let checks = [
+            ("display_quantity", self.display_quantity, display_total),
+            ("reserve_quantity", self.reserve_quantity, reserve_total),
+            (
+                "order_count",
+                self.order_count as u64,
+                self.orders.len() as u64,
+            ),
+        ];
+
+        for (field, stored, computed) in checks {
+            if stored != computed {
+                return Err(PriceLevelError::InconsistentSnapshot {
+                    field: field.to_string(),
+                    stored,
+                    computed,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the canonical text form of the snapshot, the cross-language contract for hashing
+    /// and the input of the [`PriceLevelSnapshotPackage`] checksum.
+    ///
+    /// The first line is `PriceLevel:price=..;display_quantity=..;reserve_quantity=..;order_count=..`
+    /// with the stored aggregates, in that order. It is followed by one line per order, in
+    /// queue order, holding [`Order::canonical_string`]. Lines are separated by `\n`, with no
+    /// trailing newline. Any change to this form requires a new snapshot format version.
+    pub fn canonical_string(&self) -> String {
+        self.canonical_string_with(true)
+    }
+
+    /// Get the canonical text form, leaving out the iceberg refresh state when
+    /// `iceberg_state` is false as snapshot format version 2 did
+    fn canonical_string_with(&self, iceberg_state: bool) -> String {
+        let mut canonical = format!(
+            "PriceLevel:price={};display_quantity={};reserve_quantity={};order_count={}",
+            self.price, self.display_quantity, self.reserve_quantity, self.order_count
+        );
+        for order in &self.orders {
+            canonical.push('\n');
+            canonical.push_str(&order.canonical_string_with(iceberg_state));
+        }
+        canonical
+    }
+
     /// Get the visible quantity (deprecated: use display_quantity field instead)
     #[deprecated(since = "0.5.0", note = "Use display_quantity field instead")]
-    pub fn visible_quantity(&self) -> u64 {
+    pub fn visible_quantity(&self) -> Quantity {
         self.display_quantity
     }
 
     /// Get the hidden quantity (deprecated: use reserve_quantity field instead)
     #[deprecated(since = "0.5.0", note = "Use reserve_quantity field instead")]
-    pub fn hidden_quantity(&self) -> u64 {
+    pub fn hidden_quantity(&self) -> Quantity {
         self.reserve_quantity
     }
 }
 
 /// Format version for checksum-enabled price level snapshots.
-pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+///
+/// Version 3 computes the checksum over [`PriceLevelSnapshot::canonical_string`]. Version 2
+/// packages, checksummed over the canonical form without the iceberg refresh state, and
+/// version 1 packages, checksummed over the snapshot JSON, are still accepted.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 3;
+
+/// The last format version whose checksum is computed over the snapshot JSON
+const JSON_CHECKSUM_VERSION: u32 = 1;
+
+/// The last format version whose canonical form leaves out the iceberg refresh state
+const ICEBERG_STATELESS_VERSION: u32 = 2;
 
 /// Serialized representation of a price level snapshot including checksum validation metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,7 +169,7 @@ impl PriceLevelSnapshotPackage {
     pub fn new(mut snapshot: PriceLevelSnapshot) -> Result<Self, PriceLevelError> {
         snapshot.refresh_aggregates();
 
-        let checksum = Self::compute_checksum(&snapshot)?;
+        let checksum = Self::compute_checksum(&snapshot, SNAPSHOT_FORMAT_VERSION)?;
 
         Ok(Self {
             version: SNAPSHOT_FORMAT_VERSION,
@@ -119,7 +194,7 @@ impl PriceLevelSnapshotPackage {
 
     /// Validates the checksum contained in the package against the serialized snapshot data.
     pub fn validate(&self) -> Result<(), PriceLevelError> {
-        if self.version != SNAPSHOT_FORMAT_VERSION {
+        if !(JSON_CHECKSUM_VERSION..=SNAPSHOT_FORMAT_VERSION).contains(&self.version) {
             return Err(PriceLevelError::InvalidOperation {
                 message: format!(
                     "Unsupported snapshot version: {} (expected {})",
@@ -128,7 +203,7 @@ impl PriceLevelSnapshotPackage {
             });
         }
 
-        let computed = Self::compute_checksum(&self.snapshot)?;
+        let computed = Self::compute_checksum(&self.snapshot, self.version)?;
         if computed != self.checksum {
             return Err(PriceLevelError::ChecksumMismatch {
                 expected: self.checksum.clone(),
@@ -145,11 +220,19 @@ impl PriceLevelSnapshotPackage {
         Ok(self.snapshot)
     }
 
-    fn compute_checksum(snapshot: &PriceLevelSnapshot) -> Result<String, PriceLevelError> {
-        let payload =
+    fn compute_checksum(
+        snapshot: &PriceLevelSnapshot,
+        version: u32,
+    ) -> Result<String, PriceLevelError> {
+        let payload = if version == JSON_CHECKSUM_VERSION {
             serde_json::to_vec(snapshot).map_err(|error| PriceLevelError::SerializationError {
                 message: error.to_string(),
-            })?;
+            })?
+        } else {
+            snapshot
+                .canonical_string_with(version > ICEBERG_STATELESS_VERSION)
+                .into_bytes()
+        };
 
         let mut hasher = Sha256::new();
         hasher.update(payload);
@@ -159,6 +242,71 @@ impl PriceLevelSnapshotPackage {
     }
 }
 
+/// Verify a batch of snapshot packages, such as the levels of a whole book, before restoring
+/// any of them.
+///
+/// Every package must have the same `version` as the first and pass
+/// [`validate`](PriceLevelSnapshotPackage::validate). An empty batch is valid.
+///
+/// # Errors
+///
+/// Returns [`PriceLevelError::SnapshotBatchError`] with the index and reason of the first
+/// package that fails; the rest are not checked.
+pub fn verify_snapshot_batch(
+    packages: &[PriceLevelSnapshotPackage],
+) -> Result<(), PriceLevelError> {
+    let Some(first) = packages.first() else {
+        return Ok(());
+    };
+
+    for (index, package) in packages.iter().enumerate() {
+        if package.version != first.version {
+            return Err(PriceLevelError::SnapshotBatchError {
+                index,
+                message: format!(
+                    "version {} differs from version {} of the first package",
+                    package.version, first.version
+                ),
+            });
+        }
+        package
+            .validate()
+            .map_err(|error| PriceLevelError::SnapshotBatchError {
+                index,
+                message: error.to_string(),
+            })?;
+    }
+    Ok(())
+}
+
+/// A checksum-protected snapshot package bundled with the level's statistics.
+///
+/// [`PriceLevelSnapshotPackage`] deliberately carries only the book state; this wrapper is for
+/// callers who also want monitoring counters to survive a restart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceLevelSnapshotWithStats {
+    /// Checksum-protected level state.
+    pub package: PriceLevelSnapshotPackage,
+    /// Statistics captured alongside the snapshot.
+    pub stats: PriceLevelStatistics,
+}
+
+impl PriceLevelSnapshotWithStats {
+    /// Serializes the package and statistics to JSON.
+    pub fn to_json(&self) -> Result<String, PriceLevelError> {
+        serde_json::to_string(self).map_err(|error| PriceLevelError::SerializationError {
+            message: error.to_string(),
+        })
+    }
+
+    /// Deserializes a package with statistics from JSON.
+    pub fn from_json(data: &str) -> Result<Self, PriceLevelError> {
+        serde_json::from_str(data).map_err(|error| PriceLevelError::DeserializationError {
+            message: error.to_string(),
+        })
+    }
+}
+
 impl Serialize for PriceLevelSnapshot {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -419,9 +567,11 @@ impl FromStr for PriceLevelSnapshot {
 #[cfg(test)]
 mod tests {
     use crate::errors::PriceLevelError;
-    use crate::order::{Order, OrderCommon, OrderId, Side, TimeInForce};
+    use crate::order::{IcebergRefresh, Order, OrderCommon, OrderId, Side, TimeInForce};
     use crate::price_level::snapshot::SNAPSHOT_FORMAT_VERSION;
-    use crate::price_level::{PriceLevelSnapshot, PriceLevelSnapshotPackage};
+    use crate::price_level::{
+        PriceLevelSnapshot, PriceLevelSnapshotPackage, verify_snapshot_batch,
+    };
     use serde_json::Value;
     use std::str::FromStr;
 
@@ -436,6 +586,9 @@ mod tests {
                     timestamp: 1616823000000,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
             },
             Order::IcebergOrder {
@@ -447,8 +600,14 @@ mod tests {
                     timestamp: 1616823000001,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
                 reserve_quantity: 15,
+                original_display: 5,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
             },
         ]
     }
@@ -490,6 +649,80 @@ mod tests {
         assert_eq!(restored_snapshot.orders.len(), snapshot.orders.len());
     }
 
+    #[test]
+    fn test_canonical_string_golden() {
+        let mut orders = create_sample_orders();
+        orders.push(Order::ReserveOrder {
+            common: OrderCommon {
+                id: OrderId::from_u64(3),
+                price: 1000,
+                display_quantity: 4,
+                side: Side::Sell,
+                timestamp: 1616823000002,
+                time_in_force: TimeInForce::Gtd(1617000000000),
+                extra_fields: (),
+                parent_id: Some(OrderId::from_u64(9)),
+                oco_group: Some(7),
+                suppress_in_feed: true,
+            },
+            reserve_quantity: 16,
+            replenish_threshold: 2,
+            replenish_amount: None,
+            auto_replenish: true,
+            auto_replenish_interval_ms: Some(500),
+            last_reveal_ms: None,
+            max_total_display: Some(12),
+            revealed_quantity: 0,
+        });
+        let mut snapshot = PriceLevelSnapshot::new(1000);
+        snapshot.orders = orders;
+        snapshot.refresh_aggregates();
+
+        // Pinned bytes: changing them breaks checksum agreement with other implementations
+        // and requires a new SNAPSHOT_FORMAT_VERSION
+        let expected = concat!(
+            "PriceLevel:price=1000;display_quantity=19;reserve_quantity=31;order_count=3\n",
+            "Standard:id=00000000-0000-0001-0000-000000000000;price=1000;display_quantity=10;reserve_quantity=0;side=BUY;timestamp=1616823000000;time_in_force=GTC;parent_id=null;oco_group=null;suppress_in_feed=false\n",
+            "IcebergOrder:id=00000000-0000-0002-0000-000000000000;price=1000;display_quantity=5;reserve_quantity=15;side=BUY;timestamp=1616823000001;time_in_force=GTC;parent_id=null;oco_group=null;suppress_in_feed=false;original_display=5;refresh_strategy=OriginalDisplay;last_reveal_ms=null\n",
+            "ReserveOrder:id=00000000-0000-0003-0000-000000000000;price=1000;display_quantity=4;reserve_quantity=16;side=SELL;timestamp=1616823000002;time_in_force=GTD-1617000000000;parent_id=00000000-0000-0009-0000-000000000000;oco_group=7;suppress_in_feed=true;replenish_threshold=2;replenish_amount=null;auto_replenish=true;auto_replenish_interval_ms=500;last_reveal_ms=null;max_total_display=12;revealed_quantity=0",
+        );
+        assert_eq!(snapshot.canonical_string(), expected);
+
+        let package = PriceLevelSnapshotPackage::new(snapshot).unwrap();
+        assert_eq!(
+            package.checksum,
+            "eb928bfb464d5014ad761817cb4c34f8c39de8758550940b04f4bca10e8c84e8"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_package_accepts_json_checksum_version() {
+        let mut snapshot = PriceLevelSnapshot::new(42);
+        snapshot.orders = create_sample_orders();
+        snapshot.refresh_aggregates();
+        let mut package = PriceLevelSnapshotPackage::new(snapshot.clone()).unwrap();
+
+        // A package written with the JSON checksum before the canonical form existed
+        package.version = 1;
+        assert!(package.validate().is_err());
+        package.checksum = PriceLevelSnapshotPackage::compute_checksum(&snapshot, 1).unwrap();
+        package.validate().unwrap();
+
+        // A package written before the canonical form carried the iceberg refresh state
+        package.version = 2;
+        assert!(package.validate().is_err());
+        package.checksum = PriceLevelSnapshotPackage::compute_checksum(&snapshot, 2).unwrap();
+        package.validate().unwrap();
+        assert!(
+            !snapshot
+                .canonical_string_with(false)
+                .contains("original_display")
+        );
+
+        package.version = 4;
+        assert!(package.validate().is_err());
+    }
+
     #[test]
     fn test_snapshot_package_checksum_mismatch() {
         let mut snapshot = PriceLevelSnapshot::new(99);
@@ -518,6 +751,42 @@ mod tests {
         assert!(matches!(err, PriceLevelError::ChecksumMismatch { .. }));
     }
 
+    #[test]
+    fn test_verify_snapshot_batch() {
+        let packages: Vec<PriceLevelSnapshotPackage> = (0..4)
+            .map(|i| {
+                let mut snapshot = PriceLevelSnapshot::new(1000 + i);
+                snapshot.orders = create_sample_orders();
+                PriceLevelSnapshotPackage::new(snapshot).unwrap()
+            })
+            .collect();
+        verify_snapshot_batch(&packages).unwrap();
+        verify_snapshot_batch(&[]).unwrap();
+
+        // One corrupt package fails the batch at its index
+        let mut corrupt = packages.clone();
+        corrupt[2].snapshot.orders.pop();
+        match verify_snapshot_batch(&corrupt) {
+            Err(PriceLevelError::SnapshotBatchError { index, message }) => {
+                assert_eq!(index, 2);
+                assert!(message.starts_with("Checksum mismatch"), "{message}");
+            }
+            other => panic!("Expected SnapshotBatchError, got {other:?}"),
+        }
+
+        // A valid package of another version is still rejected
+        let mut mixed = packages.clone();
+        mixed[1].version = 1;
+        mixed[1].checksum =
+            PriceLevelSnapshotPackage::compute_checksum(&mixed[1].snapshot, 1).unwrap();
+        mixed[1].validate().unwrap();
+        let error = verify_snapshot_batch(&mixed).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid snapshot package 1: version 1 differs from version 3 of the first package"
+        );
+    }
+
     #[test]
     fn test_new() {
         let snapshot = PriceLevelSnapshot::new(1000);
@@ -734,6 +1003,9 @@ mod tests {
                     timestamp: 1616823000000,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
             }
         }
@@ -753,8 +1025,14 @@ mod tests {
                     timestamp: 1616823000000,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
                 reserve_quantity: hidden_quantity,
+                original_display: visible_quantity,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
             }
         }
         // Create a snapshot with orders
@@ -829,7 +1107,7 @@ mod tests {
 
 #[cfg(test)]
 mod pricelevel_snapshot_serialization_tests {
-    use crate::order::{Order, OrderCommon, OrderId, Side, TimeInForce};
+    use crate::order::{IcebergRefresh, Order, OrderCommon, OrderId, Side, TimeInForce};
     use crate::price_level::PriceLevelSnapshot;
     use std::str::FromStr;
 
@@ -845,6 +1123,9 @@ mod pricelevel_snapshot_serialization_tests {
                     timestamp: 1616823000000,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
             },
             Order::IcebergOrder {
@@ -856,8 +1137,14 @@ mod pricelevel_snapshot_serialization_tests {
                     timestamp: 1616823000001,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
                 reserve_quantity: 15,
+                original_display: 5,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
             },
             Order::PostOnly {
                 common: OrderCommon {
@@ -868,6 +1155,9 @@ mod pricelevel_snapshot_serialization_tests {
                     timestamp: 1616823000002,
                     time_in_force: TimeInForce::Ioc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
             },
         ]
@@ -1180,6 +1470,9 @@ mod pricelevel_snapshot_serialization_tests {
                     timestamp: 1616823000000,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
             },
             // Iceberg order
@@ -1192,8 +1485,14 @@ mod pricelevel_snapshot_serialization_tests {
                     timestamp: 1616823000001,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
                 reserve_quantity: 15,
+                original_display: 5,
+                refresh_strategy: IcebergRefresh::default(),
+                last_reveal_ms: None,
             },
             // Post-only order
             Order::PostOnly {
@@ -1205,6 +1504,9 @@ mod pricelevel_snapshot_serialization_tests {
                     timestamp: 1616823000002,
                     time_in_force: TimeInForce::Ioc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
             },
             // Fill-or-kill order (as Standard with FOK time-in-force)
@@ -1217,6 +1519,9 @@ mod pricelevel_snapshot_serialization_tests {
                     timestamp: 1616823000003,
                     time_in_force: TimeInForce::Fok,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
             },
             // Good-till-date order (as Standard with GTD time-in-force)
@@ -1229,6 +1534,9 @@ mod pricelevel_snapshot_serialization_tests {
                     timestamp: 1616823000004,
                     time_in_force: TimeInForce::Gtd(1617000000000),
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
             },
             // Reserve order
@@ -1241,11 +1549,18 @@ mod pricelevel_snapshot_serialization_tests {
                     timestamp: 1616823000005,
                     time_in_force: TimeInForce::Gtc,
                     extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
                 },
                 reserve_quantity: 12,
                 replenish_threshold: 1,
                 replenish_amount: None,
                 auto_replenish: true,
+                auto_replenish_interval_ms: None,
+                last_reveal_ms: None,
+                max_total_display: None,
+                revealed_quantity: 0,
             },
         ];
 
@@ -1,13 +1,18 @@
 use crate::errors::PriceLevelError;
+use crate::order::Side;
 use serde::de::{self, MapAccess, Visitor};
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::VecDeque;
 use std::fmt;
 use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Number of recent waiting times kept for [`PriceLevelStatistics::trimmed_average_waiting_time`]
+pub const WAITING_TIME_SAMPLES: usize = 1024;
+
 /// Tracks performance statistics for a price level
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PriceLevelStatistics {
     /// Number of orders added
     pub orders_added: usize,
@@ -21,8 +26,9 @@ pub struct PriceLevelStatistics {
     /// Total quantity executed
     pub quantity_executed: u64,
 
-    /// Total value executed
-    pub value_executed: u64,
+    /// Total value executed, `price * quantity` summed over executions. Kept in `u128` so
+    /// high-price, high-volume levels do not overflow; the sum saturates at `u128::MAX`
+    pub value_executed: u128,
 
     /// Last execution timestamp
     pub last_execution_time: u64,
@@ -32,6 +38,48 @@ pub struct PriceLevelStatistics {
 
     /// Sum of waiting times for orders
     pub sum_waiting_time: u64,
+
+    /// Total quantity executed against buy takers
+    pub buy_volume: u64,
+
+    /// Total quantity executed against sell takers
+    pub sell_volume: u64,
+
+    /// Executed quantity that iceberg and reserve orders had revealed from their hidden reserve
+    pub hidden_volume: u64,
+
+    /// Waiting times of the most recent executions, oldest first. Not serialized
+    recent_waiting_times: VecDeque<u64>,
+
+    /// Time-weighted accumulator of the level's display quantity
+    #[cfg(feature = "time-weighted")]
+    time_weighted: TimeWeightedQuantity,
+}
+
+/// Integral of the display quantity over time, advanced whenever the quantity changes.
+#[cfg(feature = "time-weighted")]
+#[derive(Debug, Clone, Default)]
+struct TimeWeightedQuantity {
+    start_ms: u64,
+    last_change_ms: u64,
+    last_quantity: u64,
+    weighted_sum: u128,
+}
+
+#[cfg(feature = "time-weighted")]
+impl TimeWeightedQuantity {
+    fn starting_at(now_ms: u64) -> Self {
+        Self {
+            start_ms: now_ms,
+            last_change_ms: now_ms,
+            ..Self::default()
+        }
+    }
+
+    fn weighted_until(&self, now_ms: u64) -> u128 {
+        let elapsed = now_ms.saturating_sub(self.last_change_ms);
+        self.weighted_sum + self.last_quantity as u128 * elapsed as u128
+    }
 }
 
 impl PriceLevelStatistics {
@@ -51,31 +99,64 @@ impl PriceLevelStatistics {
             last_execution_time: 0,
             first_arrival_time: current_time,
             sum_waiting_time: 0,
+            buy_volume: 0,
+            sell_volume: 0,
+            hidden_volume: 0,
+            recent_waiting_times: VecDeque::new(),
+            #[cfg(feature = "time-weighted")]
+            time_weighted: TimeWeightedQuantity::starting_at(current_time),
         }
     }
 
     /// Record an order being added
     pub fn record_order_added(&mut self) {
-        self.orders_added += 1;
+        self.orders_added = self.orders_added.saturating_add(1);
     }
 
     /// Record an order being removed
     pub fn record_order_removed(&mut self) {
-        self.orders_removed += 1;
+        self.orders_removed = self.orders_removed.saturating_add(1);
     }
 
-    /// Record an execution
-    pub fn record_execution(&mut self, quantity: u64, price: u64, waiting_time: u64) {
-        self.orders_executed += 1;
-        self.quantity_executed += quantity;
-        self.value_executed += quantity * price;
-        self.sum_waiting_time += waiting_time;
+    /// Record an execution against a taker on `taker_side`
+    pub fn record_execution(
+        &mut self,
+        quantity: u64,
+        price: u64,
+        waiting_time: u64,
+        taker_side: Side,
+    ) {
+        // Counters saturate instead of wrapping or panicking on overflow
+        self.orders_executed = self.orders_executed.saturating_add(1);
+        self.quantity_executed = self.quantity_executed.saturating_add(quantity);
+        match taker_side {
+            Side::Buy => self.buy_volume = self.buy_volume.saturating_add(quantity),
+            Side::Sell => self.sell_volume = self.sell_volume.saturating_add(quantity),
+        }
+        // A single product always fits in u128, even for the encoded prices of a
+        // SignedPriceLevel
+        self.value_executed = self
+            .value_executed
+            .saturating_add(quantity as u128 * price as u128);
+        self.sum_waiting_time = self.sum_waiting_time.saturating_add(waiting_time);
+        if self.recent_waiting_times.len() == WAITING_TIME_SAMPLES {
+            self.recent_waiting_times.pop_front();
+        }
+        self.recent_waiting_times.push_back(waiting_time);
         self.last_execution_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis() as u64;
     }
 
+    /// Record executed quantity that came from hidden reserve, as done by
+    /// [`match_order`](crate::PriceLevel::match_order) when a fill consumes a slice a maker
+    /// revealed from its reserve. The quantity must also be recorded with
+    /// [`record_execution`](Self::record_execution).
+    pub fn record_hidden_execution(&mut self, quantity: u64) {
+        self.hidden_volume = self.hidden_volume.saturating_add(quantity);
+    }
+
     /// Get the number of orders added
     pub fn orders_added(&self) -> usize {
         self.orders_added
@@ -97,10 +178,39 @@ impl PriceLevelStatistics {
     }
 
     /// Get the total value executed
-    pub fn value_executed(&self) -> u64 {
+    pub fn value_executed(&self) -> u128 {
         self.value_executed
     }
 
+    /// Get the quantity executed against buy takers, i.e. lifted by buying aggressors
+    pub fn buy_volume(&self) -> u64 {
+        self.buy_volume
+    }
+
+    /// Get the quantity executed against sell takers, i.e. hit by selling aggressors
+    pub fn sell_volume(&self) -> u64 {
+        self.sell_volume
+    }
+
+    /// Get the executed quantity that came from hidden reserve
+    pub fn hidden_volume(&self) -> u64 {
+        self.hidden_volume
+    }
+
+    /// Get the fraction of executed quantity that came from hidden (reserve) liquidity,
+    /// between 0.0 and 1.0. Returns 0.0 when nothing has executed.
+    ///
+    /// The level tracks the slices each maker reveals from its reserve, and a fill counts as
+    /// hidden only for the part that consumes such a slice; quantity displayed when the
+    /// order was entered is visible. Slices revealed before a level was restored from a
+    /// snapshot are counted as visible.
+    pub fn hidden_execution_ratio(&self) -> f64 {
+        if self.quantity_executed == 0 {
+            return 0.0;
+        }
+        self.hidden_volume as f64 / self.quantity_executed as f64
+    }
+
     /// Get the average execution price
     pub fn average_execution_price(&self) -> f64 {
         if self.quantity_executed > 0 {
@@ -119,6 +229,56 @@ impl PriceLevelStatistics {
         }
     }
 
+    /// Get the average waiting time of the most recent executions after discarding the
+    /// shortest and longest `trim_pct` percent of them.
+    ///
+    /// Only the last [`WAITING_TIME_SAMPLES`] waiting times are kept, so unlike
+    /// [`average_waiting_time`](Self::average_waiting_time) this covers recent activity and
+    /// is not skewed by a few very stale orders. At least one sample is always kept; returns
+    /// 0.0 when there are none, including after a restore from a string or JSON.
+    pub fn trimmed_average_waiting_time(&self, trim_pct: u8) -> f64 {
+        if self.recent_waiting_times.is_empty() {
+            return 0.0;
+        }
+        let mut samples: Vec<u64> = self.recent_waiting_times.iter().copied().collect();
+        samples.sort_unstable();
+        let trim = (samples.len() * trim_pct.min(50) as usize / 100).min((samples.len() - 1) / 2);
+        let kept = &samples[trim..samples.len() - trim];
+        kept.iter().map(|&sample| sample as u128).sum::<u128>() as f64 / kept.len() as f64
+    }
+
+    /// Get the number of orders removed per order added, a surveillance signal for quote
+    /// stuffing. Returns 0.0 when nothing has been added, e.g. on a level restored from a
+    /// snapshot, even if orders were removed since.
+    pub fn cancel_to_add_ratio(&self) -> f64 {
+        if self.orders_added == 0 {
+            return 0.0;
+        }
+        self.orders_removed as f64 / self.orders_added as f64
+    }
+
+    /// Get the number of executions per order added. Returns 0.0 when nothing has been
+    /// added, even if orders were executed since.
+    pub fn execution_ratio(&self) -> f64 {
+        if self.orders_added == 0 {
+            return 0.0;
+        }
+        self.orders_executed as f64 / self.orders_added as f64
+    }
+
+    /// Get the executed quantity per millisecond over the level's lifetime, measured from
+    /// `first_arrival_time` to `now_ms`.
+    ///
+    /// Unlike a rolling-window rate this uses lifetime totals, so it reflects the level's
+    /// overall liquidity velocity. Returns 0.0 when no time has elapsed.
+    pub fn turnover_rate(&self, now_ms: u64) -> f64 {
+        let elapsed = now_ms.saturating_sub(self.first_arrival_time);
+        if elapsed == 0 {
+            return 0.0;
+        }
+        self.quantity_executed as f64 / elapsed as f64
+    }
+
     /// Get the time since last execution in milliseconds
     pub fn time_since_last_execution(&self) -> u64 {
         if self.last_execution_time > 0 {
@@ -132,6 +292,84 @@ impl PriceLevelStatistics {
         }
     }
 
+    /// Record the level's display quantity as of `now_ms`.
+    ///
+    /// The previous quantity is weighted by the time it was displayed. Calls that do not
+    /// change the quantity are ignored, so callers may report after every operation.
+    #[cfg(feature = "time-weighted")]
+    pub fn record_display_quantity(&mut self, quantity: u64, now_ms: u64) {
+        let tracker = &mut self.time_weighted;
+        if quantity == tracker.last_quantity {
+            return;
+        }
+        let now_ms = now_ms.max(tracker.last_change_ms);
+        tracker.weighted_sum = tracker.weighted_until(now_ms);
+        tracker.last_change_ms = now_ms;
+        tracker.last_quantity = quantity;
+    }
+
+    /// Report the level's current display quantity to the time-weighted tracker. This is a
+    /// no-op unless the `time-weighted` feature is enabled.
+    #[inline]
+    pub(crate) fn observe_display_quantity(&mut self, _quantity: u64) {
+        #[cfg(feature = "time-weighted")]
+        self.record_display_quantity(_quantity, current_time_millis());
+    }
+
+    /// Get the average display quantity over the time since tracking started, weighting
+    /// each quantity by how long it was displayed.
+    ///
+    /// Returns the current quantity when no time has elapsed yet.
+    #[cfg(feature = "time-weighted")]
+    pub fn time_weighted_average_quantity(&self, now_ms: u64) -> f64 {
+        let tracker = &self.time_weighted;
+        let elapsed = now_ms.saturating_sub(tracker.start_ms);
+        if elapsed == 0 {
+            return tracker.last_quantity as f64;
+        }
+        tracker.weighted_until(now_ms) as f64 / elapsed as f64
+    }
+
+    /// Render the statistics in the Prometheus text exposition format, labeled by `price`.
+    ///
+    /// This is a dependency-free alternative to the `metrics` feature for callers that only
+    /// need a scrape endpoint. Every metric gets its own `# TYPE` line.
+    pub fn to_prometheus(&self, price: u64) -> String {
+        let metrics: [(&str, &str, String); 7] = [
+            ("orders_added", "counter", self.orders_added.to_string()),
+            ("orders_removed", "counter", self.orders_removed.to_string()),
+            (
+                "orders_executed",
+                "counter",
+                self.orders_executed.to_string(),
+            ),
+            (
+                "quantity_executed",
+                "counter",
+                self.quantity_executed.to_string(),
+            ),
+            ("value_executed", "counter", self.value_executed.to_string()),
+            (
+                "average_execution_price",
+                "gauge",
+                self.average_execution_price().to_string(),
+            ),
+            (
+                "average_waiting_time_ms",
+                "gauge",
+                self.average_waiting_time().to_string(),
+            ),
+        ];
+
+        let mut output = String::new();
+        for (name, kind, value) in metrics {
+            output.push_str(&format!(
+                "# TYPE pricelevel_{name} {kind}\npricelevel_{name}{{price=\"{price}\"}} {value}\n"
+            ));
+        }
+        output
+    }
+
     /// Reset all statistics
     pub fn reset(&mut self) {
         self.orders_added = 0;
@@ -145,9 +383,27 @@ impl PriceLevelStatistics {
             .unwrap_or_default()
             .as_millis() as u64;
         self.sum_waiting_time = 0;
+        self.buy_volume = 0;
+        self.sell_volume = 0;
+        self.hidden_volume = 0;
+        self.recent_waiting_times.clear();
+        #[cfg(feature = "time-weighted")]
+        {
+            let quantity = self.time_weighted.last_quantity;
+            self.time_weighted = TimeWeightedQuantity::starting_at(self.first_arrival_time);
+            self.time_weighted.last_quantity = quantity;
+        }
     }
 }
 
+#[cfg(feature = "time-weighted")]
+fn current_time_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
 impl Default for PriceLevelStatistics {
     fn default() -> Self {
         Self::new()
@@ -158,7 +414,7 @@ impl fmt::Display for PriceLevelStatistics {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "orders_added:{},orders_removed:{},orders_executed:{},quantity_executed:{},value_executed:{},last_execution_time:{},first_arrival_time:{},sum_waiting_time:{}",
+            "orders_added:{},orders_removed:{},orders_executed:{},quantity_executed:{},value_executed:{},last_execution_time:{},first_arrival_time:{},sum_waiting_time:{},buy_volume:{},sell_volume:{},hidden_volume:{}",
             self.orders_added,
             self.orders_removed,
             self.orders_executed,
@@ -166,7 +422,10 @@ impl fmt::Display for PriceLevelStatistics {
             self.value_executed,
             self.last_execution_time,
             self.first_arrival_time,
-            self.sum_waiting_time
+            self.sum_waiting_time,
+            self.buy_volume,
+            self.sell_volume,
+            self.hidden_volume
         )
     }
 }
@@ -183,6 +442,11 @@ impl FromStr for PriceLevelStatistics {
         let mut last_execution_time = 0;
         let mut first_arrival_time = 0;
         let mut sum_waiting_time = 0;
+        // The side split and hidden volume are optional so strings written before they
+        // existed still parse
+        let mut buy_volume = 0;
+        let mut sell_volume = 0;
+        let mut hidden_volume = 0;
 
         for pair in s.split(',') {
             let parts: Vec<&str> = pair.split(':').collect();
@@ -252,6 +516,21 @@ impl FromStr for PriceLevelStatistics {
                         ))
                     })?
                 }
+                "buy_volume" => {
+                    buy_volume = value.parse().map_err(|_| {
+                        PriceLevelError::InvalidFormat(format!("Invalid buy_volume: {}", value))
+                    })?
+                }
+                "sell_volume" => {
+                    sell_volume = value.parse().map_err(|_| {
+                        PriceLevelError::InvalidFormat(format!("Invalid sell_volume: {}", value))
+                    })?
+                }
+                "hidden_volume" => {
+                    hidden_volume = value.parse().map_err(|_| {
+                        PriceLevelError::InvalidFormat(format!("Invalid hidden_volume: {}", value))
+                    })?
+                }
                 _ => {
                     return Err(PriceLevelError::InvalidFormat(format!(
                         "Unknown key: {}",
@@ -270,6 +549,12 @@ impl FromStr for PriceLevelStatistics {
             last_execution_time,
             first_arrival_time,
             sum_waiting_time,
+            buy_volume,
+            sell_volume,
+            hidden_volume,
+            recent_waiting_times: VecDeque::new(),
+            #[cfg(feature = "time-weighted")]
+            time_weighted: TimeWeightedQuantity::starting_at(current_time_millis()),
         })
     }
 }
@@ -279,7 +564,7 @@ impl Serialize for PriceLevelStatistics {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("PriceLevelStatistics", 8)?;
+        let mut state = serializer.serialize_struct("PriceLevelStatistics", 11)?;
         state.serialize_field("orders_added", &self.orders_added)?;
         state.serialize_field("orders_removed", &self.orders_removed)?;
         state.serialize_field("orders_executed", &self.orders_executed)?;
@@ -288,6 +573,9 @@ impl Serialize for PriceLevelStatistics {
         state.serialize_field("last_execution_time", &self.last_execution_time)?;
         state.serialize_field("first_arrival_time", &self.first_arrival_time)?;
         state.serialize_field("sum_waiting_time", &self.sum_waiting_time)?;
+        state.serialize_field("buy_volume", &self.buy_volume)?;
+        state.serialize_field("sell_volume", &self.sell_volume)?;
+        state.serialize_field("hidden_volume", &self.hidden_volume)?;
         state.end()
     }
 }
@@ -306,6 +594,9 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
             LastExecutionTime,
             FirstArrivalTime,
             SumWaitingTime,
+            BuyVolume,
+            SellVolume,
+            HiddenVolume,
         }
 
         impl<'de> Deserialize<'de> for Field {
@@ -335,6 +626,9 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
                             "last_execution_time" => Ok(Field::LastExecutionTime),
                             "first_arrival_time" => Ok(Field::FirstArrivalTime),
                             "sum_waiting_time" => Ok(Field::SumWaitingTime),
+                            "buy_volume" => Ok(Field::BuyVolume),
+                            "sell_volume" => Ok(Field::SellVolume),
+                            "hidden_volume" => Ok(Field::HiddenVolume),
                             _ => Err(de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -365,6 +659,9 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
                 let mut last_execution_time = None;
                 let mut first_arrival_time = None;
                 let mut sum_waiting_time = None;
+                let mut buy_volume = None;
+                let mut sell_volume = None;
+                let mut hidden_volume = None;
 
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -420,6 +717,24 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
                             }
                             sum_waiting_time = Some(map.next_value()?);
                         }
+                        Field::BuyVolume => {
+                            if buy_volume.is_some() {
+                                return Err(serde::de::Error::duplicate_field("buy_volume"));
+                            }
+                            buy_volume = Some(map.next_value()?);
+                        }
+                        Field::SellVolume => {
+                            if sell_volume.is_some() {
+                                return Err(serde::de::Error::duplicate_field("sell_volume"));
+                            }
+                            sell_volume = Some(map.next_value()?);
+                        }
+                        Field::HiddenVolume => {
+                            if hidden_volume.is_some() {
+                                return Err(serde::de::Error::duplicate_field("hidden_volume"));
+                            }
+                            hidden_volume = Some(map.next_value()?);
+                        }
                     }
                 }
 
@@ -439,6 +754,10 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
                     .ok_or_else(|| serde::de::Error::missing_field("first_arrival_time"))?;
                 let sum_waiting_time = sum_waiting_time
                     .ok_or_else(|| serde::de::Error::missing_field("sum_waiting_time"))?;
+                // Optional so payloads written before these counters still deserialize
+                let buy_volume = buy_volume.unwrap_or(0);
+                let sell_volume = sell_volume.unwrap_or(0);
+                let hidden_volume = hidden_volume.unwrap_or(0);
 
                 Ok(PriceLevelStatistics {
                     orders_added,
@@ -449,6 +768,12 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
                     last_execution_time,
                     first_arrival_time,
                     sum_waiting_time,
+                    buy_volume,
+                    sell_volume,
+                    hidden_volume,
+                    recent_waiting_times: VecDeque::new(),
+                    #[cfg(feature = "time-weighted")]
+                    time_weighted: TimeWeightedQuantity::starting_at(current_time_millis()),
                 })
             }
         }
@@ -462,6 +787,9 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
             "last_execution_time",
             "first_arrival_time",
             "sum_waiting_time",
+            "buy_volume",
+            "sell_volume",
+            "hidden_volume",
         ];
 
         deserializer.deserialize_struct("PriceLevelStatistics", FIELDS, StatisticsVisitor)
@@ -470,7 +798,8 @@ impl<'de> Deserialize<'de> for PriceLevelStatistics {
 
 #[cfg(test)]
 mod tests {
-    use crate::price_level::PriceLevelStatistics;
+    use crate::order::Side;
+    use crate::price_level::{PriceLevelStatistics, WAITING_TIME_SAMPLES};
     use std::str::FromStr;
     use std::thread;
     use std::time::Duration;
@@ -501,12 +830,12 @@ mod tests {
         stats.record_order_removed();
         assert_eq!(stats.orders_removed(), 1);
 
-        stats.record_execution(100, 50, 1000);
+        stats.record_execution(100, 50, 1000, Side::Buy);
         assert_eq!(stats.orders_executed(), 1);
         assert_eq!(stats.quantity_executed(), 100);
         assert_eq!(stats.value_executed(), 5000);
 
-        stats.record_execution(50, 60, 2000);
+        stats.record_execution(50, 60, 2000, Side::Buy);
         assert_eq!(stats.orders_executed(), 2);
         assert_eq!(stats.quantity_executed(), 150);
         assert_eq!(stats.value_executed(), 8000);
@@ -518,10 +847,10 @@ mod tests {
 
         assert_eq!(stats.average_execution_price(), 0.0);
 
-        stats.record_execution(100, 50, 1000);
+        stats.record_execution(100, 50, 1000, Side::Buy);
         assert_eq!(stats.average_execution_price(), 50.0);
 
-        stats.record_execution(50, 60, 2000);
+        stats.record_execution(50, 60, 2000, Side::Buy);
         assert_eq!(stats.average_execution_price(), 8000.0 / 150.0);
     }
 
@@ -531,10 +860,10 @@ mod tests {
 
         assert_eq!(stats.average_waiting_time(), 0.0);
 
-        stats.record_execution(100, 50, 1000);
+        stats.record_execution(100, 50, 1000, Side::Buy);
         assert_eq!(stats.average_waiting_time(), 1000.0);
 
-        stats.record_execution(50, 60, 2000);
+        stats.record_execution(50, 60, 2000, Side::Buy);
         assert_eq!(stats.average_waiting_time(), 1500.0);
     }
 
@@ -544,7 +873,7 @@ mod tests {
 
         assert_eq!(stats.time_since_last_execution(), 0);
 
-        stats.record_execution(100, 50, 1000);
+        stats.record_execution(100, 50, 1000, Side::Buy);
         thread::sleep(Duration::from_millis(10));
 
         let time_since = stats.time_since_last_execution();
@@ -557,7 +886,7 @@ mod tests {
 
         stats.record_order_added();
         stats.record_order_removed();
-        stats.record_execution(100, 50, 1000);
+        stats.record_execution(100, 50, 1000, Side::Buy);
 
         stats.reset();
 
@@ -572,7 +901,7 @@ mod tests {
     fn test_display() {
         let mut stats = PriceLevelStatistics::new();
         stats.record_order_added();
-        stats.record_execution(100, 50, 1000);
+        stats.record_execution(100, 50, 1000, Side::Buy);
 
         let display_str = format!("{}", stats);
         assert!(display_str.contains("orders_added:1"));
@@ -623,7 +952,7 @@ mod tests {
     fn test_serialize_deserialize_json() {
         let mut original_stats = PriceLevelStatistics::new();
         original_stats.record_order_added();
-        original_stats.record_execution(100, 50, 1000);
+        original_stats.record_execution(100, 50, 1000, Side::Buy);
 
         let json_str = serde_json::to_string(&original_stats).unwrap();
         let deserialized_stats: PriceLevelStatistics = serde_json::from_str(&json_str).unwrap();
@@ -651,8 +980,8 @@ mod tests {
         let mut original_stats = PriceLevelStatistics::new();
         original_stats.record_order_added();
         original_stats.record_order_removed();
-        original_stats.record_execution(150, 25, 2500);
-        original_stats.record_execution(75, 30, 1200);
+        original_stats.record_execution(150, 25, 2500, Side::Buy);
+        original_stats.record_execution(75, 30, 1200, Side::Buy);
 
         let display_str = format!("{}", original_stats);
         let parsed_stats = PriceLevelStatistics::from_str(&display_str).unwrap();
@@ -698,7 +1027,7 @@ mod tests {
 
         for i in 0..10 {
             stats.record_order_added();
-            stats.record_execution(10, i + 1, 100 * (i + 1));
+            stats.record_execution(10, i + 1, 100 * (i + 1), Side::Buy);
         }
 
         assert_eq!(stats.orders_added(), 10);
@@ -713,7 +1042,7 @@ mod tests {
         for i in 0..5 {
             stats.record_order_added();
             stats.record_order_removed();
-            stats.record_execution(20, 100 + i, 500);
+            stats.record_execution(20, 100 + i, 500, Side::Buy);
         }
 
         assert!(stats.orders_added() > 0);
@@ -737,8 +1066,8 @@ mod tests {
         stats.record_order_added();
         stats.record_order_added();
         stats.record_order_removed();
-        stats.record_execution(50, 200, 1500);
-        stats.record_execution(75, 180, 800);
+        stats.record_execution(50, 200, 1500, Side::Buy);
+        stats.record_execution(75, 180, 800, Side::Buy);
 
         let serialized = serde_json::to_string(&stats).unwrap();
         let deserialized: PriceLevelStatistics = serde_json::from_str(&serialized).unwrap();
@@ -759,4 +1088,173 @@ mod tests {
         let result: Result<PriceLevelStatistics, _> = serde_json::from_str(incomplete_json);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_statistics_to_prometheus() {
+        let mut stats = PriceLevelStatistics::new();
+        stats.record_order_added();
+        stats.record_order_added();
+        stats.record_order_removed();
+        stats.record_execution(10, 10000, 40, Side::Buy);
+        stats.record_execution(30, 10000, 20, Side::Buy);
+
+        let output = stats.to_prometheus(10000);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 14);
+
+        for pair in lines.chunks(2) {
+            let name = pair[0]
+                .strip_prefix("# TYPE ")
+                .and_then(|rest| rest.split_whitespace().next())
+                .unwrap();
+            let kind = pair[0].rsplit(' ').next().unwrap();
+            assert!(kind == "counter" || kind == "gauge");
+            assert!(pair[1].starts_with(&format!("{name}{{price=\"10000\"}} ")));
+            let value = pair[1].rsplit(' ').next().unwrap();
+            assert!(value.parse::<f64>().is_ok(), "bad value in {}", pair[1]);
+        }
+
+        assert!(output.contains("pricelevel_orders_added{price=\"10000\"} 2\n"));
+        assert!(output.contains("pricelevel_orders_removed{price=\"10000\"} 1\n"));
+        assert!(output.contains("pricelevel_orders_executed{price=\"10000\"} 2\n"));
+        assert!(output.contains("pricelevel_quantity_executed{price=\"10000\"} 40\n"));
+        assert!(output.contains("pricelevel_value_executed{price=\"10000\"} 400000\n"));
+        assert!(output.contains("pricelevel_average_waiting_time_ms{price=\"10000\"} 30\n"));
+    }
+
+    #[cfg(feature = "time-weighted")]
+    #[test]
+    fn test_time_weighted_average_quantity() {
+        let mut stats = PriceLevelStatistics::new();
+        let start = stats.first_arrival_time;
+
+        // 0 for 1s, 100 for 2s, 40 for 1s
+        stats.record_display_quantity(100, start + 1_000);
+        stats.record_display_quantity(100, start + 2_000);
+        stats.record_display_quantity(40, start + 3_000);
+        assert_eq!(stats.time_weighted_average_quantity(start + 4_000), 60.0);
+
+        // Time after the last change keeps accruing the current quantity
+        assert_eq!(stats.time_weighted_average_quantity(start + 8_000), 50.0);
+    }
+
+    #[test]
+    fn test_cancel_to_add_and_execution_ratios() {
+        let mut stats = PriceLevelStatistics::new();
+        assert_eq!(stats.cancel_to_add_ratio(), 0.0);
+        assert_eq!(stats.execution_ratio(), 0.0);
+
+        // Removals and executions without any adds report no ratio
+        for _ in 0..3 {
+            stats.record_order_removed();
+            stats.record_execution(10, 100, 0, Side::Buy);
+        }
+        assert_eq!(stats.cancel_to_add_ratio(), 0.0);
+        assert_eq!(stats.execution_ratio(), 0.0);
+
+        for _ in 0..4 {
+            stats.record_order_added();
+        }
+        assert_eq!(stats.cancel_to_add_ratio(), 0.75);
+        assert_eq!(stats.execution_ratio(), 0.75);
+    }
+
+    #[test]
+    fn test_side_split_is_optional_when_parsing() {
+        let input = "orders_added:5,orders_removed:1,orders_executed:2,quantity_executed:30,value_executed:3000,last_execution_time:0,first_arrival_time:1616823000000,sum_waiting_time:100";
+        let stats = PriceLevelStatistics::from_str(input).unwrap();
+        assert_eq!(stats.buy_volume(), 0);
+        assert_eq!(stats.sell_volume(), 0);
+
+        let json = r#"{"orders_added":5,"orders_removed":1,"orders_executed":2,"quantity_executed":30,"value_executed":3000,"last_execution_time":0,"first_arrival_time":1616823000000,"sum_waiting_time":100}"#;
+        let stats: PriceLevelStatistics = serde_json::from_str(json).unwrap();
+        assert_eq!(stats.quantity_executed(), 30);
+        assert_eq!(stats.buy_volume() + stats.sell_volume(), 0);
+
+        let mut stats = PriceLevelStatistics::new();
+        stats.record_execution(10, 100, 0, Side::Buy);
+        stats.record_execution(4, 100, 0, Side::Sell);
+        assert_eq!((stats.buy_volume(), stats.sell_volume()), (10, 4));
+        stats.reset();
+        assert_eq!((stats.buy_volume(), stats.sell_volume()), (0, 0));
+    }
+
+    #[test]
+    fn test_hidden_execution_ratio() {
+        let mut stats = PriceLevelStatistics::new();
+        assert_eq!(stats.hidden_execution_ratio(), 0.0);
+
+        stats.record_execution(10, 100, 0, Side::Buy);
+        stats.record_hidden_execution(10);
+        stats.record_execution(30, 100, 0, Side::Buy);
+        assert_eq!(stats.hidden_volume(), 10);
+        assert_eq!(stats.hidden_execution_ratio(), 0.25);
+
+        let parsed = PriceLevelStatistics::from_str(&stats.to_string()).unwrap();
+        assert_eq!(parsed.hidden_volume(), 10);
+        let json = serde_json::to_string(&stats).unwrap();
+        let parsed: PriceLevelStatistics = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.hidden_volume(), 10);
+
+        stats.reset();
+        assert_eq!(stats.hidden_volume(), 0);
+    }
+
+    #[test]
+    fn test_trimmed_average_waiting_time_drops_outliers() {
+        let mut stats = PriceLevelStatistics::new();
+        assert_eq!(stats.trimmed_average_waiting_time(10), 0.0);
+
+        for waiting_time in [100, 110, 90, 105, 95, 100, 98, 102, 100, 1_000_000] {
+            stats.record_execution(1, 100, waiting_time, Side::Buy);
+        }
+        assert!(stats.average_waiting_time() > 100_000.0);
+        assert_eq!(
+            stats.trimmed_average_waiting_time(0),
+            stats.average_waiting_time()
+        );
+        // 10% of 10 samples drops the stale order and the fastest fill
+        assert_eq!(stats.trimmed_average_waiting_time(10), 101.25);
+        // Trimming everything still keeps the median
+        assert_eq!(stats.trimmed_average_waiting_time(100), 100.0);
+
+        // Only the most recent samples are kept
+        for _ in 0..WAITING_TIME_SAMPLES {
+            stats.record_execution(1, 100, 7, Side::Buy);
+        }
+        assert_eq!(stats.trimmed_average_waiting_time(0), 7.0);
+        stats.reset();
+        assert_eq!(stats.trimmed_average_waiting_time(0), 0.0);
+    }
+
+    #[test]
+    fn test_turnover_rate() {
+        let mut stats = PriceLevelStatistics::new();
+        stats.first_arrival_time = 1_000;
+        assert_eq!(stats.turnover_rate(5_000), 0.0);
+
+        stats.record_execution(300, 100, 0, Side::Buy);
+        stats.record_execution(100, 100, 0, Side::Sell);
+        assert_eq!(stats.turnover_rate(3_000), 0.2);
+        assert_eq!(stats.turnover_rate(5_000), 0.1);
+
+        // No elapsed time, or a clock behind the first arrival
+        assert_eq!(stats.turnover_rate(1_000), 0.0);
+        assert_eq!(stats.turnover_rate(500), 0.0);
+    }
+
+    #[test]
+    fn test_execution_counters_saturate() {
+        let mut stats = PriceLevelStatistics::new();
+        stats.value_executed = u128::MAX - 1;
+        stats.quantity_executed = u64::MAX - 1;
+        stats.sum_waiting_time = u64::MAX - 1;
+        stats.orders_executed = usize::MAX;
+        stats.record_execution(u64::MAX, u64::MAX, u64::MAX, Side::Buy);
+        assert_eq!(stats.value_executed(), u128::MAX);
+        assert_eq!(stats.quantity_executed(), u64::MAX);
+        assert_eq!(stats.buy_volume(), u64::MAX);
+        assert_eq!(stats.sum_waiting_time, u64::MAX);
+        assert_eq!(stats.orders_executed(), usize::MAX);
+    }
 }
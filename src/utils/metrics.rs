@@ -0,0 +1,61 @@
+//! Optional `metrics` crate integration.
+//!
+//! When the `metrics` feature is enabled, the hot paths of [`crate::PriceLevel`] emit
+//! counters and histograms through whatever recorder the application installed (for
+//! example `metrics-exporter-prometheus`). Every series is labeled with the level price.
+//! Without the feature these functions are empty and compile away entirely.
+//!
+//! | Name | Kind | Meaning |
+//! |------|------|---------|
+//! | `pricelevel.orders_added` | counter | Orders added to a level |
+//! | `pricelevel.matches` | counter | Transactions produced by `match_order` |
+//! | `pricelevel.match_latency` | histogram | `match_order` latency in seconds |
+//! | `pricelevel.cancellations` | counter | Orders cancelled from a level, also labeled with the `reason` |
+
+/// Start timing a match. Returns `None` when metrics are disabled.
+#[inline(always)]
+pub(crate) fn match_started() -> Option<std::time::Instant> {
+    #[cfg(feature = "metrics")]
+    {
+        Some(std::time::Instant::now())
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        None
+    }
+}
+
+#[inline(always)]
+pub(crate) fn record_order_added(_price: u64) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!("pricelevel.orders_added", "price" => _price.to_string()).increment(1);
+}
+
+#[inline(always)]
+pub(crate) fn record_match(
+    _price: u64,
+    _transactions: usize,
+    _started: Option<std::time::Instant>,
+) {
+    #[cfg(feature = "metrics")]
+    {
+        let price = _price.to_string();
+        metrics::counter!("pricelevel.matches", "price" => price.clone())
+            .increment(_transactions as u64);
+        if let Some(started) = _started {
+            metrics::histogram!("pricelevel.match_latency", "price" => price)
+                .record(started.elapsed());
+        }
+    }
+}
+
+#[inline(always)]
+pub(crate) fn record_cancellation(_price: u64, _reason: crate::order::CancelReason) {
+    #[cfg(feature = "metrics")]
+    metrics::counter!(
+        "pricelevel.cancellations",
+        "price" => _price.to_string(),
+        "reason" => _reason.as_str()
+    )
+    .increment(1);
+}
@@ -1,4 +1,5 @@
 mod logger;
+pub(crate) mod metrics;
 mod uuid;
 
 pub use logger::setup_logger;
@@ -4,7 +4,10 @@
    Date: 28/3/25
 ******************************************************************************/
 
-use pricelevel::{Order, OrderCommon, OrderId, PriceLevel, Side, TimeInForce};
+use pricelevel::{
+    IcebergRefresh, Order, OrderCommon, OrderId, Price, PriceLevel, Quantity, Side, TimeInForce,
+    Timestamp,
+};
 
 #[cfg(test)]
 mod integration_tests {
@@ -21,6 +24,9 @@ mod integration_tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
 
@@ -43,8 +49,14 @@ mod integration_tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
             reserve_quantity: 150,
+            original_display: 50,
+            refresh_strategy: IcebergRefresh::default(),
+            last_reveal_ms: None,
         };
 
         assert_eq!(order.id(), OrderId::from_u64(2));
@@ -67,10 +79,13 @@ mod integration_tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
 
-        price_level.add_order(order);
+        price_level.add_order(order).unwrap();
 
         assert_eq!(price_level.price(), 10000);
         assert_eq!(price_level.display_quantity(), 75);
@@ -88,6 +103,9 @@ mod integration_tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Gtc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
 
@@ -108,6 +126,9 @@ mod integration_tests {
                 timestamp: 1616823000000,
                 time_in_force: TimeInForce::Ioc,
                 extra_fields: (),
+                parent_id: None,
+                oco_group: None,
+                suppress_in_feed: false,
             },
         };
 
@@ -115,4 +136,33 @@ mod integration_tests {
         assert!(!order.is_fill_or_kill());
         assert!(!order.is_post_only());
     }
+
+    #[test]
+    fn test_type_aliases_in_public_api() {
+        let price: Price = 10000;
+        let quantity: Quantity = 40;
+        let timestamp: Timestamp = 1616823000000;
+
+        let mut price_level = PriceLevel::new(price);
+        price_level
+            .add_order(Order::<()>::Standard {
+                common: OrderCommon {
+                    id: OrderId::from_u64(5),
+                    price,
+                    display_quantity: quantity,
+                    side: Side::Sell,
+                    timestamp,
+                    time_in_force: TimeInForce::Gtc,
+                    extra_fields: (),
+                    parent_id: None,
+                    oco_group: None,
+                    suppress_in_feed: false,
+                },
+            })
+            .unwrap();
+
+        let total: Quantity = price_level.total_quantity();
+        assert_eq!(total, quantity);
+        assert_eq!(price_level.price(), price);
+    }
 }